@@ -0,0 +1,42 @@
+//! Times cloning, simplifying and substituting into a deep expression, the operations that
+//! copy subtrees of the expression tree the most.
+//!
+//! Run with `cargo bench --bench expression`.
+
+use math_engine::equation::PartEquation;
+use std::time::Instant;
+
+const REPEATS: u32 = 100;
+
+fn measure(name: &str, eq: &PartEquation, operation: fn(&PartEquation) -> PartEquation) {
+    let start = Instant::now();
+    let mut result = PartEquation::default();
+    for _ in 0..REPEATS {
+        result = operation(eq);
+    }
+    let elapsed = start.elapsed() / REPEATS;
+
+    println!(
+        "{:<12} size = {}, time = {:?}",
+        name,
+        result.to_string().len(),
+        elapsed
+    );
+}
+
+fn main() {
+    let x: PartEquation = PartEquation::from('x');
+    let y: PartEquation = PartEquation::from('y');
+
+    // nested so that every level holds a copy of the one below
+    let mut eq: PartEquation = PartEquation::default();
+    for i in 1..=100 {
+        eq = eq + ((&x + i) * (&y - i)).pow(&PartEquation::from(2)) / (&x + &y + i);
+    }
+
+    measure("clone", &eq, |eq| eq.clone());
+    measure("simplify", &eq, |eq| eq.simplify());
+    measure("substitute", &eq, |eq| {
+        eq.substitute('y', &PartEquation::from(3))
+    });
+}
@@ -0,0 +1,93 @@
+//! Sums 10k integer constants the way the simplifier accumulates them, once by building a
+//! new `Number` for every step and once in place, and reports the time and the number of
+//! GMP allocations taken by each.
+//!
+//! Run with `cargo bench --bench number`.
+
+use math_engine::number::Number;
+use std::ffi::c_void;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+type AllocateFunction = Option<extern "C" fn(usize) -> *mut c_void>;
+type ReallocateFunction = Option<extern "C" fn(*mut c_void, usize, usize) -> *mut c_void>;
+type FreeFunction = Option<extern "C" fn(*mut c_void, usize)>;
+
+// GMP itself is linked through rug, only the memory function hooks are declared here
+extern "C" {
+    fn __gmp_get_memory_functions(
+        allocate: *mut AllocateFunction,
+        reallocate: *mut ReallocateFunction,
+        free: *mut FreeFunction,
+    );
+    fn __gmp_set_memory_functions(
+        allocate: AllocateFunction,
+        reallocate: ReallocateFunction,
+        free: FreeFunction,
+    );
+}
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static mut DEFAULT_ALLOCATE: AllocateFunction = None;
+static mut DEFAULT_REALLOCATE: ReallocateFunction = None;
+static mut DEFAULT_FREE: FreeFunction = None;
+
+extern "C" fn counting_allocate(size: usize) -> *mut c_void {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    unsafe { DEFAULT_ALLOCATE.unwrap()(size) }
+}
+
+extern "C" fn counting_reallocate(
+    ptr: *mut c_void,
+    old_size: usize,
+    new_size: usize,
+) -> *mut c_void {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    unsafe { DEFAULT_REALLOCATE.unwrap()(ptr, old_size, new_size) }
+}
+
+extern "C" fn counting_free(ptr: *mut c_void, size: usize) {
+    unsafe { DEFAULT_FREE.unwrap()(ptr, size) }
+}
+
+fn measure(name: &str, constants: &[Number], sum: fn(&[Number]) -> Number) {
+    ALLOCATIONS.store(0, Ordering::Relaxed);
+    let start = Instant::now();
+    let result = sum(constants);
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+
+    println!(
+        "{:<12} result = {}, time = {:?}, allocations = {}",
+        name, result, elapsed, allocations
+    );
+}
+
+fn main() {
+    unsafe {
+        __gmp_get_memory_functions(
+            &raw mut DEFAULT_ALLOCATE,
+            &raw mut DEFAULT_REALLOCATE,
+            &raw mut DEFAULT_FREE,
+        );
+        __gmp_set_memory_functions(
+            Some(counting_allocate),
+            Some(counting_reallocate),
+            Some(counting_free),
+        );
+    }
+
+    let constants: Vec<Number> = (1..=10_000).map(Number::from).collect();
+
+    measure("allocating", &constants, |constants| {
+        let mut constant = Number::from(0);
+        constants.iter().for_each(|x| constant = &constant + x);
+        constant
+    });
+
+    measure("in place", &constants, |constants| {
+        let mut constant = Number::from(0);
+        constants.iter().for_each(|x| constant += x);
+        constant
+    });
+}
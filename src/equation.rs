@@ -1,41 +1,69 @@
-use std::collections::HashMap;
+#[cfg(feature = "cache")]
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use std::fmt::Debug;
 use std::fmt::Display;
+use std::hash::{Hash, Hasher};
 use std::ops;
+use std::sync::Arc;
 
 use super::number::Number;
+use crate::eval::{OpCode, Program};
 use crate::math::MathError;
+use crate::polynomial::{gcd, Polynomial};
+use crate::random::{Rng, XorShift};
 
-#[derive(Clone, PartialEq, Eq)]
+/// Numeric evaluation of an expression for a single variable, see `PartEquation::compile`
+type Compiled = Box<dyn Fn(f64) -> Result<f64, MathError>>;
+
+/// Coefficient of each monomial of an expanded expression, see `PartEquation::monomials`
+type Monomials = BTreeMap<Vec<(char, i64)>, Number>;
+
+#[derive(Clone, PartialEq, Eq, Hash)]
 enum EquationComponentType {
     ConstantNode(Number),
     VariableNode(char),
     AddNode {
-        lhs: Box<EquationComponentType>,
-        rhs: Box<EquationComponentType>,
+        lhs: Arc<EquationComponentType>,
+        rhs: Arc<EquationComponentType>,
     },
     SubNode {
-        lhs: Box<EquationComponentType>,
-        rhs: Box<EquationComponentType>,
+        lhs: Arc<EquationComponentType>,
+        rhs: Arc<EquationComponentType>,
     },
     MulNode {
-        lhs: Box<EquationComponentType>,
-        rhs: Box<EquationComponentType>,
+        lhs: Arc<EquationComponentType>,
+        rhs: Arc<EquationComponentType>,
     },
     DivNode {
-        numerator: Box<EquationComponentType>,
-        denominator: Box<EquationComponentType>,
+        numerator: Arc<EquationComponentType>,
+        denominator: Arc<EquationComponentType>,
     },
     PowNode {
-        base: Box<EquationComponentType>,
-        exponent: Box<EquationComponentType>,
+        base: Arc<EquationComponentType>,
+        exponent: Arc<EquationComponentType>,
     },
     LogNode {
-        base: Box<EquationComponentType>,
-        argument: Box<EquationComponentType>,
+        base: Arc<EquationComponentType>,
+        argument: Arc<EquationComponentType>,
+    },
+    MinusNode(Arc<EquationComponentType>),
+    AbsNode(Arc<EquationComponentType>),
+    /// e ^ x
+    ExpNode(Arc<EquationComponentType>),
+    /// floor(lhs / rhs)
+    FloorDivNode {
+        lhs: Arc<EquationComponentType>,
+        rhs: Arc<EquationComponentType>,
+    },
+    /// lhs - rhs * floor(lhs / rhs), with the sign of rhs
+    ModNode {
+        lhs: Arc<EquationComponentType>,
+        rhs: Arc<EquationComponentType>,
     },
-    MinusNode(Box<EquationComponentType>),
 }
 
 impl Debug for EquationComponentType {
@@ -57,13 +85,38 @@ impl Debug for EquationComponentType {
                 write!(f, "(Log_{:?}({:?}))", base, argument)
             }
             EquationComponentType::MinusNode(value) => write!(f, "-({:?})", value),
+            EquationComponentType::AbsNode(value) => write!(f, "|{:?}|", value),
+            EquationComponentType::ExpNode(value) => write!(f, "(e ^ {:?})", value),
+            EquationComponentType::FloorDivNode { lhs, rhs } => {
+                write!(f, "floor({:?} / {:?})", lhs, rhs)
+            }
+            EquationComponentType::ModNode { lhs, rhs } => write!(f, "({:?} mod {:?})", lhs, rhs),
+        }
+    }
+}
+
+// whether `s` has a `|` outside of parentheses, inside bars it would close them early
+fn has_bare_bar(s: &str) -> bool {
+    let mut depth: usize = 0;
+    for c in s.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            '|' if depth == 0 => return true,
+            _ => {}
         }
     }
+    false
 }
 
 impl Display for EquationComponentType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            // fractions are parenthesized so that they parse back as a single constant,
+            // complex numbers so that they read as one
+            EquationComponentType::ConstantNode(i @ (Number::Rational(_) | Number::Complex(_))) => {
+                write!(f, "({})", i)
+            }
             EquationComponentType::ConstantNode(i) => write!(f, "{}", i),
             EquationComponentType::VariableNode(i) => write!(f, "{}", i),
             EquationComponentType::AddNode { lhs, rhs } => write!(f, "({} + {})", lhs, rhs),
@@ -77,23 +130,444 @@ impl Display for EquationComponentType {
                 write!(f, "({} ^ {})", base, exponent)
             }
             EquationComponentType::LogNode { base, argument } => {
-                write!(f, "(Log_{:?}({:?}))", base, argument)
+                write!(f, "log({}, {})", base, argument)
+            }
+            EquationComponentType::MinusNode(value) => match &**value {
+                // atoms need no parentheses, compound nodes already print their own
+                EquationComponentType::VariableNode(_) => write!(f, "-{}", value),
+                EquationComponentType::ConstantNode(i) if *i >= Number::from(0) => {
+                    write!(f, "-{}", value)
+                }
+                EquationComponentType::ConstantNode(_) | EquationComponentType::MinusNode(_) => {
+                    write!(f, "-({})", value)
+                }
+                _ => write!(f, "-{}", value),
+            },
+            EquationComponentType::AbsNode(value) => {
+                let value: String = value.to_string();
+                if has_bare_bar(&value) {
+                    write!(f, "|({})|", value)
+                } else {
+                    write!(f, "|{}|", value)
+                }
             }
-            EquationComponentType::MinusNode(value) => write!(f, "-({})", value),
+            EquationComponentType::ExpNode(value) => write!(f, "(e ^ {})", value),
+            EquationComponentType::FloorDivNode { lhs, rhs } => {
+                write!(f, "floor({} / {})", lhs, rhs)
+            }
+            EquationComponentType::ModNode { lhs, rhs } => write!(f, "({} mod {})", lhs, rhs),
         }
     }
 }
 
 impl EquationComponentType {
-    fn simplify(&self) -> Self {
+    // `Display` notation with only the parentheses that precedence needs, for the sides of an
+    // equation, along with the precedence of the outermost operation as in `to_infix`
+    fn to_display(&self) -> (String, u8) {
+        let wrap = |node: &EquationComponentType, min: u8| {
+            let (s, precedence) = node.to_display();
+            if precedence < min {
+                format!("({})", s)
+            } else {
+                s
+            }
+        };
+
         match self {
             EquationComponentType::ConstantNode(i) => {
+                let constant: String = i.to_string();
+                let precedence: u8 = match i {
+                    Number::Rational(_) => INFIX_PRODUCT,
+                    // a + bi
+                    Number::Complex(_) if constant.contains(' ') => INFIX_SUM,
+                    _ if constant.starts_with('-') => INFIX_UNARY,
+                    _ => INFIX_ATOM,
+                };
+                (constant, precedence)
+            }
+            EquationComponentType::VariableNode(i) => (i.to_string(), INFIX_ATOM),
+            EquationComponentType::AddNode { lhs, rhs } => (
+                format!("{} + {}", wrap(lhs, INFIX_SUM), wrap(rhs, INFIX_SUM)),
+                INFIX_SUM,
+            ),
+            EquationComponentType::SubNode { lhs, rhs } => (
+                format!("{} - {}", wrap(lhs, INFIX_SUM), wrap(rhs, INFIX_PRODUCT)),
+                INFIX_SUM,
+            ),
+            EquationComponentType::MulNode { lhs, rhs } => (
+                format!(
+                    "{} * {}",
+                    wrap(lhs, INFIX_PRODUCT),
+                    wrap(rhs, INFIX_PRODUCT)
+                ),
+                INFIX_PRODUCT,
+            ),
+            EquationComponentType::DivNode {
+                numerator,
+                denominator,
+            } => (
+                format!(
+                    "{} / {}",
+                    wrap(numerator, INFIX_PRODUCT),
+                    wrap(denominator, INFIX_UNARY)
+                ),
+                INFIX_PRODUCT,
+            ),
+            EquationComponentType::PowNode { base, exponent } => (
+                format!(
+                    "{} ^ {}",
+                    wrap(base, INFIX_ATOM),
+                    wrap(exponent, INFIX_UNARY)
+                ),
+                INFIX_POWER,
+            ),
+            EquationComponentType::LogNode { base, argument } => (
+                format!("log({}, {})", base.to_display().0, argument.to_display().0),
+                INFIX_ATOM,
+            ),
+            EquationComponentType::MinusNode(value) => {
+                (format!("-{}", wrap(value, INFIX_POWER)), INFIX_UNARY)
+            }
+            EquationComponentType::AbsNode(value) => {
+                let (value, _) = value.to_display();
+                if has_bare_bar(&value) {
+                    (format!("|({})|", value), INFIX_ATOM)
+                } else {
+                    (format!("|{}|", value), INFIX_ATOM)
+                }
+            }
+            EquationComponentType::ExpNode(value) => {
+                (format!("e ^ {}", wrap(value, INFIX_UNARY)), INFIX_POWER)
+            }
+            EquationComponentType::FloorDivNode { lhs, rhs } => (
+                format!(
+                    "floor({} / {})",
+                    wrap(lhs, INFIX_PRODUCT),
+                    wrap(rhs, INFIX_UNARY)
+                ),
+                INFIX_ATOM,
+            ),
+            EquationComponentType::ModNode { lhs, rhs } => (
+                format!(
+                    "{} mod {}",
+                    wrap(lhs, INFIX_PRODUCT),
+                    wrap(rhs, INFIX_UNARY)
+                ),
+                INFIX_PRODUCT,
+            ),
+        }
+    }
+
+    // infix with Python operators for `PartEquation::to_infix`, with the precedence of the
+    // outermost operation so that the caller knows whether to parenthesize
+    fn to_infix(&self) -> (String, u8) {
+        let wrap = |node: &EquationComponentType, min: u8| {
+            let (s, precedence) = node.to_infix();
+            if precedence < min {
+                format!("({})", s)
+            } else {
+                s
+            }
+        };
+
+        match self {
+            EquationComponentType::ConstantNode(i) => Self::constant_to_infix(i),
+            EquationComponentType::VariableNode(i) => (i.to_string(), INFIX_ATOM),
+            EquationComponentType::AddNode { lhs, rhs } => (
+                format!("{} + {}", wrap(lhs, INFIX_SUM), wrap(rhs, INFIX_SUM)),
+                INFIX_SUM,
+            ),
+            EquationComponentType::SubNode { lhs, rhs } => (
+                format!("{} - {}", wrap(lhs, INFIX_SUM), wrap(rhs, INFIX_PRODUCT)),
+                INFIX_SUM,
+            ),
+            EquationComponentType::MulNode { lhs, rhs } => (
+                format!("{}*{}", wrap(lhs, INFIX_PRODUCT), wrap(rhs, INFIX_PRODUCT)),
+                INFIX_PRODUCT,
+            ),
+            EquationComponentType::DivNode {
+                numerator,
+                denominator,
+            } => (
+                format!(
+                    "{}/{}",
+                    wrap(numerator, INFIX_PRODUCT),
+                    wrap(denominator, INFIX_UNARY)
+                ),
+                INFIX_PRODUCT,
+            ),
+            // ** is right associative and binds tighter than a unary minus on its left
+            EquationComponentType::PowNode { base, exponent } => (
+                format!(
+                    "{}**{}",
+                    wrap(base, INFIX_ATOM),
+                    wrap(exponent, INFIX_UNARY)
+                ),
+                INFIX_POWER,
+            ),
+            EquationComponentType::LogNode { base, argument } => {
+                let (argument, _) = argument.to_infix();
+                match &**base {
+                    EquationComponentType::ConstantNode(b) if *b == Number::e() => {
+                        (format!("log({})", argument), INFIX_ATOM)
+                    }
+                    base => (
+                        format!("log({}, {})", argument, base.to_infix().0),
+                        INFIX_ATOM,
+                    ),
+                }
+            }
+            EquationComponentType::MinusNode(value) => {
+                (format!("-{}", wrap(value, INFIX_POWER)), INFIX_UNARY)
+            }
+            EquationComponentType::AbsNode(value) => {
+                (format!("Abs({})", value.to_infix().0), INFIX_ATOM)
+            }
+            EquationComponentType::ExpNode(value) => {
+                (format!("exp({})", value.to_infix().0), INFIX_ATOM)
+            }
+            // floor division and modulo bind like a product in Python
+            EquationComponentType::FloorDivNode { lhs, rhs } => (
+                format!("{}//{}", wrap(lhs, INFIX_PRODUCT), wrap(rhs, INFIX_UNARY)),
+                INFIX_PRODUCT,
+            ),
+            EquationComponentType::ModNode { lhs, rhs } => (
+                format!("{}%{}", wrap(lhs, INFIX_PRODUCT), wrap(rhs, INFIX_UNARY)),
+                INFIX_PRODUCT,
+            ),
+        }
+    }
+
+    fn constant_to_infix(constant: &Number) -> (String, u8) {
+        if *constant == Number::pi() {
+            return (String::from("pi"), INFIX_ATOM);
+        }
+        if *constant == Number::e() {
+            return (String::from("E"), INFIX_ATOM);
+        }
+
+        let precedence: u8 = if *constant < Number::from(0) {
+            INFIX_UNARY
+        } else {
+            INFIX_ATOM
+        };
+        match constant {
+            Number::Rational(_) => (constant.to_string(), INFIX_PRODUCT),
+            Number::Complex(c) => {
+                let real: Number = Number::Float(c.real().clone()).normalize();
+                let imag: Number = Number::Float(c.imag().clone()).normalize();
+                (format!("({} + {}*I)", real, imag), INFIX_ATOM)
+            }
+            _ => (constant.to_string(), precedence),
+        }
+    }
+}
+
+// precedence of the operations in `to_infix` and `to_display`, from loosest to tightest
+const INFIX_SUM: u8 = 1;
+const INFIX_PRODUCT: u8 = 2;
+const INFIX_UNARY: u8 = 3;
+const INFIX_POWER: u8 = 4;
+const INFIX_ATOM: u8 = 5;
+
+// relative difference up to which the sides of an equation count as equal in `Equation::check`
+// when either of them is a float
+const CHECK_TOLERANCE: f64 = 1e-9;
+
+/// Variable spelled out by name, a single letter or the name of a greek letter like `theta`
+fn named_variable(name: &str) -> Option<char> {
+    const GREEK: [(&str, char); 23] = [
+        ("alpha", 'α'),
+        ("beta", 'β'),
+        ("gamma", 'γ'),
+        ("delta", 'δ'),
+        ("epsilon", 'ε'),
+        ("zeta", 'ζ'),
+        ("eta", 'η'),
+        ("theta", 'θ'),
+        ("iota", 'ι'),
+        ("kappa", 'κ'),
+        ("lambda", 'λ'),
+        ("mu", 'μ'),
+        ("nu", 'ν'),
+        ("xi", 'ξ'),
+        ("omicron", 'ο'),
+        ("rho", 'ρ'),
+        ("sigma", 'σ'),
+        ("tau", 'τ'),
+        ("upsilon", 'υ'),
+        ("phi", 'φ'),
+        ("chi", 'χ'),
+        ("psi", 'ψ'),
+        ("omega", 'ω'),
+    ];
+
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(c);
+    }
+    GREEK.iter().find(|(n, _)| *n == name).map(|(_, c)| *c)
+}
+
+// x ^ (n/2) for odd n
+fn is_square_root(exponent: &Number) -> bool {
+    match exponent {
+        Number::Rational(e) => *e.denom() == 2,
+        _ => false,
+    }
+}
+
+/// Expression as a key of the `simplify` cache. Numbers of different kinds must not share an
+/// entry: `x + 2.5` and `x + 5/2` are equal but do not simplify to the same tree
+#[cfg(feature = "cache")]
+struct SimplifyKey(EquationComponentType);
+
+#[cfg(feature = "cache")]
+impl PartialEq for SimplifyKey {
+    fn eq(&self, other: &Self) -> bool {
+        SimplifyKey::same_tree(&self.0, &other.0)
+    }
+}
+
+#[cfg(feature = "cache")]
+impl SimplifyKey {
+    // `==` on the trees, except that constants must also be the same kind of number
+    fn same_tree(a: &EquationComponentType, b: &EquationComponentType) -> bool {
+        match (a, b) {
+            (EquationComponentType::ConstantNode(a), EquationComponentType::ConstantNode(b)) => {
+                a == b && std::mem::discriminant(a) == std::mem::discriminant(b)
+            }
+            (EquationComponentType::VariableNode(a), EquationComponentType::VariableNode(b)) => {
+                a == b
+            }
+            (
+                EquationComponentType::AddNode { lhs: a, rhs: b },
+                EquationComponentType::AddNode { lhs: c, rhs: d },
+            )
+            | (
+                EquationComponentType::SubNode { lhs: a, rhs: b },
+                EquationComponentType::SubNode { lhs: c, rhs: d },
+            )
+            | (
+                EquationComponentType::MulNode { lhs: a, rhs: b },
+                EquationComponentType::MulNode { lhs: c, rhs: d },
+            )
+            | (
+                EquationComponentType::DivNode {
+                    numerator: a,
+                    denominator: b,
+                },
+                EquationComponentType::DivNode {
+                    numerator: c,
+                    denominator: d,
+                },
+            )
+            | (
+                EquationComponentType::PowNode {
+                    base: a,
+                    exponent: b,
+                },
+                EquationComponentType::PowNode {
+                    base: c,
+                    exponent: d,
+                },
+            )
+            | (
+                EquationComponentType::LogNode {
+                    base: a,
+                    argument: b,
+                },
+                EquationComponentType::LogNode {
+                    base: c,
+                    argument: d,
+                },
+            )
+            | (
+                EquationComponentType::FloorDivNode { lhs: a, rhs: b },
+                EquationComponentType::FloorDivNode { lhs: c, rhs: d },
+            )
+            | (
+                EquationComponentType::ModNode { lhs: a, rhs: b },
+                EquationComponentType::ModNode { lhs: c, rhs: d },
+            ) => SimplifyKey::same_tree(a, c) && SimplifyKey::same_tree(b, d),
+            (EquationComponentType::MinusNode(a), EquationComponentType::MinusNode(b))
+            | (EquationComponentType::AbsNode(a), EquationComponentType::AbsNode(b))
+            | (EquationComponentType::ExpNode(a), EquationComponentType::ExpNode(b)) => {
+                SimplifyKey::same_tree(a, b)
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(feature = "cache")]
+impl Eq for SimplifyKey {}
+
+#[cfg(feature = "cache")]
+impl Hash for SimplifyKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+/// The cache is dropped whole once it holds this many expressions
+#[cfg(feature = "cache")]
+const SIMPLIFY_CACHE_CAPACITY: usize = 4096;
+
+#[cfg(feature = "cache")]
+thread_local! {
+    static SIMPLIFY_CACHE: RefCell<HashMap<SimplifyKey, EquationComponentType>> =
+        RefCell::new(HashMap::new());
+    static SIMPLIFY_CACHE_HITS: Cell<usize> = const { Cell::new(0) };
+}
+
+impl EquationComponentType {
+    #[cfg(not(feature = "cache"))]
+    fn simplify(&self) -> Self {
+        self.simplify_with(&SimplifyOptions::default())
+    }
+
+    // only the expression passed in is cached, not the subtrees simplified on the way: hashing
+    // every subtree again at each level of the recursion costs more than it saves
+    #[cfg(feature = "cache")]
+    fn simplify(&self) -> Self {
+        let key: SimplifyKey = SimplifyKey(self.clone());
+
+        let cached: Option<Self> = SIMPLIFY_CACHE.with(|cache| cache.borrow().get(&key).cloned());
+        if let Some(result) = cached {
+            SIMPLIFY_CACHE_HITS.with(|hits| hits.set(hits.get() + 1));
+            return result;
+        }
+
+        let result: Self = self.simplify_with(&SimplifyOptions::default());
+        SIMPLIFY_CACHE.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            if cache.len() >= SIMPLIFY_CACHE_CAPACITY {
+                cache.clear();
+            }
+            cache.insert(key, result.clone());
+        });
+        result
+    }
+
+    fn simplify_with(&self, options: &SimplifyOptions) -> Self {
+        match self {
+            EquationComponentType::ConstantNode(i @ Number::Float(_)) if !options.fold_floats => {
                 EquationComponentType::ConstantNode(i.clone())
             }
+            EquationComponentType::ConstantNode(i) => {
+                EquationComponentType::ConstantNode(i.clone().normalize())
+            }
 
             EquationComponentType::VariableNode(i) => EquationComponentType::VariableNode(*i),
 
             EquationComponentType::AddNode { lhs: _, rhs: _ } => {
+                if options.combine_fractions {
+                    if let Some(result) = self.combine_fractions(options) {
+                        return result;
+                    }
+                }
+
                 // TODO: implement the following simplification `log(x) + log(x) = log(2x)`
 
                 // TODO: implement the following simplification `x^n + x^n = 2*x^n`
@@ -105,18 +579,21 @@ impl EquationComponentType {
                 let mut constants: Vec<Number> = Vec::new();
                 let mut nodes: Vec<EquationComponentType> = Vec::new();
 
-                self.extract(&mut variables, &mut constants, &mut nodes);
+                self.extract(&mut variables, &mut constants, &mut nodes, options);
+                options.split_floats(&mut constants, &mut nodes);
 
                 // calculating the constant's value
                 let mut constant: Number = Number::from(0);
-                constants.iter().for_each(|x| constant = &constant + x);
+                constants.iter().for_each(|x| constant += x);
+                // mixed-type sums like 1/2 + 1/2 stay exact, as the integer 1
+                let constant: Number = constant.normalize();
 
                 // no constant required if sum is 0
                 let constant_is_zero: bool = constant == Number::from(0);
 
                 // updating nodes with MulNode if there are many AddNode's over a variable
                 // example: x + x -> 2 * x
-                let mut variable_occurrence: HashMap<char, i64> = HashMap::new();
+                let mut variable_occurrence: HashMap<char, usize> = HashMap::new();
 
                 for i in variables.iter() {
                     match variable_occurrence.get(&i) {
@@ -130,8 +607,8 @@ impl EquationComponentType {
                 for (i, k) in variable_occurrence.into_iter() {
                     if k > 1 {
                         variables_nodes.push(EquationComponentType::MulNode {
-                            lhs: Box::new(EquationComponentType::VariableNode(i)),
-                            rhs: Box::new(EquationComponentType::ConstantNode(Number::from(k))),
+                            lhs: Arc::new(EquationComponentType::VariableNode(i)),
+                            rhs: Arc::new(EquationComponentType::ConstantNode(Number::from(k))),
                         });
                     } else {
                         variables_nodes.push(EquationComponentType::VariableNode(i));
@@ -148,7 +625,7 @@ impl EquationComponentType {
                 variables_nodes.retain(|node_to_simplify| {
                     if let EquationComponentType::MulNode { lhs, rhs } = node_to_simplify {
                         if let EquationComponentType::VariableNode(v) = **lhs {
-                            if let EquationComponentType::ConstantNode(c) = *(*rhs).clone() {
+                            if let EquationComponentType::ConstantNode(c) = (**rhs).clone() {
                                 // variable * constant
                                 match variable_occurrence.remove(&v) {
                                     Some(x) => {
@@ -167,7 +644,7 @@ impl EquationComponentType {
                                 return false;
                             }
                         } else if let EquationComponentType::VariableNode(v) = **rhs {
-                            if let EquationComponentType::ConstantNode(c) = *(*lhs).clone() {
+                            if let EquationComponentType::ConstantNode(c) = (**lhs).clone() {
                                 // constant * variable
                                 match variable_occurrence.remove(&v) {
                                     Some(x) => {
@@ -230,10 +707,14 @@ impl EquationComponentType {
 
                 for (k, v) in variable_occurrence.into_iter() {
                     if let EquationComponentType::ConstantNode(o) = v.clone() {
+                        // x - x -> 0
+                        if o == Number::from(0) {
+                            continue;
+                        }
                         if o != Number::from(1) {
                             variables_nodes.push(EquationComponentType::MulNode {
-                                lhs: Box::new(EquationComponentType::VariableNode(k)),
-                                rhs: Box::new(v),
+                                lhs: Arc::new(v),
+                                rhs: Arc::new(EquationComponentType::VariableNode(k)),
                             });
                         } else {
                             variables_nodes.push(EquationComponentType::VariableNode(k));
@@ -251,26 +732,26 @@ impl EquationComponentType {
 
                 if variables_nodes.len() == 1 {
                     if constant_is_zero {
-                        return variables_nodes.pop().unwrap().simplify();
+                        return variables_nodes.pop().unwrap().simplify_with(options);
                     }
 
                     return EquationComponentType::AddNode {
-                        lhs: Box::new(EquationComponentType::ConstantNode(constant)),
-                        rhs: Box::new(variables_nodes.pop().unwrap().simplify()),
+                        lhs: Arc::new(EquationComponentType::ConstantNode(constant)),
+                        rhs: Arc::new(variables_nodes.pop().unwrap().simplify_with(options)),
                     };
                 }
 
                 let mut base_node: EquationComponentType = EquationComponentType::AddNode {
-                    lhs: Box::new(variables_nodes.pop().unwrap().simplify()),
-                    rhs: Box::new(variables_nodes.pop().unwrap().simplify()),
+                    lhs: Arc::new(variables_nodes.pop().unwrap().simplify_with(options)),
+                    rhs: Arc::new(variables_nodes.pop().unwrap().simplify_with(options)),
                 };
 
                 loop {
                     match variables_nodes.pop() {
                         Some(i) => {
                             base_node = EquationComponentType::AddNode {
-                                lhs: Box::new(i.simplify()),
-                                rhs: Box::new(base_node),
+                                lhs: Arc::new(i.simplify_with(options)),
+                                rhs: Arc::new(base_node),
                             };
                         }
                         None => break,
@@ -281,47 +762,54 @@ impl EquationComponentType {
                     return base_node;
                 }
                 return EquationComponentType::AddNode {
-                    lhs: Box::new(EquationComponentType::ConstantNode(constant)),
-                    rhs: Box::new(base_node),
+                    lhs: Arc::new(EquationComponentType::ConstantNode(constant)),
+                    rhs: Arc::new(base_node),
                 };
             } // End EquationComponentType::AddNode
 
             EquationComponentType::SubNode { lhs, rhs } => {
                 // TODO: implement the following simplifications `log(x) - log(y) = log(x/y)`
 
-                let lhs: EquationComponentType = lhs.simplify();
-                let rhs: EquationComponentType = rhs.simplify();
+                let lhs: EquationComponentType = lhs.simplify_with(options);
+                let rhs: EquationComponentType = rhs.simplify_with(options);
 
                 return EquationComponentType::AddNode {
-                    lhs: Box::new(lhs),
-                    rhs: Box::new(EquationComponentType::MinusNode(Box::new(rhs)).simplify()),
+                    lhs: Arc::new(lhs),
+                    rhs: Arc::new(
+                        EquationComponentType::MinusNode(Arc::new(rhs)).simplify_with(options),
+                    ),
                 }
-                .simplify();
+                .simplify_with(options);
             } // End EquationComponentType::SubNode
 
             EquationComponentType::MulNode { lhs: _, rhs: _ } => {
+                if options.distribute {
+                    if let Some(result) = self.distribute(options) {
+                        return result;
+                    }
+                }
+
                 // extracting simplified child nodes
                 let mut variables: Vec<char> = Vec::new();
                 let mut constants: Vec<Number> = Vec::new();
                 let mut nodes: Vec<EquationComponentType> = Vec::new();
 
-                self.extract(&mut variables, &mut constants, &mut nodes);
+                self.extract(&mut variables, &mut constants, &mut nodes, options);
+                options.split_floats(&mut constants, &mut nodes);
 
                 // calculating the constant's value
                 let mut constant = Number::from(1);
-                constants.iter().for_each(|x| constant = &constant * x);
+                constants.iter().for_each(|x| constant *= x);
+                let mut constant: Number = constant.normalize();
 
                 // return 0, if constant is 0
                 if constant == Number::from(0) {
                     return EquationComponentType::ConstantNode(Number::from(0));
                 }
 
-                // no constant required if product is 1
-                let constant_is_one: bool = constant == Number::from(1);
-
                 // updating node with PowNode of there are many MulNode's over a variable
                 // example: x * x -> x ^ 2
-                let mut variable_occurrence: HashMap<char, i64> = HashMap::new();
+                let mut variable_occurrence: HashMap<char, usize> = HashMap::new();
 
                 for i in variables.iter() {
                     match variable_occurrence.get(&i) {
@@ -335,8 +823,8 @@ impl EquationComponentType {
                 for (i, k) in variable_occurrence.into_iter() {
                     if k > 1 {
                         variables_nodes.push(EquationComponentType::PowNode {
-                            base: Box::new(EquationComponentType::VariableNode(i)),
-                            exponent: Box::new(EquationComponentType::ConstantNode(Number::from(
+                            base: Arc::new(EquationComponentType::VariableNode(i)),
+                            exponent: Arc::new(EquationComponentType::ConstantNode(Number::from(
                                 k,
                             ))),
                         });
@@ -354,7 +842,7 @@ impl EquationComponentType {
                 variables_nodes.retain(|node_to_simplify| {
                     if let EquationComponentType::PowNode { base, exponent } = node_to_simplify {
                         if let EquationComponentType::VariableNode(v) = **base {
-                            if let EquationComponentType::ConstantNode(c) = *(*exponent).clone() {
+                            if let EquationComponentType::ConstantNode(c) = (**exponent).clone() {
                                 // variable * constant
                                 match variable_occurrence.remove(&v) {
                                     Some(x) => {
@@ -399,8 +887,8 @@ impl EquationComponentType {
                     if let EquationComponentType::ConstantNode(o) = v.clone() {
                         if o != Number::from(1) {
                             variables_nodes.push(EquationComponentType::PowNode {
-                                base: Box::new(EquationComponentType::VariableNode(k)),
-                                exponent: Box::new(v),
+                                base: Arc::new(EquationComponentType::VariableNode(k)),
+                                exponent: Arc::new(v),
                             });
                         } else {
                             variables_nodes.push(EquationComponentType::VariableNode(k));
@@ -408,6 +896,21 @@ impl EquationComponentType {
                     }
                 }
 
+                // (2 ^ x) * (2 ^ y) -> 2 ^ (x + y), powers that fold to a number join the constant
+                variables_nodes = Self::combine_powers(variables_nodes, options)
+                    .into_iter()
+                    .filter(|node| match node {
+                        EquationComponentType::ConstantNode(i) => {
+                            constant = (&constant * i).normalize();
+                            false
+                        }
+                        _ => true,
+                    })
+                    .collect();
+
+                // no constant required if product is 1
+                let constant_is_one: bool = constant == Number::from(1);
+
                 // TODO: implement the following simplifications
                 // x * (y + z) = x * y + x * z
 
@@ -418,25 +921,25 @@ impl EquationComponentType {
 
                 if variables_nodes.len() == 1 {
                     if constant_is_one {
-                        return variables_nodes.pop().unwrap().simplify();
+                        return variables_nodes.pop().unwrap().simplify_with(options);
                     }
                     return EquationComponentType::MulNode {
-                        lhs: Box::new(EquationComponentType::ConstantNode(constant)),
-                        rhs: Box::new(variables_nodes.pop().unwrap().simplify()),
+                        lhs: Arc::new(EquationComponentType::ConstantNode(constant)),
+                        rhs: Arc::new(variables_nodes.pop().unwrap().simplify_with(options)),
                     };
                 }
 
                 let mut base_node: EquationComponentType = EquationComponentType::MulNode {
-                    lhs: Box::new(variables_nodes.pop().unwrap().simplify()),
-                    rhs: Box::new(variables_nodes.pop().unwrap().simplify()),
+                    lhs: Arc::new(variables_nodes.pop().unwrap().simplify_with(options)),
+                    rhs: Arc::new(variables_nodes.pop().unwrap().simplify_with(options)),
                 };
 
                 loop {
                     match variables_nodes.pop() {
                         Some(i) => {
                             base_node = EquationComponentType::MulNode {
-                                lhs: Box::new(i.simplify()),
-                                rhs: Box::new(base_node),
+                                lhs: Arc::new(i.simplify_with(options)),
+                                rhs: Arc::new(base_node),
                             };
                         }
                         None => break,
@@ -447,8 +950,8 @@ impl EquationComponentType {
                     return base_node;
                 }
                 return EquationComponentType::MulNode {
-                    lhs: Box::new(EquationComponentType::ConstantNode(constant)),
-                    rhs: Box::new(base_node),
+                    lhs: Arc::new(EquationComponentType::ConstantNode(constant)),
+                    rhs: Arc::new(base_node),
                 };
             } // End EquationComponentType::MulNod
 
@@ -460,37 +963,125 @@ impl EquationComponentType {
 
                 // TODO: implement the following simplifications `x^3 / x^2 = x`
 
-                // TODO: implement the following simplifications `x / (y / z) = (x * z) / y`
+                let numerator: EquationComponentType = numerator.simplify_with(options);
+                let denominator: EquationComponentType = denominator.simplify_with(options);
+
+                // (x^2 - 1) / (x - 1) -> x + 1, the reduced fraction has nothing left to cancel
+                if let Some(result) = Self::divide_polynomials(&numerator, &denominator) {
+                    return result.simplify_with(options);
+                }
+
+                // x / 1 -> x, solving divides by the coefficient of the variable which is often 1
+                if denominator == EquationComponentType::ConstantNode(Number::from(1)) {
+                    return numerator;
+                }
+
+                // x / x -> 1 and 0 / x -> 0, taking x to be nonzero as CAS usually do
+                if denominator != EquationComponentType::ConstantNode(Number::from(0)) {
+                    // simplify alone leaves terms in no fixed order, (x + y) / (y + x) needs `order`
+                    if numerator.order() == denominator.order() {
+                        return EquationComponentType::ConstantNode(Number::from(1));
+                    }
+                    if numerator == EquationComponentType::ConstantNode(Number::from(0)) {
+                        return EquationComponentType::ConstantNode(Number::from(0));
+                    }
+                }
+
+                // x / (y / z) -> (x * z) / y and (x / y) / z -> x / (y * z),
+                // also for fractions among the factors of a product
+                if let Some(result) = Self::lift_fractions(&numerator, &denominator) {
+                    return result.simplify_with(options);
+                }
+
+                // (a ^ m) / (a ^ n) -> a ^ (m - n), (a ^ 2) / (a ^ 5) -> 1 / (a ^ 3)
+                if let Some(result) = Self::cancel_powers(&numerator, &denominator, options) {
+                    return result;
+                }
+
+                // x / (2/3) -> x * (3/2)
+                if let EquationComponentType::ConstantNode(i @ Number::Rational(_)) = &denominator {
+                    if !matches!(numerator, EquationComponentType::ConstantNode(_)) {
+                        return EquationComponentType::MulNode {
+                            lhs: Arc::new(numerator),
+                            rhs: Arc::new(EquationComponentType::ConstantNode(
+                                (Number::from(1) / i.clone()).normalize(),
+                            )),
+                        }
+                        .simplify_with(options);
+                    }
+                }
 
-                let numerator: EquationComponentType = numerator.simplify();
-                let denominator: EquationComponentType = denominator.simplify();
+                // x / -y -> -x / y
+                if let Some(denominator) = denominator.without_leading_minus() {
+                    return EquationComponentType::DivNode {
+                        numerator: Arc::new(EquationComponentType::MinusNode(Arc::new(numerator))),
+                        denominator: Arc::new(denominator),
+                    }
+                    .simplify_with(options);
+                }
 
                 if let EquationComponentType::ConstantNode(i) = numerator {
                     if let EquationComponentType::ConstantNode(j) = denominator {
+                        // a division by zero is kept for `evaluate` to report
+                        if !options.folds(&i, &j) || j == Number::from(0) {
+                            return EquationComponentType::DivNode {
+                                numerator: Arc::new(EquationComponentType::ConstantNode(i)),
+                                denominator: Arc::new(EquationComponentType::ConstantNode(j)),
+                            };
+                        }
                         let result = i / j;
-                        return EquationComponentType::ConstantNode(result);
+                        return EquationComponentType::ConstantNode(result.normalize());
                     } else {
                         return EquationComponentType::DivNode {
-                            numerator: Box::new(EquationComponentType::ConstantNode(i)),
-                            denominator: Box::new(denominator),
+                            numerator: Arc::new(EquationComponentType::ConstantNode(i)),
+                            denominator: Arc::new(denominator),
                         };
                     }
                 } else {
                     return EquationComponentType::DivNode {
-                        numerator: Box::new(numerator),
-                        denominator: Box::new(denominator),
+                        numerator: Arc::new(numerator),
+                        denominator: Arc::new(denominator),
                     };
                 }
             } // End EquationComponentType::DivNode
 
             EquationComponentType::PowNode { base, exponent } => {
-                let base: EquationComponentType = base.simplify();
-                let exponent: EquationComponentType = exponent.simplify();
+                let base: EquationComponentType = base.simplify_with(options);
+                let exponent: EquationComponentType = exponent.simplify_with(options);
 
                 // x^1 -> x
                 if let EquationComponentType::ConstantNode(i) = exponent.clone() {
                     if i == Number::from(1) {
-                        return base.simplify();
+                        return base.simplify_with(options);
+                    }
+                }
+
+                // e^x -> exp(x)
+                if base == EquationComponentType::ConstantNode(Number::e()) {
+                    return EquationComponentType::ExpNode(Arc::new(exponent))
+                        .simplify_with(options);
+                }
+
+                // ((x ^ 2) ^ (1/2)) -> |x|, an even power drops the sign of x
+                if let EquationComponentType::PowNode {
+                    base: lvalue,
+                    exponent: rvalue,
+                } = &base
+                {
+                    if let (
+                        EquationComponentType::ConstantNode(y),
+                        EquationComponentType::ConstantNode(z),
+                    ) = (&**rvalue, &exponent)
+                    {
+                        let is_even = |n: &Number| n.to_i64().is_some_and(|n| n % 2 == 0);
+                        let product: Number = (y * z).normalize();
+                        if is_even(y) && !is_even(&product) {
+                            return EquationComponentType::PowNode {
+                                base: Arc::new(EquationComponentType::AbsNode(lvalue.clone())),
+                                exponent: Arc::new(EquationComponentType::ConstantNode(product)),
+                            }
+                            .simplify_with(options);
+                        }
                     }
                 }
 
@@ -502,123 +1093,371 @@ impl EquationComponentType {
                 {
                     return EquationComponentType::PowNode {
                         base: lvalue,
-                        exponent: Box::new(EquationComponentType::MulNode {
+                        exponent: Arc::new(EquationComponentType::MulNode {
                             lhs: rvalue,
-                            rhs: Box::new(exponent),
+                            rhs: Arc::new(exponent),
                         }),
-                    };
+                    }
+                    .simplify_with(options);
                 } else if let EquationComponentType::ConstantNode(i) = base {
                     if let EquationComponentType::ConstantNode(j) = exponent {
+                        // (-4) ^ (1/2) = 2i, other roots of negative numbers are left unevaluated,
+                        // as is 0 ^ -1
+                        let undefined: bool = (i.signum() == Number::from(-1)
+                            && !j.is_integer()
+                            && !is_square_root(&j))
+                            || (i.signum() == Number::from(0) && j.signum() == Number::from(-1));
+                        if undefined || !options.folds(&i, &j) {
+                            return EquationComponentType::PowNode {
+                                base: Arc::new(EquationComponentType::ConstantNode(i)),
+                                exponent: Arc::new(EquationComponentType::ConstantNode(j)),
+                            };
+                        }
                         let result = i.pow(&j);
-                        return EquationComponentType::ConstantNode(result);
+                        return EquationComponentType::ConstantNode(result.normalize());
                     } else {
                         return EquationComponentType::PowNode {
-                            base: Box::new(EquationComponentType::ConstantNode(i)),
-                            exponent: Box::new(exponent),
+                            base: Arc::new(EquationComponentType::ConstantNode(i)),
+                            exponent: Arc::new(exponent),
                         };
                     }
                 } else if let EquationComponentType::ConstantNode(i) = base {
                     if let EquationComponentType::ConstantNode(j) = exponent {
                         let result = i.pow(&j);
-                        return EquationComponentType::ConstantNode(result);
+                        return EquationComponentType::ConstantNode(result.normalize());
                     } else {
                         return EquationComponentType::PowNode {
-                            base: Box::new(EquationComponentType::ConstantNode(i)),
-                            exponent: Box::new(exponent),
+                            base: Arc::new(EquationComponentType::ConstantNode(i)),
+                            exponent: Arc::new(exponent),
                         };
                     }
                 } else {
                     return EquationComponentType::PowNode {
-                        base: Box::new(base),
-                        exponent: Box::new(exponent),
+                        base: Arc::new(base),
+                        exponent: Arc::new(exponent),
                     };
                 }
             } // End EquationComponentType::PowNode
 
             EquationComponentType::LogNode { base, argument } => {
-                // log_base(base ^ n) -> n
-                if let EquationComponentType::PowNode {
-                    base: pow_base,
-                    exponent,
-                } = *argument.clone()
-                {
-                    if pow_base.simplify().order() == base.simplify().order() {
-                        return exponent.simplify();
+                // the rules match on the simplified operands, so that a power which only
+                // appears after simplification is still rewritten in this pass
+                let base: EquationComponentType = base.simplify_with(options);
+                let argument: EquationComponentType = argument.simplify_with(options);
+
+                match argument {
+                    // ln(exp(x)) -> x
+                    EquationComponentType::ExpNode(value)
+                        if base == EquationComponentType::ConstantNode(Number::e()) =>
+                    {
+                        Arc::unwrap_or_clone(value)
                     }
-                }
 
-                // log(x^n) -> n*log(x)
-                if let EquationComponentType::PowNode {
-                    base: base_pow,
-                    exponent,
-                } = *argument.clone()
-                {
-                    return EquationComponentType::MulNode {
-                        lhs: Box::new(exponent.simplify()),
-                        rhs: Box::new(EquationComponentType::LogNode {
-                            base: Box::new(base.simplify()),
-                            argument: Box::new(base_pow.simplify()),
+                    // log_base(base ^ n) -> n
+                    EquationComponentType::PowNode {
+                        base: pow_base,
+                        exponent,
+                    } if pow_base.order() == base.order() => Arc::unwrap_or_clone(exponent),
+
+                    // log(x^n) -> n*log(x)
+                    EquationComponentType::PowNode {
+                        base: base_pow,
+                        exponent,
+                    } => EquationComponentType::MulNode {
+                        lhs: exponent,
+                        rhs: Arc::new(EquationComponentType::LogNode {
+                            base: Arc::new(base),
+                            argument: base_pow,
                         }),
-                    };
-                }
+                    }
+                    .simplify_with(options),
 
-                return EquationComponentType::LogNode {
-                    base: Box::new(base.simplify()),
-                    argument: Box::new(argument.simplify()),
-                };
+                    argument => EquationComponentType::LogNode {
+                        base: Arc::new(base),
+                        argument: Arc::new(argument),
+                    },
+                }
             } // End EquationComponentType::LogNode
 
             EquationComponentType::MinusNode(value) => {
-                let value: EquationComponentType = value.simplify();
+                // unwrap the whole chain at once: an even number of negations cancels out,
+                // an odd number leaves a single one
+                let mut value: &EquationComponentType = value;
+                let mut negated: bool = true;
+                while let EquationComponentType::MinusNode(inner) = value {
+                    value = inner;
+                    negated = !negated;
+                }
+
+                let value: EquationComponentType = value.simplify_with(options);
+                if !negated {
+                    return value;
+                }
 
                 match value {
                     EquationComponentType::ConstantNode(i) => {
                         EquationComponentType::ConstantNode(-i)
                     }
                     EquationComponentType::AddNode { lhs, rhs } => EquationComponentType::AddNode {
-                        lhs: Box::new(EquationComponentType::MinusNode(lhs)),
-                        rhs: Box::new(EquationComponentType::MinusNode(rhs)),
+                        lhs: Arc::new(EquationComponentType::MinusNode(lhs)),
+                        rhs: Arc::new(EquationComponentType::MinusNode(rhs)),
                     }
-                    .simplify(),
+                    .simplify_with(options),
                     EquationComponentType::SubNode { lhs, rhs } => EquationComponentType::SubNode {
-                        lhs: Box::new(EquationComponentType::MinusNode(lhs)),
-                        rhs: Box::new(EquationComponentType::MinusNode(rhs)),
+                        lhs: Arc::new(EquationComponentType::MinusNode(lhs)),
+                        rhs: Arc::new(EquationComponentType::MinusNode(rhs)),
                     }
-                    .simplify(),
-                    EquationComponentType::MulNode { lhs, rhs } => EquationComponentType::MulNode {
-                        lhs: Box::new(EquationComponentType::MinusNode(lhs)),
-                        rhs: rhs,
+                    .simplify_with(options),
+                    // -(3 * x) -> (-3 * x), -(x * (y * 3)) -> (-3 * (x * y)),
+                    // -(x * y) -> (-1 * (x * y)), -(-1 * x) -> x
+                    product @ EquationComponentType::MulNode { .. } => {
+                        let (coefficient, factors) = product.term_factors();
+                        if factors.is_empty() {
+                            return EquationComponentType::ConstantNode(-coefficient);
+                        }
+
+                        EquationComponentType::MulNode {
+                            lhs: Arc::new(EquationComponentType::ConstantNode(-coefficient)),
+                            rhs: Arc::new(EquationComponentType::construct_from_products(factors)),
+                        }
+                        .simplify_with(options)
                     }
-                    .simplify(),
                     EquationComponentType::DivNode {
                         numerator,
                         denominator,
                     } => EquationComponentType::DivNode {
-                        numerator: Box::new(EquationComponentType::MinusNode(numerator)),
+                        numerator: Arc::new(EquationComponentType::MinusNode(numerator)),
                         denominator: denominator,
                     }
-                    .simplify(),
-                    EquationComponentType::MinusNode(i) => *i,
-                    n => EquationComponentType::MinusNode(Box::new(n.simplify())),
+                    .simplify_with(options),
+                    EquationComponentType::MinusNode(i) => Arc::unwrap_or_clone(i),
+                    n => EquationComponentType::MinusNode(Arc::new(n.simplify_with(options))),
+                }
+            }
+
+            EquationComponentType::AbsNode(value) => match value.simplify_with(options) {
+                EquationComponentType::ConstantNode(i) => {
+                    if i.signum() == Number::from(-1) {
+                        return EquationComponentType::ConstantNode(-i);
+                    }
+                    EquationComponentType::ConstantNode(i)
+                }
+                // |-x| -> |x|, x may itself be dropped by the rules below
+                EquationComponentType::MinusNode(i) => {
+                    EquationComponentType::AbsNode(i).simplify_with(options)
+                }
+                // ||x|| -> |x|
+                i @ EquationComponentType::AbsNode(_) => i,
+                // |exp(x)| -> exp(x)
+                i @ EquationComponentType::ExpNode(_) => i,
+                n => EquationComponentType::AbsNode(Arc::new(n)),
+            },
+
+            EquationComponentType::ExpNode(value) => match value.simplify_with(options) {
+                // exp(ln(x)) -> x
+                EquationComponentType::LogNode { base, argument }
+                    if *base == EquationComponentType::ConstantNode(Number::e()) =>
+                {
+                    Arc::unwrap_or_clone(argument)
+                }
+                EquationComponentType::ConstantNode(i)
+                    if options.fold_floats || i == Number::from(0) =>
+                {
+                    EquationComponentType::ConstantNode(i.exp().normalize())
+                }
+                n => EquationComponentType::ExpNode(Arc::new(n)),
+            },
+
+            EquationComponentType::FloorDivNode { lhs, rhs } => {
+                let lhs: EquationComponentType = lhs.simplify_with(options);
+                let rhs: EquationComponentType = rhs.simplify_with(options);
+
+                // floor(7 / 2) -> 3, a division by zero is kept for `evaluate` to report
+                if let (
+                    EquationComponentType::ConstantNode(i),
+                    EquationComponentType::ConstantNode(j),
+                ) = (&lhs, &rhs)
+                {
+                    if options.folds(i, j) {
+                        if let Ok(result) = i.floor_div(j) {
+                            return EquationComponentType::ConstantNode(result);
+                        }
+                    }
+                }
+                EquationComponentType::FloorDivNode {
+                    lhs: Arc::new(lhs),
+                    rhs: Arc::new(rhs),
+                }
+            }
+
+            EquationComponentType::ModNode { lhs, rhs } => {
+                let lhs: EquationComponentType = lhs.simplify_with(options);
+                let rhs: EquationComponentType = rhs.simplify_with(options);
+
+                // 7 mod 3 -> 1
+                if let (
+                    EquationComponentType::ConstantNode(i),
+                    EquationComponentType::ConstantNode(j),
+                ) = (&lhs, &rhs)
+                {
+                    if options.folds(i, j) {
+                        if let Ok(result) = i.rem(j) {
+                            return EquationComponentType::ConstantNode(result);
+                        }
+                    }
+                }
+                EquationComponentType::ModNode {
+                    lhs: Arc::new(lhs),
+                    rhs: Arc::new(rhs),
                 }
             }
         }
     }
 
-    fn order(&self) -> Self {
-        let sort = |terms: &mut Vec<EquationComponentType>, weights: &mut Vec<Number>| {
-            for i in 0..terms.len() {
-                let mut highest = i;
-                for j in i + 1..terms.len() {
-                    if weights[highest] < weights[j] {
-                        highest = j;
-                    }
+    // -y -> y, -3 -> 3, (-3 * y) -> (3 * y); None if there is no leading minus to drop
+    fn without_leading_minus(&self) -> Option<Self> {
+        match self {
+            EquationComponentType::MinusNode(value) => Some(value.as_ref().clone()),
+            EquationComponentType::ConstantNode(i) if i.signum() == Number::from(-1) => {
+                Some(EquationComponentType::ConstantNode(-i))
+            }
+            EquationComponentType::MulNode { lhs, rhs } => match (lhs.as_ref(), rhs.as_ref()) {
+                (EquationComponentType::ConstantNode(i), n)
+                | (n, EquationComponentType::ConstantNode(i))
+                    if i.signum() == Number::from(-1) =>
+                {
+                    Some(EquationComponentType::MulNode {
+                        lhs: Arc::new(EquationComponentType::ConstantNode(-i)),
+                        rhs: Arc::new(n.clone()),
+                    })
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    // x * (y + z) -> (x * y) + (x * z), the result is simplified as a whole
+    fn distribute(&self, options: &SimplifyOptions) -> Option<Self> {
+        if let EquationComponentType::MulNode { lhs, rhs } = self {
+            let (first, second, factor) = match (&**lhs, &**rhs) {
+                (EquationComponentType::AddNode { lhs, rhs }, factor)
+                | (factor, EquationComponentType::AddNode { lhs, rhs }) => {
+                    (lhs.clone(), rhs.clone(), factor.clone())
                 }
-                if i != highest {
-                    weights.swap(i, highest);
-                    terms.swap(i, highest);
+                _ => return None,
+            };
+
+            return Some(
+                EquationComponentType::AddNode {
+                    lhs: Arc::new(EquationComponentType::MulNode {
+                        lhs: first,
+                        rhs: Arc::new(factor.clone()),
+                    }),
+                    rhs: Arc::new(EquationComponentType::MulNode {
+                        lhs: second,
+                        rhs: Arc::new(factor),
+                    }),
+                }
+                .simplify_with(options),
+            );
+        }
+        None
+    }
+
+    // a / b + c / d -> ((a * d) + (c * b)) / (b * d), the result is simplified as a whole
+    fn combine_fractions(&self, options: &SimplifyOptions) -> Option<Self> {
+        if let EquationComponentType::AddNode { lhs, rhs } = self {
+            let (lhs, rhs) = ((**lhs).clone(), (**rhs).clone());
+
+            if !matches!(lhs, EquationComponentType::DivNode { .. })
+                && !matches!(rhs, EquationComponentType::DivNode { .. })
+            {
+                return None;
+            }
+
+            let split = |node: EquationComponentType| match node {
+                EquationComponentType::DivNode {
+                    numerator,
+                    denominator,
+                } => (
+                    Arc::unwrap_or_clone(numerator),
+                    Arc::unwrap_or_clone(denominator),
+                ),
+                // 1/2 is a fraction too, so that x/3 + 1/2 gives (2x + 3) / 6
+                EquationComponentType::ConstantNode(i @ Number::Rational(_)) => (
+                    EquationComponentType::ConstantNode(i.numerator()),
+                    EquationComponentType::ConstantNode(i.denominator()),
+                ),
+                node => (node, EquationComponentType::ConstantNode(Number::from(1))),
+            };
+            let (a, b) = split(lhs);
+            let (c, d) = split(rhs);
+
+            // x/4 + 3/4 -> (x + 3) / 4 over the least common denominator, not 16
+            if let (
+                EquationComponentType::ConstantNode(b @ Number::Integer(_)),
+                EquationComponentType::ConstantNode(d @ Number::Integer(_)),
+            ) = (&b, &d)
+            {
+                if b.signum() == Number::from(1) && d.signum() == Number::from(1) {
+                    let lcm: Number = (Number::from(1) / b.clone())
+                        .lcm_denominators(&(Number::from(1) / d.clone()));
+                    let scaled = |node: EquationComponentType, denominator: &Number| {
+                        EquationComponentType::MulNode {
+                            lhs: Arc::new(node),
+                            rhs: Arc::new(EquationComponentType::ConstantNode(
+                                (&lcm / denominator).normalize(),
+                            )),
+                        }
+                    };
+                    return Some(
+                        EquationComponentType::DivNode {
+                            numerator: Arc::new(EquationComponentType::AddNode {
+                                lhs: Arc::new(scaled(a, b)),
+                                rhs: Arc::new(scaled(c, d)),
+                            }),
+                            denominator: Arc::new(EquationComponentType::ConstantNode(lcm.clone())),
+                        }
+                        .simplify_with(options),
+                    );
                 }
             }
+
+            return Some(
+                EquationComponentType::DivNode {
+                    numerator: Arc::new(EquationComponentType::AddNode {
+                        lhs: Arc::new(EquationComponentType::MulNode {
+                            lhs: Arc::new(a),
+                            rhs: Arc::new(d.clone()),
+                        }),
+                        rhs: Arc::new(EquationComponentType::MulNode {
+                            lhs: Arc::new(c),
+                            rhs: Arc::new(b.clone()),
+                        }),
+                    }),
+                    denominator: Arc::new(EquationComponentType::MulNode {
+                        lhs: Arc::new(b),
+                        rhs: Arc::new(d),
+                    }),
+                }
+                .simplify_with(options),
+            );
+        }
+        None
+    }
+
+    fn order(&self) -> Self {
+        // highest weight first, terms of equal weight by their printed form so that the order
+        // does not depend on the input: x + |x| and |x| + x must order the same
+        let sort = |terms: Vec<EquationComponentType>| -> Vec<EquationComponentType> {
+            // the printed form is computed once per term, not on every comparison
+            let mut weighted: Vec<(Number, String, EquationComponentType)> = terms
+                .into_iter()
+                .map(|term| (term.calculate_weight(), term.to_string(), term))
+                .collect();
+            weighted.sort_by(|(i, a, _), (j, b, _)| j.cmp(i).then_with(|| a.cmp(b)));
+            weighted.into_iter().map(|(_, _, term)| term).collect()
         };
         match self {
             EquationComponentType::ConstantNode(i) => {
@@ -630,11 +1469,7 @@ impl EquationComponentType {
                 lhs.separate_terms(&mut terms);
                 rhs.separate_terms(&mut terms);
 
-                let mut weights: Vec<Number> = Vec::new();
-                for i in 0..terms.len() {
-                    weights.push(terms[i].calculate_weight());
-                }
-                sort(&mut terms, &mut weights);
+                let terms: Vec<EquationComponentType> = sort(terms);
                 EquationComponentType::construct_from_terms(terms)
             }
             EquationComponentType::MulNode { lhs, rhs } => {
@@ -642,36 +1477,55 @@ impl EquationComponentType {
                 lhs.separate_products(&mut terms);
                 rhs.separate_products(&mut terms);
 
-                let mut weights: Vec<Number> = Vec::new();
-                for i in 0..terms.len() {
-                    weights.push(terms[i].calculate_weight());
-                }
-                sort(&mut terms, &mut weights);
-                EquationComponentType::construct_from_products(terms)
+                // the numeric coefficient leads, 3 * x and never x * 3
+                let (mut factors, others): (
+                    Vec<EquationComponentType>,
+                    Vec<EquationComponentType>,
+                ) = terms
+                    .into_iter()
+                    .partition(|i| matches!(i, EquationComponentType::ConstantNode(_)));
+                factors.extend(sort(others));
+                EquationComponentType::construct_from_products(factors)
             }
             EquationComponentType::SubNode { lhs, rhs } => EquationComponentType::SubNode {
                 // ???: This not should not exist after the simplify step
-                lhs: Box::new(lhs.order()),
-                rhs: Box::new(rhs.order()),
+                lhs: Arc::new(lhs.order()),
+                rhs: Arc::new(rhs.order()),
             },
             EquationComponentType::DivNode {
                 numerator,
                 denominator,
             } => EquationComponentType::DivNode {
-                numerator: Box::new(numerator.order()),
-                denominator: Box::new(denominator.order()),
+                numerator: Arc::new(numerator.order()),
+                denominator: Arc::new(denominator.order()),
             },
             EquationComponentType::PowNode { base, exponent } => EquationComponentType::PowNode {
-                base: Box::new(base.order()),
-                exponent: Box::new(exponent.order()),
+                base: Arc::new(base.order()),
+                exponent: Arc::new(exponent.order()),
             },
             EquationComponentType::LogNode { base, argument } => EquationComponentType::LogNode {
-                base: Box::new(base.order()),
-                argument: Box::new(argument.order()),
+                base: Arc::new(base.order()),
+                argument: Arc::new(argument.order()),
             },
             EquationComponentType::MinusNode(i) => {
-                EquationComponentType::MinusNode(Box::new(i.order()))
+                EquationComponentType::MinusNode(Arc::new(i.order()))
+            }
+            EquationComponentType::AbsNode(i) => {
+                EquationComponentType::AbsNode(Arc::new(i.order()))
             }
+            EquationComponentType::ExpNode(i) => {
+                EquationComponentType::ExpNode(Arc::new(i.order()))
+            }
+            EquationComponentType::FloorDivNode { lhs, rhs } => {
+                EquationComponentType::FloorDivNode {
+                    lhs: Arc::new(lhs.order()),
+                    rhs: Arc::new(rhs.order()),
+                }
+            }
+            EquationComponentType::ModNode { lhs, rhs } => EquationComponentType::ModNode {
+                lhs: Arc::new(lhs.order()),
+                rhs: Arc::new(rhs.order()),
+            },
         }
     }
 
@@ -691,18 +1545,41 @@ impl EquationComponentType {
             EquationComponentType::DivNode {
                 numerator,
                 denominator,
-            } => numerator.calculate_weight() / denominator.calculate_weight(),
-            EquationComponentType::PowNode { base, exponent } => {
-                base.calculate_weight().pow(&exponent.calculate_weight())
-            }
-            EquationComponentType::LogNode {
-                base: _,
-                argument: _,
             } => {
-                // TODO: implement
-                todo!();
+                let denominator: Number = denominator.calculate_weight();
+                if denominator == Number::from(0) {
+                    return Number::from(0);
+                }
+                numerator.calculate_weight() / denominator
             }
+            // a NaN weight would leave the term wherever it started, keep weights real
+            EquationComponentType::PowNode { base, exponent } => base
+                .calculate_weight()
+                .real_pow(&exponent.calculate_weight())
+                .unwrap_or(Number::from(0)),
+            // log of a non-positive weight, or to base 1, has no real value and weighs 0,
+            // like a PowNode without a real weight
+            EquationComponentType::LogNode { base, argument } => argument
+                .calculate_weight()
+                .log(&base.calculate_weight())
+                .unwrap_or(Number::from(0)),
             EquationComponentType::MinusNode(i) => -(i.calculate_weight()),
+            EquationComponentType::AbsNode(i) => {
+                let weight: Number = i.calculate_weight();
+                if weight.signum() == Number::from(-1) {
+                    return -weight;
+                }
+                weight
+            }
+            EquationComponentType::ExpNode(i) => i.calculate_weight().exp(),
+            EquationComponentType::FloorDivNode { lhs, rhs } => lhs
+                .calculate_weight()
+                .floor_div(&rhs.calculate_weight())
+                .unwrap_or(Number::from(0)),
+            EquationComponentType::ModNode { lhs, rhs } => lhs
+                .calculate_weight()
+                .rem(&rhs.calculate_weight())
+                .unwrap_or(Number::from(0)),
         }
     }
 
@@ -713,8 +1590,8 @@ impl EquationComponentType {
             terms.remove(0)
         } else {
             EquationComponentType::AddNode {
-                lhs: Box::new(terms.remove(0)),
-                rhs: Box::new(EquationComponentType::construct_from_terms(terms)),
+                lhs: Arc::new(terms.remove(0)),
+                rhs: Arc::new(EquationComponentType::construct_from_terms(terms)),
             }
         }
     }
@@ -726,8 +1603,8 @@ impl EquationComponentType {
             terms.remove(0)
         } else {
             EquationComponentType::MulNode {
-                lhs: Box::new(terms.remove(0)),
-                rhs: Box::new(EquationComponentType::construct_from_products(terms)),
+                lhs: Arc::new(terms.remove(0)),
+                rhs: Arc::new(EquationComponentType::construct_from_products(terms)),
             }
         }
     }
@@ -742,1548 +1619,6901 @@ impl EquationComponentType {
         };
     }
 
-    fn separate_products(&self, products: &mut Vec<EquationComponentType>) {
+    // terms of a sum as written, each with whether it is subtracted: x - (3 * y) + -2 gives
+    // x, (3 * y) and 2 with the last two subtracted
+    fn signed_terms(&self, negated: bool, terms: &mut Vec<(bool, EquationComponentType)>) {
         match self {
-            EquationComponentType::MulNode { lhs, rhs } => {
-                lhs.separate_products(products);
-                rhs.separate_products(products);
+            EquationComponentType::AddNode { lhs, rhs } => {
+                lhs.signed_terms(negated, terms);
+                rhs.signed_terms(negated, terms);
             }
-            n => products.push(n.order()),
-        }
-    }
-
-    fn substitute(&self, variable: char, value: &EquationComponentType) -> Self {
-        match self {
-            EquationComponentType::ConstantNode(i) => {
-                EquationComponentType::ConstantNode(i.clone())
+            EquationComponentType::SubNode { lhs, rhs } => {
+                lhs.signed_terms(negated, terms);
+                rhs.signed_terms(!negated, terms);
             }
-            EquationComponentType::VariableNode(i) => {
-                if *i == variable {
-                    return value.clone();
-                }
-                return EquationComponentType::VariableNode(*i);
+            EquationComponentType::MinusNode(value) => value.signed_terms(!negated, terms),
+            EquationComponentType::ConstantNode(i)
+                if !matches!(i, Number::Complex(_)) && i.signum() == Number::from(-1) =>
+            {
+                terms.push((!negated, EquationComponentType::ConstantNode(-i)))
             }
-            EquationComponentType::AddNode { lhs, rhs } => EquationComponentType::AddNode {
-                lhs: Box::new(lhs.substitute(variable, value)),
-                rhs: Box::new(rhs.substitute(variable, value)),
-            },
-            EquationComponentType::SubNode { lhs, rhs } => EquationComponentType::SubNode {
-                lhs: Box::new(lhs.substitute(variable, value)),
-                rhs: Box::new(rhs.substitute(variable, value)),
-            },
-            EquationComponentType::MulNode { lhs, rhs } => EquationComponentType::MulNode {
-                lhs: Box::new(lhs.substitute(variable, value)),
-                rhs: Box::new(rhs.substitute(variable, value)),
-            },
-            EquationComponentType::DivNode {
-                numerator,
-                denominator,
-            } => EquationComponentType::DivNode {
-                numerator: Box::new(numerator.substitute(variable, value)),
-                denominator: Box::new(denominator.substitute(variable, value)),
-            },
-            EquationComponentType::PowNode { base, exponent } => EquationComponentType::PowNode {
-                base: Box::new(base.substitute(variable, value)),
-                exponent: Box::new(exponent.substitute(variable, value)),
-            },
-            EquationComponentType::LogNode { base, argument } => EquationComponentType::LogNode {
-                base: Box::new(base.substitute(variable, value)),
-                argument: Box::new(argument.substitute(variable, value)),
+            EquationComponentType::MulNode { lhs, rhs } => match lhs.as_ref() {
+                EquationComponentType::ConstantNode(i)
+                    if !matches!(i, Number::Complex(_)) && i.signum() == Number::from(-1) =>
+                {
+                    let term: EquationComponentType = if *i == Number::from(-1) {
+                        rhs.as_ref().clone()
+                    } else {
+                        EquationComponentType::MulNode {
+                            lhs: Arc::new(EquationComponentType::ConstantNode(-i)),
+                            rhs: rhs.clone(),
+                        }
+                    };
+                    terms.push((!negated, term));
+                }
+                _ => terms.push((negated, self.clone())),
             },
-            EquationComponentType::MinusNode(node) => {
-                EquationComponentType::MinusNode(Box::new(node.substitute(variable, value)))
-            }
+            n => terms.push((negated, n.clone())),
         }
     }
 
-    fn extract(
-        &self,
-        variables: &mut Vec<char>,
-        constants: &mut Vec<Number>,
-        nodes: &mut Vec<EquationComponentType>,
-    ) {
+    // numeric factor of a single term: 3 for (3 * x), -1 for -x, 1 for x
+    fn coefficient(&self) -> Number {
         match self {
-            EquationComponentType::AddNode { lhs, rhs } => {
-                match &**lhs {
-                    EquationComponentType::ConstantNode(i) => constants.push(i.clone()),
-                    EquationComponentType::VariableNode(i) => variables.push(*i),
-                    i @ EquationComponentType::AddNode { lhs: _, rhs: _ } => {
-                        i.extract(variables, constants, nodes)
-                    }
-                    n => {
-                        let m = n.simplify();
-                        match m {
-                            EquationComponentType::ConstantNode(i) => constants.push(i),
-                            EquationComponentType::VariableNode(i) => variables.push(i),
-                            i @ EquationComponentType::AddNode { lhs: _, rhs: _ } => {
-                                i.extract(variables, constants, nodes)
-                            }
-                            n => nodes.push(n),
-                        }
-                    }
-                };
+            EquationComponentType::ConstantNode(i) => i.clone(),
+            EquationComponentType::MinusNode(value) => -value.coefficient(),
+            EquationComponentType::MulNode { lhs: _, rhs: _ } => {
+                let mut products: Vec<EquationComponentType> = Vec::new();
+                self.separate_products(&mut products);
 
-                match &**rhs {
-                    EquationComponentType::ConstantNode(i) => constants.push(i.clone()),
-                    EquationComponentType::VariableNode(i) => variables.push(*i),
-                    i @ EquationComponentType::AddNode { lhs: _, rhs: _ } => {
-                        i.extract(variables, constants, nodes)
-                    }
-                    n => {
-                        let m = n.simplify();
-                        match m {
-                            EquationComponentType::ConstantNode(i) => constants.push(i),
-                            EquationComponentType::VariableNode(i) => variables.push(i),
-                            i @ EquationComponentType::AddNode { lhs: _, rhs: _ } => {
-                                i.extract(variables, constants, nodes)
-                            }
-                            n => nodes.push(n),
-                        }
+                let mut coefficient: Number = Number::from(1);
+                for i in products.iter() {
+                    if let EquationComponentType::ConstantNode(c) = i {
+                        coefficient *= c;
                     }
-                };
-            } // End EquationComponentType::AddNode
+                }
+                coefficient
+            }
+            _ => Number::from(1),
+        }
+    }
 
-            EquationComponentType::MulNode { lhs, rhs } => {
-                match &**lhs {
-                    EquationComponentType::ConstantNode(i) => constants.push(i.clone()),
-                    EquationComponentType::VariableNode(i) => variables.push(*i),
-                    i @ EquationComponentType::MulNode { lhs: _, rhs: _ } => {
-                        i.extract(variables, constants, nodes)
-                    }
-                    n => {
-                        let m = n.simplify();
+    // numerator / denominator as a single fraction if either has fractions among its factors
+    fn lift_fractions(
+        numerator: &EquationComponentType,
+        denominator: &EquationComponentType,
+    ) -> Option<Self> {
+        let mut numerator_factors: Vec<EquationComponentType> = Vec::new();
+        let mut denominator_factors: Vec<EquationComponentType> = Vec::new();
+        numerator.separate_products(&mut numerator_factors);
+        denominator.separate_products(&mut denominator_factors);
+
+        let is_fraction =
+            |f: &EquationComponentType| matches!(f, EquationComponentType::DivNode { .. });
+        if !numerator_factors.iter().any(is_fraction)
+            && !denominator_factors.iter().any(is_fraction)
+        {
+            return None;
+        }
 
-                        match m {
-                            EquationComponentType::ConstantNode(i) => constants.push(i),
-                            EquationComponentType::VariableNode(i) => variables.push(i),
-                            i @ EquationComponentType::MulNode { lhs: _, rhs: _ } => {
-                                i.extract(variables, constants, nodes)
-                            }
-                            n => nodes.push(n),
-                        }
-                    }
+        let mut top: Vec<EquationComponentType> = Vec::new();
+        let mut bottom: Vec<EquationComponentType> = Vec::new();
+        for (factors, flipped) in [(numerator_factors, false), (denominator_factors, true)] {
+            for f in factors.into_iter() {
+                let (up, down) = match f {
+                    EquationComponentType::DivNode {
+                        numerator,
+                        denominator,
+                    } => (
+                        Arc::unwrap_or_clone(numerator),
+                        Some(Arc::unwrap_or_clone(denominator)),
+                    ),
+                    f => (f, None),
                 };
+                if flipped {
+                    bottom.push(up);
+                    top.extend(down);
+                } else {
+                    top.push(up);
+                    bottom.extend(down);
+                }
+            }
+        }
 
-                match &**rhs {
-                    EquationComponentType::ConstantNode(i) => constants.push(i.clone()),
-                    EquationComponentType::VariableNode(i) => variables.push(*i),
-                    i @ EquationComponentType::MulNode { lhs: _, rhs: _ } => {
-                        i.extract(variables, constants, nodes)
-                    }
-                    n => {
-                        let m = n.simplify();
+        let product = |factors: Vec<EquationComponentType>| {
+            factors
+                .into_iter()
+                .reduce(|lhs, rhs| EquationComponentType::MulNode {
+                    lhs: Arc::new(lhs),
+                    rhs: Arc::new(rhs),
+                })
+                .unwrap_or(EquationComponentType::ConstantNode(Number::from(1)))
+        };
 
-                        match m {
-                            EquationComponentType::ConstantNode(i) => constants.push(i),
-                            EquationComponentType::VariableNode(i) => variables.push(i),
-                            i @ EquationComponentType::MulNode { lhs: _, rhs: _ } => {
-                                i.extract(variables, constants, nodes)
-                            }
-                            n => nodes.push(n),
-                        }
-                    }
-                };
-            } // End EquationComponentType::MulNode
-            _ => return,
-        }
+        Some(EquationComponentType::DivNode {
+            numerator: Arc::new(product(top)),
+            denominator: Arc::new(product(bottom)),
+        })
     }
-}
 
-#[derive(Debug, Clone)]
-pub struct PartEquation {
-    eq: EquationComponentType,
-}
+    // numeric coefficient and the other factors of a single term
+    fn term_factors(&self) -> (Number, Vec<EquationComponentType>) {
+        if let EquationComponentType::MinusNode(value) = self {
+            let (coefficient, factors) = value.term_factors();
+            return (-coefficient, factors);
+        }
 
-impl PartEquation {
-    pub fn substitute(&self, variable: char, value: &PartEquation) -> PartEquation {
-        PartEquation {
-            eq: self.eq.substitute(variable, &value.eq).simplify().order(),
+        let mut products: Vec<EquationComponentType> = Vec::new();
+        self.separate_products(&mut products);
+
+        let mut coefficient: Number = Number::from(1);
+        let mut factors: Vec<EquationComponentType> = Vec::new();
+        for i in products.into_iter() {
+            match i {
+                EquationComponentType::ConstantNode(c) => coefficient *= &c,
+                n => factors.push(n),
+            }
         }
+        (coefficient, factors)
     }
 
-    fn simplify(&self) -> Self {
-        PartEquation {
-            eq: self.eq.simplify().order(),
+    // x * y + x * z -> x * (y + z), 2 * x + 4 -> 2 * (x + 2).
+    // Pulls out the factors shared by every term and the gcd of integer coefficients,
+    // returns None if there is no common factor
+    fn factor(&self) -> Option<Self> {
+        let mut terms: Vec<EquationComponentType> = Vec::new();
+        self.separate_terms(&mut terms);
+        if terms.len() < 2 {
+            return None;
         }
-    }
 
-    pub fn pow(&self, exponent: &PartEquation) -> Self {
-        PartEquation {
-            eq: EquationComponentType::PowNode {
-                base: Box::new(self.eq.clone()),
-                exponent: Box::new(exponent.eq.clone()),
+        let mut terms: Vec<(Number, Vec<EquationComponentType>)> =
+            terms.iter().map(|t| t.term_factors()).collect();
+
+        let mut common: Vec<EquationComponentType> = terms[0].1.clone();
+        for (_, factors) in terms.iter().skip(1) {
+            let mut remaining: Vec<EquationComponentType> = factors.clone();
+            common.retain(|f| match remaining.iter().position(|r| r == f) {
+                Some(position) => {
+                    remaining.remove(position);
+                    true
+                }
+                None => false,
+            });
+        }
+
+        let mut divisor: i64 = 0;
+        for (coefficient, _) in terms.iter() {
+            match coefficient
+                .to_i64()
+                .and_then(|c| i64::try_from(gcd(divisor, c)).ok())
+            {
+                Some(c) => divisor = c,
+                None => {
+                    divisor = 1;
+                    break;
+                }
             }
-            .simplify()
-            .order(),
         }
-    }
-}
+        let divisor: Number = Number::from(divisor.max(1));
 
-impl Display for PartEquation {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.eq)
-    }
-}
+        if common.is_empty() && divisor == Number::from(1) {
+            return None;
+        }
 
-impl PartialEq for PartEquation {
-    fn eq(&self, other: &Self) -> bool {
-        self.eq.simplify().order() == other.eq.simplify().order()
-    }
-}
+        let mut sum: Option<EquationComponentType> = None;
+        for (coefficient, factors) in terms.iter_mut() {
+            for f in common.iter() {
+                if let Some(position) = factors.iter().position(|r| r == f) {
+                    factors.remove(position);
+                }
+            }
 
-impl Eq for PartEquation {}
+            let mut term: EquationComponentType = EquationComponentType::ConstantNode(
+                (coefficient.clone() / divisor.clone()).normalize(),
+            );
+            for f in factors.drain(..) {
+                term = EquationComponentType::MulNode {
+                    lhs: Arc::new(term),
+                    rhs: Arc::new(f),
+                };
+            }
 
-impl From<char> for PartEquation {
-    fn from(value: char) -> Self {
-        PartEquation {
-            eq: EquationComponentType::VariableNode(value),
+            sum = Some(match sum {
+                Some(sum) => EquationComponentType::AddNode {
+                    lhs: Arc::new(sum),
+                    rhs: Arc::new(term),
+                },
+                None => term,
+            });
         }
-    }
-}
 
-impl From<i8> for PartEquation {
-    fn from(value: i8) -> Self {
-        PartEquation {
-            eq: EquationComponentType::ConstantNode(Number::from(value)),
+        let mut product: EquationComponentType = EquationComponentType::ConstantNode(divisor);
+        for f in common.into_iter() {
+            product = EquationComponentType::MulNode {
+                lhs: Arc::new(product),
+                rhs: Arc::new(f),
+            };
         }
-    }
-}
 
-impl From<i16> for PartEquation {
-    fn from(value: i16) -> Self {
-        PartEquation {
-            eq: EquationComponentType::ConstantNode(Number::from(value)),
-        }
+        Some(EquationComponentType::MulNode {
+            lhs: Arc::new(product.simplify()),
+            rhs: Arc::new(sum?.simplify()),
+        })
     }
-}
 
-impl From<i32> for PartEquation {
-    fn from(value: i32) -> Self {
-        PartEquation {
-            eq: EquationComponentType::ConstantNode(Number::from(value)),
+    fn separate_products(&self, products: &mut Vec<EquationComponentType>) {
+        match self {
+            EquationComponentType::MulNode { lhs, rhs } => {
+                lhs.separate_products(products);
+                rhs.separate_products(products);
+            }
+            n => products.push(n.order()),
         }
     }
-}
 
-impl From<i64> for PartEquation {
-    fn from(value: i64) -> Self {
-        PartEquation {
-            eq: EquationComponentType::ConstantNode(Number::from(value)),
+    fn substitute(&self, variable: char, value: &EquationComponentType) -> Self {
+        match self {
+            EquationComponentType::ConstantNode(i) => {
+                EquationComponentType::ConstantNode(i.clone())
+            }
+            EquationComponentType::VariableNode(i) => {
+                if *i == variable {
+                    return value.clone();
+                }
+                return EquationComponentType::VariableNode(*i);
+            }
+            EquationComponentType::AddNode { lhs, rhs } => EquationComponentType::AddNode {
+                lhs: Arc::new(lhs.substitute(variable, value)),
+                rhs: Arc::new(rhs.substitute(variable, value)),
+            },
+            EquationComponentType::SubNode { lhs, rhs } => EquationComponentType::SubNode {
+                lhs: Arc::new(lhs.substitute(variable, value)),
+                rhs: Arc::new(rhs.substitute(variable, value)),
+            },
+            EquationComponentType::MulNode { lhs, rhs } => EquationComponentType::MulNode {
+                lhs: Arc::new(lhs.substitute(variable, value)),
+                rhs: Arc::new(rhs.substitute(variable, value)),
+            },
+            EquationComponentType::DivNode {
+                numerator,
+                denominator,
+            } => EquationComponentType::DivNode {
+                numerator: Arc::new(numerator.substitute(variable, value)),
+                denominator: Arc::new(denominator.substitute(variable, value)),
+            },
+            EquationComponentType::PowNode { base, exponent } => EquationComponentType::PowNode {
+                base: Arc::new(base.substitute(variable, value)),
+                exponent: Arc::new(exponent.substitute(variable, value)),
+            },
+            EquationComponentType::LogNode { base, argument } => EquationComponentType::LogNode {
+                base: Arc::new(base.substitute(variable, value)),
+                argument: Arc::new(argument.substitute(variable, value)),
+            },
+            EquationComponentType::MinusNode(node) => {
+                EquationComponentType::MinusNode(Arc::new(node.substitute(variable, value)))
+            }
+            EquationComponentType::AbsNode(node) => {
+                EquationComponentType::AbsNode(Arc::new(node.substitute(variable, value)))
+            }
+            EquationComponentType::ExpNode(node) => {
+                EquationComponentType::ExpNode(Arc::new(node.substitute(variable, value)))
+            }
+            EquationComponentType::FloorDivNode { lhs, rhs } => {
+                EquationComponentType::FloorDivNode {
+                    lhs: Arc::new(lhs.substitute(variable, value)),
+                    rhs: Arc::new(rhs.substitute(variable, value)),
+                }
+            }
+            EquationComponentType::ModNode { lhs, rhs } => EquationComponentType::ModNode {
+                lhs: Arc::new(lhs.substitute(variable, value)),
+                rhs: Arc::new(rhs.substitute(variable, value)),
+            },
         }
     }
-}
 
-impl From<i128> for PartEquation {
-    fn from(value: i128) -> Self {
-        PartEquation {
-            eq: EquationComponentType::ConstantNode(Number::from(value)),
+    fn contains(&self, sub: &EquationComponentType) -> bool {
+        if self == sub {
+            return true;
         }
-    }
-}
 
-impl From<u8> for PartEquation {
-    fn from(value: u8) -> Self {
-        PartEquation {
-            eq: EquationComponentType::ConstantNode(Number::from(value)),
+        match self {
+            EquationComponentType::ConstantNode(_) => false,
+            EquationComponentType::VariableNode(_) => false,
+            EquationComponentType::AddNode { lhs, rhs } => lhs.contains(sub) || rhs.contains(sub),
+            EquationComponentType::SubNode { lhs, rhs } => lhs.contains(sub) || rhs.contains(sub),
+            EquationComponentType::MulNode { lhs, rhs } => lhs.contains(sub) || rhs.contains(sub),
+            EquationComponentType::DivNode {
+                numerator,
+                denominator,
+            } => numerator.contains(sub) || denominator.contains(sub),
+            EquationComponentType::PowNode { base, exponent } => {
+                base.contains(sub) || exponent.contains(sub)
+            }
+            EquationComponentType::LogNode { base, argument } => {
+                base.contains(sub) || argument.contains(sub)
+            }
+            EquationComponentType::MinusNode(value) => value.contains(sub),
+            EquationComponentType::AbsNode(value) => value.contains(sub),
+            EquationComponentType::ExpNode(value) => value.contains(sub),
+            EquationComponentType::FloorDivNode { lhs, rhs }
+            | EquationComponentType::ModNode { lhs, rhs } => lhs.contains(sub) || rhs.contains(sub),
         }
     }
-}
 
-impl From<u16> for PartEquation {
-    fn from(value: u16) -> Self {
-        PartEquation {
-            eq: EquationComponentType::ConstantNode(Number::from(value)),
+    fn replace(&self, target: &EquationComponentType, replacement: &EquationComponentType) -> Self {
+        if self == target {
+            return replacement.clone();
         }
-    }
-}
 
-impl From<u32> for PartEquation {
-    fn from(value: u32) -> Self {
-        PartEquation {
-            eq: EquationComponentType::ConstantNode(Number::from(value)),
-        }
-    }
-}
-
-impl From<u64> for PartEquation {
-    fn from(value: u64) -> Self {
-        PartEquation {
-            eq: EquationComponentType::ConstantNode(Number::from(value)),
-        }
-    }
-}
-
-impl From<u128> for PartEquation {
-    fn from(value: u128) -> Self {
-        PartEquation {
-            eq: EquationComponentType::ConstantNode(Number::from(value)),
-        }
-    }
-}
-
-impl From<f32> for PartEquation {
-    fn from(value: f32) -> Self {
-        PartEquation {
-            eq: EquationComponentType::ConstantNode(Number::from(value)),
-        }
-    }
-}
-
-impl From<f64> for PartEquation {
-    fn from(value: f64) -> Self {
-        PartEquation {
-            eq: EquationComponentType::ConstantNode(Number::from(value)),
-        }
-    }
-}
-
-#[derive(Debug, Clone)]
-pub struct Equation {
-    lhs: EquationComponentType,
-    rhs: EquationComponentType,
-}
-
-enum AntiOperations {
-    AddLHS,
-    AddRHS,
-    SubLHS,
-    SubRHS,
-    MulNumerator,
-    MulDenominator,
-    DivLHS,
-    DivRHS,
-    PowLHS,
-    PowRHS,
-    LogLHS,
-    LogRHS,
-    Minus,
-}
-
-impl Equation {
-    pub fn new(lhs: &PartEquation, rhs: &PartEquation) -> Self {
-        Equation {
-            lhs: lhs.eq.clone(),
-            rhs: rhs.eq.clone(),
+        match self {
+            EquationComponentType::ConstantNode(i) => {
+                EquationComponentType::ConstantNode(i.clone())
+            }
+            EquationComponentType::VariableNode(i) => EquationComponentType::VariableNode(*i),
+            EquationComponentType::AddNode { lhs, rhs } => EquationComponentType::AddNode {
+                lhs: Arc::new(lhs.replace(target, replacement)),
+                rhs: Arc::new(rhs.replace(target, replacement)),
+            },
+            EquationComponentType::SubNode { lhs, rhs } => EquationComponentType::SubNode {
+                lhs: Arc::new(lhs.replace(target, replacement)),
+                rhs: Arc::new(rhs.replace(target, replacement)),
+            },
+            EquationComponentType::MulNode { lhs, rhs } => EquationComponentType::MulNode {
+                lhs: Arc::new(lhs.replace(target, replacement)),
+                rhs: Arc::new(rhs.replace(target, replacement)),
+            },
+            EquationComponentType::DivNode {
+                numerator,
+                denominator,
+            } => EquationComponentType::DivNode {
+                numerator: Arc::new(numerator.replace(target, replacement)),
+                denominator: Arc::new(denominator.replace(target, replacement)),
+            },
+            EquationComponentType::PowNode { base, exponent } => EquationComponentType::PowNode {
+                base: Arc::new(base.replace(target, replacement)),
+                exponent: Arc::new(exponent.replace(target, replacement)),
+            },
+            EquationComponentType::LogNode { base, argument } => EquationComponentType::LogNode {
+                base: Arc::new(base.replace(target, replacement)),
+                argument: Arc::new(argument.replace(target, replacement)),
+            },
+            EquationComponentType::MinusNode(value) => {
+                EquationComponentType::MinusNode(Arc::new(value.replace(target, replacement)))
+            }
+            EquationComponentType::AbsNode(value) => {
+                EquationComponentType::AbsNode(Arc::new(value.replace(target, replacement)))
+            }
+            EquationComponentType::ExpNode(value) => {
+                EquationComponentType::ExpNode(Arc::new(value.replace(target, replacement)))
+            }
+            EquationComponentType::FloorDivNode { lhs, rhs } => {
+                EquationComponentType::FloorDivNode {
+                    lhs: Arc::new(lhs.replace(target, replacement)),
+                    rhs: Arc::new(rhs.replace(target, replacement)),
+                }
+            }
+            EquationComponentType::ModNode { lhs, rhs } => EquationComponentType::ModNode {
+                lhs: Arc::new(lhs.replace(target, replacement)),
+                rhs: Arc::new(rhs.replace(target, replacement)),
+            },
         }
     }
 
-    pub fn solve(&self, variable: char) -> Result<PartEquation, MathError> {
-        let eq: EquationComponentType = EquationComponentType::AddNode {
-            lhs: Box::new(self.lhs.simplify()),
-            rhs: Box::new(EquationComponentType::MinusNode(Box::new(
-                self.rhs.simplify(),
-            ))),
-        }
-        .simplify();
-
-        if Self::count_occurrences(&eq, variable) > 1 {
-            // TODO: Implement numeric approximation
-            return Err(MathError::NotYetImplemented);
-        } else if Self::count_occurrences(&eq, variable) == 0 {
-            return Err(MathError::EquationMismatchError);
-        }
+    fn evaluate(&self) -> Result<Number, MathError> {
+        match self {
+            EquationComponentType::ConstantNode(i) => Ok(i.clone()),
+            EquationComponentType::VariableNode(_) => Err(MathError::EquationMismatchError),
+            EquationComponentType::AddNode { lhs, rhs } => Ok(lhs.evaluate()? + rhs.evaluate()?),
+            EquationComponentType::SubNode { lhs, rhs } => Ok(lhs.evaluate()? - rhs.evaluate()?),
+            EquationComponentType::MulNode { lhs, rhs } => Ok(lhs.evaluate()? * rhs.evaluate()?),
+            EquationComponentType::DivNode {
+                numerator,
+                denominator,
+            } => {
+                let numerator: Number = numerator.evaluate()?;
+                let denominator: Number = denominator.evaluate()?;
+                if denominator == Number::from(0) {
+                    return Err(MathError::ZeroDivisionError);
+                }
+                Ok(numerator / denominator)
+            }
+            EquationComponentType::PowNode { base, exponent } => {
+                let base: Number = base.evaluate()?;
+                let exponent: Number = exponent.evaluate()?;
 
-        match Self::do_inverse(&eq, variable) {
-            Ok(result) => Ok(PartEquation { eq: result }),
-            Err(err) => Err(err),
+                // (-4) ^ (1/2) = 2i
+                if base.signum() == Number::from(-1) && is_square_root(&exponent) {
+                    return Ok(base.pow(&exponent));
+                }
+                base.real_pow(&exponent)
+            }
+            EquationComponentType::LogNode { base, argument } => {
+                argument.evaluate()?.log(&base.evaluate()?)
+            }
+            EquationComponentType::MinusNode(value) => Ok(-value.evaluate()?),
+            EquationComponentType::AbsNode(value) => {
+                let value: Number = value.evaluate()?;
+                if value.signum() == Number::from(-1) {
+                    return Ok(-value);
+                }
+                Ok(value)
+            }
+            EquationComponentType::ExpNode(value) => Ok(value.evaluate()?.exp()),
+            EquationComponentType::FloorDivNode { lhs, rhs } => {
+                lhs.evaluate()?.floor_div(&rhs.evaluate()?)
+            }
+            EquationComponentType::ModNode { lhs, rhs } => lhs.evaluate()?.rem(&rhs.evaluate()?),
         }
     }
 
-    fn count_occurrences(eq: &EquationComponentType, variable: char) -> i64 {
-        let mut occurrences = 0;
-
-        match eq {
+    // builds nested closures mirroring the tree, follows the domain rules of evaluate
+    fn compile(&self, variable: char) -> Compiled {
+        match self {
+            EquationComponentType::ConstantNode(i) => {
+                let value: f64 = i.to_f64();
+                Box::new(move |_| Ok(value))
+            }
             EquationComponentType::VariableNode(i) => {
                 if *i == variable {
-                    occurrences += 1;
+                    return Box::new(Ok);
                 }
+                Box::new(|_| Err(MathError::EquationMismatchError))
             }
             EquationComponentType::AddNode { lhs, rhs } => {
-                occurrences += Self::count_occurrences(lhs, variable);
-                occurrences += Self::count_occurrences(rhs, variable);
+                let (lhs, rhs) = (lhs.compile(variable), rhs.compile(variable));
+                Box::new(move |x| Ok(lhs(x)? + rhs(x)?))
             }
             EquationComponentType::SubNode { lhs, rhs } => {
-                occurrences += Self::count_occurrences(lhs, variable);
-                occurrences += Self::count_occurrences(rhs, variable);
+                let (lhs, rhs) = (lhs.compile(variable), rhs.compile(variable));
+                Box::new(move |x| Ok(lhs(x)? - rhs(x)?))
             }
             EquationComponentType::MulNode { lhs, rhs } => {
-                occurrences += Self::count_occurrences(lhs, variable);
-                occurrences += Self::count_occurrences(rhs, variable);
+                let (lhs, rhs) = (lhs.compile(variable), rhs.compile(variable));
+                Box::new(move |x| Ok(lhs(x)? * rhs(x)?))
             }
             EquationComponentType::DivNode {
                 numerator,
                 denominator,
             } => {
-                occurrences += Self::count_occurrences(numerator, variable);
-                occurrences += Self::count_occurrences(denominator, variable);
+                let numerator: Compiled = numerator.compile(variable);
+                let denominator: Compiled = denominator.compile(variable);
+                Box::new(move |x| {
+                    let denominator: f64 = denominator(x)?;
+                    if denominator == 0.0 {
+                        return Err(MathError::ZeroDivisionError);
+                    }
+                    Ok(numerator(x)? / denominator)
+                })
             }
             EquationComponentType::PowNode { base, exponent } => {
-                occurrences += Self::count_occurrences(base, variable);
-                occurrences += Self::count_occurrences(exponent, variable);
+                let odd_root: Option<bool> = exponent.odd_root();
+                let (base, exponent) = (base.compile(variable), exponent.compile(variable));
+                Box::new(move |x| {
+                    let base: f64 = base(x)?;
+                    let exponent: f64 = exponent(x)?;
+                    if base >= 0.0 || exponent.fract() == 0.0 {
+                        return Ok(base.powf(exponent));
+                    }
+                    match odd_root {
+                        Some(true) => Ok(-(-base).powf(exponent)),
+                        Some(false) => Ok((-base).powf(exponent)),
+                        None => Err(MathError::DomainError),
+                    }
+                })
             }
             EquationComponentType::LogNode { base, argument } => {
-                occurrences += Self::count_occurrences(base, variable);
-                occurrences += Self::count_occurrences(argument, variable);
+                let (base, argument) = (base.compile(variable), argument.compile(variable));
+                Box::new(move |x| {
+                    let base: f64 = base(x)?;
+                    let argument: f64 = argument(x)?;
+                    if argument <= 0.0 || base <= 0.0 || base == 1.0 {
+                        return Err(MathError::DomainError);
+                    }
+                    Ok(argument.ln() / base.ln())
+                })
             }
             EquationComponentType::MinusNode(value) => {
-                occurrences += Self::count_occurrences(value, variable);
+                let value: Compiled = value.compile(variable);
+                Box::new(move |x| Ok(-value(x)?))
+            }
+            EquationComponentType::AbsNode(value) => {
+                let value: Compiled = value.compile(variable);
+                Box::new(move |x| Ok(value(x)?.abs()))
+            }
+            EquationComponentType::ExpNode(value) => {
+                let value: Compiled = value.compile(variable);
+                Box::new(move |x| Ok(value(x)?.exp()))
+            }
+            EquationComponentType::FloorDivNode { lhs, rhs } => {
+                let (lhs, rhs) = (lhs.compile(variable), rhs.compile(variable));
+                Box::new(move |x| {
+                    let rhs: f64 = rhs(x)?;
+                    if rhs == 0.0 {
+                        return Err(MathError::ZeroDivisionError);
+                    }
+                    Ok((lhs(x)? / rhs).floor())
+                })
+            }
+            EquationComponentType::ModNode { lhs, rhs } => {
+                let (lhs, rhs) = (lhs.compile(variable), rhs.compile(variable));
+                Box::new(move |x| {
+                    let (lhs, rhs): (f64, f64) = (lhs(x)?, rhs(x)?);
+                    if rhs == 0.0 {
+                        return Err(MathError::ZeroDivisionError);
+                    }
+                    Ok(lhs - rhs * (lhs / rhs).floor())
+                })
             }
-            _ => {}
         }
-
-        return occurrences;
     }
 
-    fn make_anti_operations_list(
-        eq: &EquationComponentType,
-        variable: char,
-        list: &mut Vec<AntiOperations>,
-    ) -> bool {
-        match eq {
-            EquationComponentType::VariableNode(i) => {
-                if *i == variable {
-                    true
-                } else {
-                    false
+    // (-8) ^ (1/3) = -2: for a rational exponent with odd denominator, whether a negative
+    // base gives a negative result. Decided from the exact exponent
+    fn odd_root(&self) -> Option<bool> {
+        match self {
+            EquationComponentType::ConstantNode(Number::Rational(e)) => {
+                if e.denom().is_odd() {
+                    return Some(e.numer().is_odd());
                 }
+                None
             }
+            _ => None,
+        }
+    }
+
+    // appends the postfix form of the expression
+    fn lower(&self, code: &mut Vec<OpCode>) {
+        match self {
+            EquationComponentType::ConstantNode(i) => code.push(OpCode::Push(i.to_f64())),
+            EquationComponentType::VariableNode(i) => code.push(OpCode::Load(*i)),
             EquationComponentType::AddNode { lhs, rhs } => {
-                if Self::make_anti_operations_list(lhs, variable, list) {
-                    list.push(AntiOperations::SubRHS);
-                    true
-                } else if Self::make_anti_operations_list(rhs, variable, list) {
-                    list.push(AntiOperations::SubLHS);
-                    true
-                } else {
-                    false
-                }
+                lhs.lower(code);
+                rhs.lower(code);
+                code.push(OpCode::Add);
             }
             EquationComponentType::SubNode { lhs, rhs } => {
-                if Self::make_anti_operations_list(lhs, variable, list) {
-                    list.push(AntiOperations::AddRHS);
-                    true
-                } else if Self::make_anti_operations_list(rhs, variable, list) {
-                    list.push(AntiOperations::AddLHS);
-                    true
-                } else {
-                    false
-                }
+                lhs.lower(code);
+                rhs.lower(code);
+                code.push(OpCode::Sub);
             }
             EquationComponentType::MulNode { lhs, rhs } => {
-                if Self::make_anti_operations_list(lhs, variable, list) {
-                    list.push(AntiOperations::DivRHS);
-                    true
-                } else if Self::make_anti_operations_list(rhs, variable, list) {
-                    list.push(AntiOperations::DivLHS);
-                    true
-                } else {
-                    false
-                }
+                lhs.lower(code);
+                rhs.lower(code);
+                code.push(OpCode::Mul);
             }
             EquationComponentType::DivNode {
                 numerator,
                 denominator,
             } => {
-                if Self::make_anti_operations_list(numerator, variable, list) {
-                    list.push(AntiOperations::MulDenominator);
-                    true
-                } else if Self::make_anti_operations_list(denominator, variable, list) {
-                    list.push(AntiOperations::MulNumerator);
-                    true
-                } else {
-                    false
-                }
+                numerator.lower(code);
+                denominator.lower(code);
+                code.push(OpCode::Div);
             }
             EquationComponentType::PowNode { base, exponent } => {
-                if Self::make_anti_operations_list(base, variable, list) {
-                    list.push(AntiOperations::PowRHS);
-                    true
-                } else if Self::make_anti_operations_list(exponent, variable, list) {
-                    list.push(AntiOperations::LogLHS);
-                    true
-                } else {
-                    false
+                base.lower(code);
+                exponent.lower(code);
+                match exponent.odd_root() {
+                    Some(negate) => code.push(OpCode::OddRootPow(negate)),
+                    None => code.push(OpCode::Pow),
                 }
             }
             EquationComponentType::LogNode { base, argument } => {
-                if Self::make_anti_operations_list(base, variable, list) {
-                    list.push(AntiOperations::LogRHS);
-                    true
-                } else if Self::make_anti_operations_list(argument, variable, list) {
-                    list.push(AntiOperations::PowLHS);
-                    true
-                } else {
-                    false
-                }
+                base.lower(code);
+                argument.lower(code);
+                code.push(OpCode::Log);
             }
             EquationComponentType::MinusNode(value) => {
-                if Self::make_anti_operations_list(value, variable, list) {
-                    list.push(AntiOperations::Minus);
-                    true
-                } else {
-                    false
-                }
+                value.lower(code);
+                code.push(OpCode::Neg);
+            }
+            EquationComponentType::AbsNode(value) => {
+                value.lower(code);
+                code.push(OpCode::Abs);
+            }
+            EquationComponentType::ExpNode(value) => {
+                value.lower(code);
+                code.push(OpCode::Exp);
+            }
+            EquationComponentType::FloorDivNode { lhs, rhs } => {
+                lhs.lower(code);
+                rhs.lower(code);
+                code.push(OpCode::FloorDiv);
+            }
+            EquationComponentType::ModNode { lhs, rhs } => {
+                lhs.lower(code);
+                rhs.lower(code);
+                code.push(OpCode::Mod);
             }
-            _ => false,
         }
     }
 
-    fn do_inverse(
-        eq: &EquationComponentType,
-        variable: char,
-    ) -> Result<EquationComponentType, MathError> {
-        // Step 1: make a list of anti operations to perform
-        let mut anti_ops: Vec<AntiOperations> = Vec::new();
-        Self::make_anti_operations_list(&eq, variable, &mut anti_ops);
-
-        let mut result: EquationComponentType =
-            EquationComponentType::ConstantNode(Number::from(0));
-        let mut eq: EquationComponentType = eq.clone();
+    fn free_of(&self, variable: char) -> bool {
+        self.count_occurrences(variable) == 0
+    }
 
-        // Step 2: perform the anti operations`
-        for _ in 0..anti_ops.len() {
-            match anti_ops.pop().unwrap() {
-                AntiOperations::AddLHS => {
-                    if let EquationComponentType::SubNode { lhs, rhs } = eq {
-                        eq = *rhs;
-                        result = EquationComponentType::AddNode {
-                            lhs: Box::new(result),
-                            rhs: Box::new(EquationComponentType::MinusNode(lhs)),
-                        }
-                    } else {
-                        return Err(MathError::InternalError);
-                    }
-                }
-                AntiOperations::AddRHS => {
-                    if let EquationComponentType::SubNode { lhs, rhs } = eq {
-                        eq = *lhs;
-                        result = EquationComponentType::AddNode {
-                            lhs: Box::new(result),
-                            rhs: rhs,
-                        }
-                    } else {
-                        return Err(MathError::InternalError);
-                    }
-                }
-                AntiOperations::SubLHS => {
-                    if let EquationComponentType::AddNode { lhs, rhs } = eq {
-                        eq = *rhs;
-                        result = EquationComponentType::SubNode {
-                            lhs: Box::new(result),
-                            rhs: lhs,
-                        }
-                    } else {
-                        return Err(MathError::InternalError);
-                    }
+    fn collect_variables(&self, variables: &mut Vec<char>) {
+        match self {
+            EquationComponentType::ConstantNode(_) => {}
+            EquationComponentType::VariableNode(i) => {
+                if !variables.contains(i) {
+                    variables.push(*i);
                 }
-                AntiOperations::SubRHS => {
-                    if let EquationComponentType::AddNode { lhs, rhs } = eq {
-                        eq = *lhs;
-                        result = EquationComponentType::SubNode {
-                            lhs: Box::new(result),
-                            rhs: rhs,
-                        }
-                    } else {
-                        return Err(MathError::InternalError);
-                    }
+            }
+            EquationComponentType::AddNode { lhs, rhs }
+            | EquationComponentType::SubNode { lhs, rhs }
+            | EquationComponentType::MulNode { lhs, rhs }
+            | EquationComponentType::FloorDivNode { lhs, rhs }
+            | EquationComponentType::ModNode { lhs, rhs } => {
+                lhs.collect_variables(variables);
+                rhs.collect_variables(variables);
+            }
+            EquationComponentType::DivNode {
+                numerator,
+                denominator,
+            } => {
+                numerator.collect_variables(variables);
+                denominator.collect_variables(variables);
+            }
+            EquationComponentType::PowNode { base, exponent } => {
+                base.collect_variables(variables);
+                exponent.collect_variables(variables);
+            }
+            EquationComponentType::LogNode { base, argument } => {
+                base.collect_variables(variables);
+                argument.collect_variables(variables);
+            }
+            EquationComponentType::MinusNode(value)
+            | EquationComponentType::AbsNode(value)
+            | EquationComponentType::ExpNode(value) => value.collect_variables(variables),
+        }
+    }
+
+    // counts every subtree that is not a leaf, `order` lists them as they are first completed,
+    // inner subtrees before the ones containing them
+    fn count_subtrees(
+        &self,
+        counts: &mut HashMap<EquationComponentType, usize>,
+        order: &mut Vec<EquationComponentType>,
+    ) {
+        match self {
+            EquationComponentType::ConstantNode(_) | EquationComponentType::VariableNode(_) => {
+                return;
+            }
+            EquationComponentType::AddNode { lhs, rhs }
+            | EquationComponentType::SubNode { lhs, rhs }
+            | EquationComponentType::MulNode { lhs, rhs }
+            | EquationComponentType::FloorDivNode { lhs, rhs }
+            | EquationComponentType::ModNode { lhs, rhs } => {
+                lhs.count_subtrees(counts, order);
+                rhs.count_subtrees(counts, order);
+            }
+            EquationComponentType::DivNode {
+                numerator,
+                denominator,
+            } => {
+                numerator.count_subtrees(counts, order);
+                denominator.count_subtrees(counts, order);
+            }
+            EquationComponentType::PowNode { base, exponent } => {
+                base.count_subtrees(counts, order);
+                exponent.count_subtrees(counts, order);
+            }
+            EquationComponentType::LogNode { base, argument } => {
+                base.count_subtrees(counts, order);
+                argument.count_subtrees(counts, order);
+            }
+            EquationComponentType::MinusNode(value)
+            | EquationComponentType::AbsNode(value)
+            | EquationComponentType::ExpNode(value) => value.count_subtrees(counts, order),
+        }
+
+        match counts.get_mut(self) {
+            Some(n) => *n += 1,
+            None => {
+                counts.insert(self.clone(), 1);
+                order.push(self.clone());
+            }
+        }
+    }
+
+    // base in canonical order and exponent of a factor, x is x ^ 1, and whether it is a power
+    fn as_power(&self) -> (Self, Self, bool) {
+        match self {
+            EquationComponentType::PowNode { base, exponent } => {
+                (base.order(), exponent.as_ref().clone(), true)
+            }
+            node => (
+                node.order(),
+                EquationComponentType::ConstantNode(Number::from(1)),
+                false,
+            ),
+        }
+    }
+
+    // (a ^ m) / (a ^ n) -> a ^ (m - n) for factors of the numerator and the denominator that
+    // share a base, one of them a power. A negative numeric difference leaves the power in the
+    // denominator: (a ^ 2) / (a ^ 5) -> 1 / (a ^ 3). None if no factors share a base
+    fn cancel_powers(
+        numerator: &EquationComponentType,
+        denominator: &EquationComponentType,
+        options: &SimplifyOptions,
+    ) -> Option<Self> {
+        let mut top: Vec<EquationComponentType> = Vec::new();
+        let mut bottom: Vec<EquationComponentType> = Vec::new();
+        numerator.separate_products(&mut top);
+        denominator.separate_products(&mut bottom);
+
+        let power = |base: EquationComponentType, exponent: EquationComponentType| {
+            EquationComponentType::PowNode {
+                base: Arc::new(base),
+                exponent: Arc::new(exponent),
+            }
+            .simplify_with(options)
+        };
+
+        let mut cancelled: bool = false;
+        let mut i: usize = 0;
+        while i < bottom.len() {
+            let (base, exponent, is_power) = bottom[i].as_power();
+            let position: Option<usize> = top.iter().position(|f| {
+                let (b, _, p) = f.as_power();
+                b == base && (p || is_power)
+            });
+            let j: usize = match position {
+                Some(j) => j,
+                None => {
+                    i += 1;
+                    continue;
                 }
-                AntiOperations::MulNumerator => {
-                    if let EquationComponentType::DivNode {
-                        numerator,
-                        denominator,
-                    } = eq
-                    {
-                        eq = *denominator;
-                        result = EquationComponentType::DivNode {
-                            numerator: numerator,
-                            denominator: Box::new(result),
-                        }
-                    } else {
-                        return Err(MathError::InternalError);
-                    }
+            };
+
+            let (_, top_exponent, _) = top.remove(j).as_power();
+            bottom.remove(i);
+            cancelled = true;
+
+            let difference: EquationComponentType = EquationComponentType::SubNode {
+                lhs: Arc::new(top_exponent),
+                rhs: Arc::new(exponent),
+            }
+            .simplify_with(options);
+            match difference {
+                // taking a to be nonzero as for x / x
+                EquationComponentType::ConstantNode(d) if d == Number::from(0) => {}
+                EquationComponentType::ConstantNode(d) if d.signum() == Number::from(-1) => {
+                    bottom.insert(i, power(base, EquationComponentType::ConstantNode(-d)));
+                    i += 1;
                 }
-                AntiOperations::MulDenominator => {
-                    if let EquationComponentType::DivNode {
-                        numerator,
-                        denominator,
-                    } = eq
-                    {
-                        eq = *numerator;
-                        result = EquationComponentType::MulNode {
-                            lhs: Box::new(result),
-                            rhs: denominator,
-                        }
-                    } else {
-                        return Err(MathError::InternalError);
-                    }
+                difference => top.push(power(base, difference)),
+            }
+        }
+
+        if !cancelled {
+            return None;
+        }
+
+        let product = |factors: Vec<EquationComponentType>| {
+            factors
+                .into_iter()
+                .reduce(|lhs, rhs| EquationComponentType::MulNode {
+                    lhs: Arc::new(lhs),
+                    rhs: Arc::new(rhs),
+                })
+                .unwrap_or(EquationComponentType::ConstantNode(Number::from(1)))
+        };
+
+        if bottom.is_empty() {
+            return Some(product(top).simplify_with(options));
+        }
+        Some(
+            EquationComponentType::DivNode {
+                numerator: Arc::new(product(top)),
+                denominator: Arc::new(product(bottom)),
+            }
+            .simplify_with(options),
+        )
+    }
+
+    // merges the factors of a product that share a base with one of its powers by adding their
+    // exponents: (2 ^ x) * (2 ^ y) -> 2 ^ (x + y), (a ^ m) * a -> a ^ (m + 1). Factors that are
+    // not powers are left as they are, as is the order of the remaining factors
+    fn combine_powers(nodes: Vec<Self>, options: &SimplifyOptions) -> Vec<Self> {
+        // base in canonical order, exponents, whether one of them is a power, original factors
+        let mut groups: Vec<(Self, Vec<Self>, bool, Vec<Self>)> = Vec::new();
+
+        for node in nodes.into_iter() {
+            let (base, exponent, is_power) = node.as_power();
+
+            match groups.iter_mut().find(|(b, _, _, _)| *b == base) {
+                Some((_, exponents, has_power, factors)) => {
+                    exponents.push(exponent);
+                    *has_power |= is_power;
+                    factors.push(node);
                 }
-                AntiOperations::DivLHS => {
-                    if let EquationComponentType::MulNode { lhs, rhs } = eq {
-                        eq = *rhs;
-                        result = EquationComponentType::DivNode {
-                            numerator: Box::new(result),
-                            denominator: lhs,
-                        }
-                    } else {
-                        return Err(MathError::InternalError);
-                    }
+                None => groups.push((base, vec![exponent], is_power, vec![node])),
+            }
+        }
+
+        let mut result: Vec<Self> = Vec::new();
+        for (base, exponents, has_power, factors) in groups.into_iter() {
+            if factors.len() < 2 || !has_power {
+                result.extend(factors);
+                continue;
+            }
+
+            let exponent: Self = exponents
+                .into_iter()
+                .reduce(|lhs, rhs| EquationComponentType::AddNode {
+                    lhs: Arc::new(lhs),
+                    rhs: Arc::new(rhs),
+                })
+                .unwrap()
+                .simplify_with(options);
+            // a ^ m * a ^ -m -> 1, taking a to be nonzero as for x / x
+            if exponent == EquationComponentType::ConstantNode(Number::from(0)) {
+                result.push(EquationComponentType::ConstantNode(Number::from(1)));
+                continue;
+            }
+            result.push(
+                EquationComponentType::PowNode {
+                    base: Arc::new(base),
+                    exponent: Arc::new(exponent),
                 }
-                AntiOperations::DivRHS => {
-                    if let EquationComponentType::MulNode { lhs, rhs } = eq {
-                        eq = *lhs;
-                        result = EquationComponentType::DivNode {
-                            numerator: Box::new(result),
-                            denominator: rhs,
-                        }
-                    } else {
-                        return Err(MathError::InternalError);
-                    }
+                .simplify_with(options),
+            );
+        }
+        result
+    }
+
+    // reduces numerator / denominator to lowest terms if both are polynomials in the same
+    // single variable, returns None if nothing cancels
+    fn divide_polynomials(
+        numerator: &EquationComponentType,
+        denominator: &EquationComponentType,
+    ) -> Option<EquationComponentType> {
+        let mut variables: Vec<char> = Vec::new();
+        numerator.collect_variables(&mut variables);
+        denominator.collect_variables(&mut variables);
+        if variables.len() != 1 {
+            return None;
+        }
+        if numerator.degree(variables[0])? > MAX_DIVISION_DEGREE
+            || denominator.degree(variables[0])? > MAX_DIVISION_DEGREE
+        {
+            return None;
+        }
+
+        let numerator: Polynomial = numerator.to_polynomial(variables[0])?;
+        let denominator: Polynomial = denominator.to_polynomial(variables[0])?;
+        if denominator.degree()? == 0 {
+            return None;
+        }
+
+        let (quotient, remainder) = numerator.div_rem(&denominator).ok()?;
+        if remainder.is_zero() {
+            return Some(quotient.to_part_equation().eq);
+        }
+
+        let gcd: Polynomial = numerator.gcd(&denominator).ok()?;
+        if gcd.degree()? == 0 {
+            return None;
+        }
+
+        let (numerator, _) = numerator.div_rem(&gcd).ok()?;
+        let (denominator, _) = denominator.div_rem(&gcd).ok()?;
+
+        Some(EquationComponentType::DivNode {
+            numerator: Arc::new(numerator.to_part_equation().eq),
+            denominator: Arc::new(denominator.to_part_equation().eq),
+        })
+    }
+
+    fn degree(&self, variable: char) -> Option<i64> {
+        match self {
+            EquationComponentType::ConstantNode(_) => Some(0),
+            EquationComponentType::VariableNode(i) => {
+                if *i == variable {
+                    Some(1)
+                } else {
+                    Some(0)
                 }
-                AntiOperations::PowLHS => {
-                    if let EquationComponentType::LogNode { base, argument } = eq {
-                        eq = *argument;
-                        result = EquationComponentType::PowNode {
-                            base: base,
-                            exponent: Box::new(result),
+            }
+            EquationComponentType::AddNode { lhs, rhs }
+            | EquationComponentType::SubNode { lhs, rhs } => {
+                Some(lhs.degree(variable)?.max(rhs.degree(variable)?))
+            }
+            EquationComponentType::MulNode { lhs, rhs } => {
+                Some(lhs.degree(variable)? + rhs.degree(variable)?)
+            }
+            EquationComponentType::DivNode {
+                numerator,
+                denominator,
+            } => {
+                if denominator.degree(variable)? != 0 {
+                    return None;
+                }
+                numerator.degree(variable)
+            }
+            EquationComponentType::PowNode { base, exponent } => {
+                let base: i64 = base.degree(variable)?;
+                if exponent.degree(variable)? != 0 {
+                    return None;
+                }
+                if base == 0 {
+                    return Some(0);
+                }
+
+                match exponent.simplify() {
+                    EquationComponentType::ConstantNode(i) => {
+                        if i.signum() == Number::from(-1) || !i.is_integer() {
+                            return None;
                         }
-                    } else {
-                        return Err(MathError::InternalError);
+                        Some(base * i.to_i64()?)
                     }
+                    _ => None,
                 }
-                AntiOperations::PowRHS => {
-                    if let EquationComponentType::PowNode { base, exponent } = eq {
-                        eq = *base;
-                        result = EquationComponentType::PowNode {
-                            base: Box::new(result),
-                            exponent: Box::new(EquationComponentType::DivNode {
-                                numerator: Box::new(EquationComponentType::ConstantNode(
-                                    Number::from(1),
-                                )),
-                                denominator: exponent,
+            }
+            EquationComponentType::LogNode { base, argument } => {
+                if base.degree(variable)? != 0 || argument.degree(variable)? != 0 {
+                    return None;
+                }
+                Some(0)
+            }
+            EquationComponentType::FloorDivNode { lhs, rhs }
+            | EquationComponentType::ModNode { lhs, rhs } => {
+                if lhs.degree(variable)? != 0 || rhs.degree(variable)? != 0 {
+                    return None;
+                }
+                Some(0)
+            }
+            EquationComponentType::MinusNode(value) => value.degree(variable),
+            EquationComponentType::AbsNode(value) | EquationComponentType::ExpNode(value) => {
+                if value.degree(variable)? != 0 {
+                    return None;
+                }
+                Some(0)
+            }
+        }
+    }
+
+    fn differentiate(&self, variable: char) -> EquationComponentType {
+        if self.free_of(variable) {
+            return EquationComponentType::ConstantNode(Number::from(0));
+        }
+
+        let ln = |argument: &EquationComponentType| EquationComponentType::LogNode {
+            base: Arc::new(EquationComponentType::ConstantNode(Number::e())),
+            argument: Arc::new(argument.clone()),
+        };
+
+        match self {
+            EquationComponentType::ConstantNode(_) => {
+                EquationComponentType::ConstantNode(Number::from(0))
+            }
+            EquationComponentType::VariableNode(_) => {
+                EquationComponentType::ConstantNode(Number::from(1))
+            }
+            EquationComponentType::AddNode { lhs, rhs } => EquationComponentType::AddNode {
+                lhs: Arc::new(lhs.differentiate(variable)),
+                rhs: Arc::new(rhs.differentiate(variable)),
+            },
+            EquationComponentType::SubNode { lhs, rhs } => EquationComponentType::SubNode {
+                lhs: Arc::new(lhs.differentiate(variable)),
+                rhs: Arc::new(rhs.differentiate(variable)),
+            },
+            // (u * v)' = u' * v + u * v'
+            EquationComponentType::MulNode { lhs, rhs } => EquationComponentType::AddNode {
+                lhs: Arc::new(EquationComponentType::MulNode {
+                    lhs: Arc::new(lhs.differentiate(variable)),
+                    rhs: rhs.clone(),
+                }),
+                rhs: Arc::new(EquationComponentType::MulNode {
+                    lhs: lhs.clone(),
+                    rhs: Arc::new(rhs.differentiate(variable)),
+                }),
+            },
+            // (u / v)' = (u' * v - u * v') / v^2
+            EquationComponentType::DivNode {
+                numerator,
+                denominator,
+            } => EquationComponentType::DivNode {
+                numerator: Arc::new(EquationComponentType::SubNode {
+                    lhs: Arc::new(EquationComponentType::MulNode {
+                        lhs: Arc::new(numerator.differentiate(variable)),
+                        rhs: denominator.clone(),
+                    }),
+                    rhs: Arc::new(EquationComponentType::MulNode {
+                        lhs: numerator.clone(),
+                        rhs: Arc::new(denominator.differentiate(variable)),
+                    }),
+                }),
+                denominator: Arc::new(EquationComponentType::PowNode {
+                    base: denominator.clone(),
+                    exponent: Arc::new(EquationComponentType::ConstantNode(Number::from(2))),
+                }),
+            },
+            EquationComponentType::PowNode { base, exponent } => {
+                // (u^n)' = n * u^(n - 1) * u'
+                if exponent.free_of(variable) {
+                    return EquationComponentType::MulNode {
+                        lhs: Arc::new(EquationComponentType::MulNode {
+                            lhs: exponent.clone(),
+                            rhs: Arc::new(EquationComponentType::PowNode {
+                                base: base.clone(),
+                                exponent: Arc::new(EquationComponentType::SubNode {
+                                    lhs: exponent.clone(),
+                                    rhs: Arc::new(EquationComponentType::ConstantNode(
+                                        Number::from(1),
+                                    )),
+                                }),
                             }),
-                        }
-                    } else {
-                        return Err(MathError::InternalError);
-                    }
+                        }),
+                        rhs: Arc::new(base.differentiate(variable)),
+                    };
                 }
-                AntiOperations::LogLHS => {
-                    if let EquationComponentType::PowNode { base, exponent } = eq {
-                        eq = *exponent;
-                        result = EquationComponentType::LogNode {
-                            base: base,
-                            argument: Box::new(result),
-                        }
-                    } else {
-                        return Err(MathError::InternalError);
-                    }
+                // (a^v)' = a^v * ln(a) * v'
+                if base.free_of(variable) {
+                    return EquationComponentType::MulNode {
+                        lhs: Arc::new(EquationComponentType::MulNode {
+                            lhs: Arc::new(self.clone()),
+                            rhs: Arc::new(ln(base)),
+                        }),
+                        rhs: Arc::new(exponent.differentiate(variable)),
+                    };
                 }
-                AntiOperations::LogRHS => {
-                    if let EquationComponentType::PowNode { base, exponent } = eq {
-                        eq = *base;
-                        result = EquationComponentType::PowNode {
-                            base: exponent,
-                            exponent: Box::new(EquationComponentType::DivNode {
-                                numerator: Box::new(EquationComponentType::ConstantNode(
-                                    Number::from(1),
-                                )),
-                                denominator: Box::new(result),
+                // (u^v)' = u^v * (v' * ln(u) + v * u' / u)
+                EquationComponentType::MulNode {
+                    lhs: Arc::new(self.clone()),
+                    rhs: Arc::new(EquationComponentType::AddNode {
+                        lhs: Arc::new(EquationComponentType::MulNode {
+                            lhs: Arc::new(exponent.differentiate(variable)),
+                            rhs: Arc::new(ln(base)),
+                        }),
+                        rhs: Arc::new(EquationComponentType::DivNode {
+                            numerator: Arc::new(EquationComponentType::MulNode {
+                                lhs: exponent.clone(),
+                                rhs: Arc::new(base.differentiate(variable)),
                             }),
-                        }
-                    } else {
-                        return Err(MathError::InternalError);
-                    }
+                            denominator: base.clone(),
+                        }),
+                    }),
                 }
-                AntiOperations::Minus => {
-                    if let EquationComponentType::MinusNode(v) = eq {
-                        eq = *v;
-                        result = EquationComponentType::MinusNode(Box::new(result));
-                    } else {
-                        return Err(MathError::InternalError);
-                    }
+            }
+            EquationComponentType::LogNode { base, argument } => {
+                // log_b(u)' = u' / (u * ln(b))
+                if base.free_of(variable) {
+                    return EquationComponentType::DivNode {
+                        numerator: Arc::new(argument.differentiate(variable)),
+                        denominator: Arc::new(EquationComponentType::MulNode {
+                            lhs: argument.clone(),
+                            rhs: Arc::new(ln(base)),
+                        }),
+                    };
                 }
+                // log_v(u) = ln(u) / ln(v)
+                EquationComponentType::DivNode {
+                    numerator: Arc::new(ln(argument)),
+                    denominator: Arc::new(ln(base)),
+                }
+                .differentiate(variable)
+            }
+            EquationComponentType::MinusNode(value) => {
+                EquationComponentType::MinusNode(Arc::new(value.differentiate(variable)))
+            }
+            // |u|' = u' * u / |u|
+            EquationComponentType::AbsNode(value) => EquationComponentType::MulNode {
+                lhs: Arc::new(value.differentiate(variable)),
+                rhs: Arc::new(EquationComponentType::DivNode {
+                    numerator: value.clone(),
+                    denominator: Arc::new(self.clone()),
+                }),
+            },
+            // exp(u)' = exp(u) * u'
+            EquationComponentType::ExpNode(value) => EquationComponentType::MulNode {
+                lhs: Arc::new(self.clone()),
+                rhs: Arc::new(value.differentiate(variable)),
+            },
+            // floor(u / v) is constant between its jumps, where it has no derivative
+            EquationComponentType::FloorDivNode { .. } => {
+                EquationComponentType::ConstantNode(Number::from(0))
             }
+            // (u mod v)' = u' - v' * floor(u / v), away from the jumps
+            EquationComponentType::ModNode { lhs, rhs } => EquationComponentType::SubNode {
+                lhs: Arc::new(lhs.differentiate(variable)),
+                rhs: Arc::new(EquationComponentType::MulNode {
+                    lhs: Arc::new(rhs.differentiate(variable)),
+                    rhs: Arc::new(EquationComponentType::FloorDivNode {
+                        lhs: lhs.clone(),
+                        rhs: rhs.clone(),
+                    }),
+                }),
+            },
+        }
+    }
+
+    fn integrate(&self, variable: char) -> Result<EquationComponentType, MathError> {
+        if self.free_of(variable) {
+            return Ok(EquationComponentType::MulNode {
+                lhs: Arc::new(self.clone()),
+                rhs: Arc::new(EquationComponentType::VariableNode(variable)),
+            });
         }
 
-        // Step 3: return the simplified answer
-        return Ok(result.simplify().order());
+        match self {
+            EquationComponentType::VariableNode(_) => {
+                EquationComponentType::integrate_power(variable, Number::from(1))
+            }
+            EquationComponentType::AddNode { lhs, rhs } => Ok(EquationComponentType::AddNode {
+                lhs: Arc::new(lhs.integrate(variable)?),
+                rhs: Arc::new(rhs.integrate(variable)?),
+            }),
+            EquationComponentType::SubNode { lhs, rhs } => Ok(EquationComponentType::SubNode {
+                lhs: Arc::new(lhs.integrate(variable)?),
+                rhs: Arc::new(rhs.integrate(variable)?),
+            }),
+            EquationComponentType::MinusNode(value) => Ok(EquationComponentType::MinusNode(
+                Arc::new(value.integrate(variable)?),
+            )),
+            EquationComponentType::MulNode { lhs, rhs } => {
+                if lhs.free_of(variable) {
+                    return Ok(EquationComponentType::MulNode {
+                        lhs: lhs.clone(),
+                        rhs: Arc::new(rhs.integrate(variable)?),
+                    });
+                }
+                if rhs.free_of(variable) {
+                    return Ok(EquationComponentType::MulNode {
+                        lhs: Arc::new(lhs.integrate(variable)?),
+                        rhs: rhs.clone(),
+                    });
+                }
+                Err(MathError::NotYetImplemented)
+            }
+            EquationComponentType::DivNode {
+                numerator,
+                denominator,
+            } => {
+                if denominator.free_of(variable) {
+                    return Ok(EquationComponentType::DivNode {
+                        numerator: Arc::new(numerator.integrate(variable)?),
+                        denominator: denominator.clone(),
+                    });
+                }
+                if !numerator.free_of(variable) {
+                    return Err(MathError::NotYetImplemented);
+                }
+
+                // c / x^n is c * x^-n
+                let reciprocal: EquationComponentType = match denominator.as_ref() {
+                    EquationComponentType::VariableNode(_) => {
+                        EquationComponentType::integrate_power(variable, Number::from(-1))?
+                    }
+                    EquationComponentType::PowNode { base, exponent }
+                        if matches!(base.as_ref(), EquationComponentType::VariableNode(_)) =>
+                    {
+                        match exponent.simplify() {
+                            EquationComponentType::ConstantNode(i) => {
+                                EquationComponentType::integrate_power(variable, -i)?
+                            }
+                            _ => return Err(MathError::NotYetImplemented),
+                        }
+                    }
+                    _ => return Err(MathError::NotYetImplemented),
+                };
+                Ok(EquationComponentType::MulNode {
+                    lhs: numerator.clone(),
+                    rhs: Arc::new(reciprocal),
+                })
+            }
+            // exp(x) is its own antiderivative
+            EquationComponentType::ExpNode(value)
+                if **value == EquationComponentType::VariableNode(variable) =>
+            {
+                Ok(self.clone())
+            }
+            EquationComponentType::PowNode { base, exponent } => {
+                if !matches!(base.as_ref(), EquationComponentType::VariableNode(_)) {
+                    return Err(MathError::NotYetImplemented);
+                }
+                match exponent.simplify() {
+                    EquationComponentType::ConstantNode(i) => {
+                        EquationComponentType::integrate_power(variable, i)
+                    }
+                    _ => Err(MathError::NotYetImplemented),
+                }
+            }
+            _ => Err(MathError::NotYetImplemented),
+        }
+    }
+
+    /// Antiderivative of `variable ^ exponent`
+    fn integrate_power(
+        variable: char,
+        exponent: Number,
+    ) -> Result<EquationComponentType, MathError> {
+        if let Number::Complex(_) = exponent {
+            return Err(MathError::NotYetImplemented);
+        }
+
+        if exponent == Number::from(-1) {
+            return Ok(EquationComponentType::LogNode {
+                base: Arc::new(EquationComponentType::ConstantNode(Number::e())),
+                argument: Arc::new(EquationComponentType::VariableNode(variable)),
+            });
+        }
+
+        // a constant factor rather than a division, so it folds into the coefficients
+        let exponent: Number = exponent + 1;
+        let factor: Number = Number::from(1) / exponent.clone();
+        Ok(EquationComponentType::MulNode {
+            lhs: Arc::new(EquationComponentType::PowNode {
+                base: Arc::new(EquationComponentType::VariableNode(variable)),
+                exponent: Arc::new(EquationComponentType::ConstantNode(exponent)),
+            }),
+            rhs: Arc::new(EquationComponentType::ConstantNode(factor)),
+        })
+    }
+
+    fn to_polynomial(&self, variable: char) -> Option<Polynomial> {
+        match self {
+            EquationComponentType::ConstantNode(i) => match i {
+                Number::Float(_) => None,
+                _ => Some(Polynomial::constant(variable, i.clone())),
+            },
+            EquationComponentType::VariableNode(i) => {
+                if *i != variable {
+                    return None;
+                }
+                Some(Polynomial::new(
+                    variable,
+                    vec![Number::from(0), Number::from(1)],
+                ))
+            }
+            EquationComponentType::AddNode { lhs, rhs } => Some(
+                lhs.to_polynomial(variable)?
+                    .add(&rhs.to_polynomial(variable)?),
+            ),
+            EquationComponentType::SubNode { lhs, rhs } => Some(
+                lhs.to_polynomial(variable)?
+                    .add(&rhs.to_polynomial(variable)?.neg()),
+            ),
+            EquationComponentType::MulNode { lhs, rhs } => Some(
+                lhs.to_polynomial(variable)?
+                    .mul(&rhs.to_polynomial(variable)?),
+            ),
+            EquationComponentType::DivNode {
+                numerator,
+                denominator,
+            } => {
+                // only division by a non-zero constant keeps the expression a polynomial
+                let numerator: Polynomial = numerator.to_polynomial(variable)?;
+                let denominator: Polynomial = denominator.to_polynomial(variable)?;
+                if denominator.degree() != Some(0) {
+                    return None;
+                }
+
+                let inverse: Number = Number::from(1) / denominator.coefficients()[0].clone();
+                Some(numerator.mul(&Polynomial::constant(variable, inverse)))
+            }
+            EquationComponentType::PowNode { base, exponent } => {
+                let exponent: u32 = match exponent.simplify() {
+                    EquationComponentType::ConstantNode(Number::Float(_)) => return None,
+                    EquationComponentType::ConstantNode(i) => u32::try_from(i.to_i64()?).ok()?,
+                    _ => return None,
+                };
+                Some(base.to_polynomial(variable)?.pow(exponent))
+            }
+            EquationComponentType::LogNode {
+                base: _,
+                argument: _,
+            } => None,
+            EquationComponentType::MinusNode(value) => Some(value.to_polynomial(variable)?.neg()),
+            EquationComponentType::AbsNode(_)
+            | EquationComponentType::ExpNode(_)
+            | EquationComponentType::FloorDivNode { .. }
+            | EquationComponentType::ModNode { .. } => None,
+        }
+    }
+
+    fn monomials(&self) -> Option<Monomials> {
+        match self {
+            EquationComponentType::ConstantNode(i) => {
+                let mut terms: Monomials = Monomials::new();
+                Self::add_monomial(&mut terms, Vec::new(), i.clone());
+                Some(terms)
+            }
+            EquationComponentType::VariableNode(i) => {
+                Some(Monomials::from([(vec![(*i, 1)], Number::from(1))]))
+            }
+            EquationComponentType::AddNode { lhs, rhs } => {
+                let mut terms: Monomials = lhs.monomials()?;
+                for (powers, coefficient) in rhs.monomials()? {
+                    Self::add_monomial(&mut terms, powers, coefficient);
+                }
+                Some(terms)
+            }
+            EquationComponentType::SubNode { lhs, rhs } => {
+                let mut terms: Monomials = lhs.monomials()?;
+                for (powers, coefficient) in rhs.monomials()? {
+                    Self::add_monomial(&mut terms, powers, -coefficient);
+                }
+                Some(terms)
+            }
+            EquationComponentType::MulNode { lhs, rhs } => Some(Self::multiply_monomials(
+                &lhs.monomials()?,
+                &rhs.monomials()?,
+            )),
+            EquationComponentType::DivNode {
+                numerator,
+                denominator,
+            } => Some(Self::multiply_monomials(
+                &numerator.monomials()?,
+                &Self::invert_monomial(denominator.monomials()?)?,
+            )),
+            EquationComponentType::PowNode { base, exponent } => {
+                let exponent: i64 = match exponent.simplify() {
+                    EquationComponentType::ConstantNode(i) if i.is_integer() => i.to_i64()?,
+                    _ => return None,
+                };
+                let mut base: Monomials = base.monomials()?;
+                if exponent < 0 {
+                    base = Self::invert_monomial(base)?;
+                }
+
+                // by squaring, x ^ 1000 takes ten multiplications
+                let mut result: Monomials = Monomials::from([(Vec::new(), Number::from(1))]);
+                let mut exponent: u64 = exponent.unsigned_abs();
+                while exponent > 0 {
+                    if exponent % 2 == 1 {
+                        result = Self::multiply_monomials(&result, &base);
+                    }
+                    exponent /= 2;
+                    if exponent > 0 {
+                        base = Self::multiply_monomials(&base, &base);
+                    }
+                }
+                Some(result)
+            }
+            EquationComponentType::MinusNode(value) => Some(
+                value
+                    .monomials()?
+                    .into_iter()
+                    .map(|(powers, coefficient)| (powers, -coefficient))
+                    .collect(),
+            ),
+            EquationComponentType::LogNode { .. }
+            | EquationComponentType::AbsNode(_)
+            | EquationComponentType::ExpNode(_)
+            | EquationComponentType::FloorDivNode { .. }
+            | EquationComponentType::ModNode { .. } => None,
+        }
+    }
+
+    // adds to the coefficient of the monomial, dropping it once the coefficient is zero
+    fn add_monomial(terms: &mut Monomials, powers: Vec<(char, i64)>, coefficient: Number) {
+        let sum: Number = match terms.remove(&powers) {
+            Some(existing) => existing + coefficient,
+            None => coefficient,
+        };
+        if sum != Number::from(0) {
+            terms.insert(powers, sum.normalize());
+        }
+    }
+
+    fn multiply_monomials(lhs: &Monomials, rhs: &Monomials) -> Monomials {
+        let mut terms: Monomials = Monomials::new();
+        for (lhs_powers, lhs_coefficient) in lhs.iter() {
+            for (rhs_powers, rhs_coefficient) in rhs.iter() {
+                let mut powers: Vec<(char, i64)> = lhs_powers.clone();
+                for (variable, exponent) in rhs_powers.iter() {
+                    match powers.iter_mut().find(|(v, _)| v == variable) {
+                        Some((_, e)) => *e += exponent,
+                        None => powers.push((*variable, *exponent)),
+                    }
+                }
+                powers.retain(|(_, e)| *e != 0);
+                powers.sort();
+                Self::add_monomial(&mut terms, powers, lhs_coefficient * rhs_coefficient);
+            }
+        }
+        terms
+    }
+
+    // 1 / (c * x^a * y^b) = (1/c) * x^-a * y^-b, sums have no inverse as monomials
+    fn invert_monomial(terms: Monomials) -> Option<Monomials> {
+        if terms.len() != 1 {
+            return None;
+        }
+        let (powers, coefficient) = terms.into_iter().next()?;
+        let powers: Vec<(char, i64)> = powers.into_iter().map(|(v, e)| (v, -e)).collect();
+        Some(Monomials::from([(
+            powers,
+            (Number::from(1) / coefficient).normalize(),
+        )]))
+    }
+
+    // sign of the expression if it follows from the constants and the assumed variable signs
+    fn sign(&self, assumptions: &Assumptions) -> Option<Sign> {
+        match self {
+            EquationComponentType::ConstantNode(Number::Complex(_)) => None,
+            EquationComponentType::ConstantNode(i) => match i.signum().to_i64()? {
+                1 => Some(Sign::Positive),
+                -1 => Some(Sign::Negative),
+                _ => Some(Sign::NonNegative),
+            },
+            EquationComponentType::VariableNode(i) => assumptions.sign(*i),
+            EquationComponentType::AddNode { lhs, rhs } => {
+                let lhs: Sign = lhs.sign(assumptions)?;
+                let rhs: Sign = rhs.sign(assumptions)?;
+                if lhs.is_nonnegative() != rhs.is_nonnegative() {
+                    return None;
+                }
+                Some(lhs.combine_sum(rhs))
+            }
+            EquationComponentType::MulNode { lhs, rhs } => Some(
+                lhs.sign(assumptions)?
+                    .combine_product(rhs.sign(assumptions)?),
+            ),
+            EquationComponentType::DivNode {
+                numerator,
+                denominator,
+            } => Some(
+                numerator
+                    .sign(assumptions)?
+                    .combine_product(denominator.sign(assumptions)?),
+            ),
+            EquationComponentType::PowNode { base, exponent } => {
+                let even: bool = match &**exponent {
+                    EquationComponentType::ConstantNode(i) => {
+                        i.to_i64().is_some_and(|n| n % 2 == 0)
+                    }
+                    _ => false,
+                };
+                match base.sign(assumptions) {
+                    Some(Sign::Positive) => Some(Sign::Positive),
+                    Some(Sign::NonNegative) => Some(Sign::NonNegative),
+                    _ if even => Some(Sign::NonNegative),
+                    _ => None,
+                }
+            }
+            EquationComponentType::MinusNode(value) => Some(value.sign(assumptions)?.flip()),
+            EquationComponentType::AbsNode(_) => Some(Sign::NonNegative),
+            EquationComponentType::ExpNode(_) => Some(Sign::Positive),
+            // the remainder takes the sign of the divisor
+            EquationComponentType::ModNode { lhs: _, rhs } => match rhs.sign(assumptions)? {
+                Sign::Positive => Some(Sign::NonNegative),
+                Sign::Negative => Some(Sign::NonPositive),
+                _ => None,
+            },
+            EquationComponentType::FloorDivNode { lhs: _, rhs: _ }
+            | EquationComponentType::SubNode { lhs: _, rhs: _ }
+            | EquationComponentType::LogNode {
+                base: _,
+                argument: _,
+            } => None,
+        }
+    }
+
+    // applies the rewrites that only hold under the given assumptions, bottom up
+    fn assume(&self, assumptions: &Assumptions) -> Self {
+        match self {
+            EquationComponentType::ConstantNode(i) => {
+                EquationComponentType::ConstantNode(i.clone())
+            }
+            EquationComponentType::VariableNode(i) => EquationComponentType::VariableNode(*i),
+            EquationComponentType::AddNode { lhs, rhs } => EquationComponentType::AddNode {
+                lhs: Arc::new(lhs.assume(assumptions)),
+                rhs: Arc::new(rhs.assume(assumptions)),
+            },
+            EquationComponentType::SubNode { lhs, rhs } => EquationComponentType::SubNode {
+                lhs: Arc::new(lhs.assume(assumptions)),
+                rhs: Arc::new(rhs.assume(assumptions)),
+            },
+            EquationComponentType::MulNode { lhs, rhs } => EquationComponentType::MulNode {
+                lhs: Arc::new(lhs.assume(assumptions)),
+                rhs: Arc::new(rhs.assume(assumptions)),
+            },
+            EquationComponentType::DivNode {
+                numerator,
+                denominator,
+            } => EquationComponentType::DivNode {
+                numerator: Arc::new(numerator.assume(assumptions)),
+                denominator: Arc::new(denominator.assume(assumptions)),
+            },
+            EquationComponentType::PowNode { base, exponent } => {
+                let base: EquationComponentType = base.assume(assumptions);
+                let exponent: EquationComponentType = exponent.assume(assumptions);
+
+                // b ^ log_b(x) -> x, for x > 0
+                if let EquationComponentType::LogNode {
+                    base: log_base,
+                    argument,
+                } = &exponent
+                {
+                    if **log_base == base && argument.sign(assumptions) == Some(Sign::Positive) {
+                        return argument.as_ref().clone();
+                    }
+                }
+
+                EquationComponentType::PowNode {
+                    base: Arc::new(base),
+                    exponent: Arc::new(exponent),
+                }
+            }
+            EquationComponentType::LogNode { base, argument } => EquationComponentType::LogNode {
+                base: Arc::new(base.assume(assumptions)),
+                argument: Arc::new(argument.assume(assumptions)),
+            },
+            EquationComponentType::MinusNode(value) => {
+                EquationComponentType::MinusNode(Arc::new(value.assume(assumptions)))
+            }
+            EquationComponentType::AbsNode(value) => {
+                let value: EquationComponentType = value.assume(assumptions);
+
+                // |x| -> x for x >= 0, |x| -> -x for x <= 0
+                match value.sign(assumptions) {
+                    Some(Sign::Positive) | Some(Sign::NonNegative) => value,
+                    Some(Sign::Negative) | Some(Sign::NonPositive) => {
+                        EquationComponentType::MinusNode(Arc::new(value))
+                    }
+                    None => EquationComponentType::AbsNode(Arc::new(value)),
+                }
+            }
+            EquationComponentType::ExpNode(value) => {
+                EquationComponentType::ExpNode(Arc::new(value.assume(assumptions)))
+            }
+            EquationComponentType::FloorDivNode { lhs, rhs } => {
+                EquationComponentType::FloorDivNode {
+                    lhs: Arc::new(lhs.assume(assumptions)),
+                    rhs: Arc::new(rhs.assume(assumptions)),
+                }
+            }
+            EquationComponentType::ModNode { lhs, rhs } => EquationComponentType::ModNode {
+                lhs: Arc::new(lhs.assume(assumptions)),
+                rhs: Arc::new(rhs.assume(assumptions)),
+            },
+        }
+    }
+
+    fn extract(
+        &self,
+        variables: &mut Vec<char>,
+        constants: &mut Vec<Number>,
+        nodes: &mut Vec<EquationComponentType>,
+        options: &SimplifyOptions,
+    ) {
+        match self {
+            EquationComponentType::AddNode { lhs, rhs } => {
+                match &**lhs {
+                    EquationComponentType::ConstantNode(i) => constants.push(i.clone()),
+                    EquationComponentType::VariableNode(i) => variables.push(*i),
+                    i @ EquationComponentType::AddNode { lhs: _, rhs: _ } => {
+                        i.extract(variables, constants, nodes, options)
+                    }
+                    n => {
+                        let m = n.simplify_with(options);
+                        match m {
+                            EquationComponentType::ConstantNode(i) => constants.push(i),
+                            EquationComponentType::VariableNode(i) => variables.push(i),
+                            i @ EquationComponentType::AddNode { lhs: _, rhs: _ } => {
+                                i.extract(variables, constants, nodes, options)
+                            }
+                            n => nodes.push(n),
+                        }
+                    }
+                };
+
+                match &**rhs {
+                    EquationComponentType::ConstantNode(i) => constants.push(i.clone()),
+                    EquationComponentType::VariableNode(i) => variables.push(*i),
+                    i @ EquationComponentType::AddNode { lhs: _, rhs: _ } => {
+                        i.extract(variables, constants, nodes, options)
+                    }
+                    n => {
+                        let m = n.simplify_with(options);
+                        match m {
+                            EquationComponentType::ConstantNode(i) => constants.push(i),
+                            EquationComponentType::VariableNode(i) => variables.push(i),
+                            i @ EquationComponentType::AddNode { lhs: _, rhs: _ } => {
+                                i.extract(variables, constants, nodes, options)
+                            }
+                            n => nodes.push(n),
+                        }
+                    }
+                };
+            } // End EquationComponentType::AddNode
+
+            EquationComponentType::MulNode { lhs, rhs } => {
+                match &**lhs {
+                    EquationComponentType::ConstantNode(i) => constants.push(i.clone()),
+                    EquationComponentType::VariableNode(i) => variables.push(*i),
+                    i @ EquationComponentType::MulNode { lhs: _, rhs: _ } => {
+                        i.extract(variables, constants, nodes, options)
+                    }
+                    n => {
+                        let m = n.simplify_with(options);
+
+                        match m {
+                            EquationComponentType::ConstantNode(i) => constants.push(i),
+                            EquationComponentType::VariableNode(i) => variables.push(i),
+                            i @ EquationComponentType::MulNode { lhs: _, rhs: _ } => {
+                                i.extract(variables, constants, nodes, options)
+                            }
+                            n => nodes.push(n),
+                        }
+                    }
+                };
+
+                match &**rhs {
+                    EquationComponentType::ConstantNode(i) => constants.push(i.clone()),
+                    EquationComponentType::VariableNode(i) => variables.push(*i),
+                    i @ EquationComponentType::MulNode { lhs: _, rhs: _ } => {
+                        i.extract(variables, constants, nodes, options)
+                    }
+                    n => {
+                        let m = n.simplify_with(options);
+
+                        match m {
+                            EquationComponentType::ConstantNode(i) => constants.push(i),
+                            EquationComponentType::VariableNode(i) => variables.push(i),
+                            i @ EquationComponentType::MulNode { lhs: _, rhs: _ } => {
+                                i.extract(variables, constants, nodes, options)
+                            }
+                            n => nodes.push(n),
+                        }
+                    }
+                };
+            } // End EquationComponentType::MulNode
+            _ => return,
+        }
+    }
+}
+
+/// Sign a variable is assumed to have
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sign {
+    Positive,
+    NonNegative,
+    Negative,
+    NonPositive,
+}
+
+impl Sign {
+    fn is_nonnegative(&self) -> bool {
+        matches!(self, Sign::Positive | Sign::NonNegative)
+    }
+
+    fn is_strict(&self) -> bool {
+        matches!(self, Sign::Positive | Sign::Negative)
+    }
+
+    fn flip(self) -> Sign {
+        match self {
+            Sign::Positive => Sign::Negative,
+            Sign::NonNegative => Sign::NonPositive,
+            Sign::Negative => Sign::Positive,
+            Sign::NonPositive => Sign::NonNegative,
+        }
+    }
+
+    fn from_parts(nonnegative: bool, strict: bool) -> Sign {
+        match (nonnegative, strict) {
+            (true, true) => Sign::Positive,
+            (true, false) => Sign::NonNegative,
+            (false, true) => Sign::Negative,
+            (false, false) => Sign::NonPositive,
+        }
+    }
+
+    // sign of a + b, where a and b lie on the same side of zero
+    fn combine_sum(self, other: Sign) -> Sign {
+        Sign::from_parts(self.is_nonnegative(), self.is_strict() || other.is_strict())
+    }
+
+    fn combine_product(self, other: Sign) -> Sign {
+        Sign::from_parts(
+            self.is_nonnegative() == other.is_nonnegative(),
+            self.is_strict() && other.is_strict(),
+        )
+    }
+}
+
+/// Per-variable sign assumptions for `PartEquation::simplify_with_assumptions`
+///
+/// ```
+/// use math_engine::equation::{Assumptions, PartEquation};
+///
+/// let x = PartEquation::from('x');
+/// let eq = x.pow(&PartEquation::from(2)).sqrt();
+///
+/// assert_eq!(eq.to_string(), "|x|");
+/// assert_eq!(
+///     eq.simplify_with_assumptions(&Assumptions::new().positive('x')),
+///     x
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Assumptions {
+    signs: HashMap<char, Sign>,
+}
+
+impl Assumptions {
+    pub fn new() -> Self {
+        Assumptions {
+            signs: HashMap::new(),
+        }
+    }
+
+    pub fn positive(self, variable: char) -> Self {
+        self.with_sign(variable, Sign::Positive)
+    }
+
+    pub fn nonnegative(self, variable: char) -> Self {
+        self.with_sign(variable, Sign::NonNegative)
+    }
+
+    pub fn negative(self, variable: char) -> Self {
+        self.with_sign(variable, Sign::Negative)
+    }
+
+    pub fn nonpositive(self, variable: char) -> Self {
+        self.with_sign(variable, Sign::NonPositive)
+    }
+
+    pub fn with_sign(mut self, variable: char, sign: Sign) -> Self {
+        self.signs.insert(variable, sign);
+        self
+    }
+
+    /// The assumed sign of `variable`, `None` if nothing is assumed
+    pub fn sign(&self, variable: char) -> Option<Sign> {
+        self.signs.get(&variable).copied()
+    }
+}
+
+/// Which rewrites `PartEquation::simplify_with` applies. The defaults are what `simplify` does:
+/// floats are folded, products are not distributed, fractions are not combined and a single pass is made
+///
+/// ```
+/// use math_engine::equation::{PartEquation, SimplifyOptions};
+///
+/// let x = PartEquation::from('x');
+/// let eq = (&x + 1) * 3;
+///
+/// assert_eq!(eq.to_string(), "(3 * (x + 1))");
+/// assert_eq!(
+///     eq.simplify_with(&SimplifyOptions::new().distribute(true)).to_string(),
+///     "((3 * x) + 3)"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SimplifyOptions {
+    distribute: bool,
+    fold_floats: bool,
+    combine_fractions: bool,
+    fixpoint: bool,
+}
+
+impl Default for SimplifyOptions {
+    fn default() -> Self {
+        SimplifyOptions {
+            distribute: false,
+            fold_floats: true,
+            combine_fractions: false,
+            fixpoint: false,
+        }
+    }
+}
+
+impl SimplifyOptions {
+    pub fn new() -> Self {
+        SimplifyOptions::default()
+    }
+
+    /// `x * (y + z) -> (x * y) + (x * z)`
+    pub fn distribute(mut self, enabled: bool) -> Self {
+        self.distribute = enabled;
+        self
+    }
+
+    /// `1.5 + 2.5 -> 4`, when disabled float constants are kept as written
+    pub fn fold_floats(mut self, enabled: bool) -> Self {
+        self.fold_floats = enabled;
+        self
+    }
+
+    /// `a / b + c / d -> ((a * d) + (c * b)) / (b * d)`
+    pub fn combine_fractions(mut self, enabled: bool) -> Self {
+        self.combine_fractions = enabled;
+        self
+    }
+
+    /// Repeats the simplification until the expression stops changing
+    pub fn fixpoint(mut self, enabled: bool) -> Self {
+        self.fixpoint = enabled;
+        self
+    }
+
+    // whether an operation on two constants is computed
+    fn folds(&self, lhs: &Number, rhs: &Number) -> bool {
+        self.fold_floats || !(matches!(lhs, Number::Float(_)) || matches!(rhs, Number::Float(_)))
+    }
+
+    // floats are kept as separate terms or factors when they are not folded
+    fn split_floats(&self, constants: &mut Vec<Number>, nodes: &mut Vec<EquationComponentType>) {
+        if self.fold_floats {
+            return;
+        }
+        let (floats, exact): (Vec<Number>, Vec<Number>) = constants
+            .drain(..)
+            .partition(|c| matches!(c, Number::Float(_)));
+        *constants = exact;
+        nodes.extend(floats.into_iter().map(EquationComponentType::ConstantNode));
+    }
+}
+
+// upper bound on the passes of a fixpoint simplification, in case rewrites cycle
+const FIXPOINT_PASSES: usize = 32;
+
+// highest degree `divide_polynomials` expands to dense coefficients, above it the division is
+// left as it is
+const MAX_DIVISION_DEGREE: i64 = 1024;
+
+/// A node of an expression, as passed to a `Visitor`
+pub enum Node<'a> {
+    Constant(&'a Number),
+    Variable(char),
+    Add(Subtree<'a>, Subtree<'a>),
+    Sub(Subtree<'a>, Subtree<'a>),
+    Mul(Subtree<'a>, Subtree<'a>),
+    Div {
+        numerator: Subtree<'a>,
+        denominator: Subtree<'a>,
+    },
+    Pow {
+        base: Subtree<'a>,
+        exponent: Subtree<'a>,
+    },
+    Log {
+        base: Subtree<'a>,
+        argument: Subtree<'a>,
+    },
+    Minus(Subtree<'a>),
+    Abs(Subtree<'a>),
+    Exp(Subtree<'a>),
+    FloorDiv(Subtree<'a>, Subtree<'a>),
+    Mod(Subtree<'a>, Subtree<'a>),
+}
+
+/// Borrowed child of a node, see `PartEquation::walk`
+#[derive(Clone, Copy)]
+pub struct Subtree<'a>(&'a EquationComponentType);
+
+impl<'a> Subtree<'a> {
+    pub fn node(&self) -> Node<'a> {
+        match self.0 {
+            EquationComponentType::ConstantNode(i) => Node::Constant(i),
+            EquationComponentType::VariableNode(i) => Node::Variable(*i),
+            EquationComponentType::AddNode { lhs, rhs } => Node::Add(Subtree(lhs), Subtree(rhs)),
+            EquationComponentType::SubNode { lhs, rhs } => Node::Sub(Subtree(lhs), Subtree(rhs)),
+            EquationComponentType::MulNode { lhs, rhs } => Node::Mul(Subtree(lhs), Subtree(rhs)),
+            EquationComponentType::DivNode {
+                numerator,
+                denominator,
+            } => Node::Div {
+                numerator: Subtree(numerator),
+                denominator: Subtree(denominator),
+            },
+            EquationComponentType::PowNode { base, exponent } => Node::Pow {
+                base: Subtree(base),
+                exponent: Subtree(exponent),
+            },
+            EquationComponentType::LogNode { base, argument } => Node::Log {
+                base: Subtree(base),
+                argument: Subtree(argument),
+            },
+            EquationComponentType::MinusNode(value) => Node::Minus(Subtree(value)),
+            EquationComponentType::AbsNode(value) => Node::Abs(Subtree(value)),
+            EquationComponentType::ExpNode(value) => Node::Exp(Subtree(value)),
+            EquationComponentType::FloorDivNode { lhs, rhs } => {
+                Node::FloorDiv(Subtree(lhs), Subtree(rhs))
+            }
+            EquationComponentType::ModNode { lhs, rhs } => Node::Mod(Subtree(lhs), Subtree(rhs)),
+        }
+    }
+
+    pub fn to_part_equation(&self) -> PartEquation {
+        PartEquation { eq: self.0.clone() }
+    }
+}
+
+impl Display for Subtree<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Callbacks for `PartEquation::walk`, one per kind of node. Every callback does nothing unless
+/// overridden, the children of a node are visited after the node itself
+pub trait Visitor {
+    fn visit_constant(&mut self, _value: &Number) {}
+    fn visit_variable(&mut self, _variable: char) {}
+    fn visit_add(&mut self, _lhs: Subtree, _rhs: Subtree) {}
+    fn visit_sub(&mut self, _lhs: Subtree, _rhs: Subtree) {}
+    fn visit_mul(&mut self, _lhs: Subtree, _rhs: Subtree) {}
+    fn visit_div(&mut self, _numerator: Subtree, _denominator: Subtree) {}
+    fn visit_pow(&mut self, _base: Subtree, _exponent: Subtree) {}
+    fn visit_log(&mut self, _base: Subtree, _argument: Subtree) {}
+    fn visit_minus(&mut self, _value: Subtree) {}
+    fn visit_abs(&mut self, _value: Subtree) {}
+    fn visit_exp(&mut self, _value: Subtree) {}
+    fn visit_floor_div(&mut self, _lhs: Subtree, _rhs: Subtree) {}
+    fn visit_mod(&mut self, _lhs: Subtree, _rhs: Subtree) {}
+}
+
+fn walk<V: Visitor>(tree: Subtree, visitor: &mut V) {
+    match tree.node() {
+        Node::Constant(i) => visitor.visit_constant(i),
+        Node::Variable(i) => visitor.visit_variable(i),
+        Node::Add(lhs, rhs) => {
+            visitor.visit_add(lhs, rhs);
+            walk(lhs, visitor);
+            walk(rhs, visitor);
+        }
+        Node::Sub(lhs, rhs) => {
+            visitor.visit_sub(lhs, rhs);
+            walk(lhs, visitor);
+            walk(rhs, visitor);
+        }
+        Node::Mul(lhs, rhs) => {
+            visitor.visit_mul(lhs, rhs);
+            walk(lhs, visitor);
+            walk(rhs, visitor);
+        }
+        Node::Div {
+            numerator,
+            denominator,
+        } => {
+            visitor.visit_div(numerator, denominator);
+            walk(numerator, visitor);
+            walk(denominator, visitor);
+        }
+        Node::Pow { base, exponent } => {
+            visitor.visit_pow(base, exponent);
+            walk(base, visitor);
+            walk(exponent, visitor);
+        }
+        Node::Log { base, argument } => {
+            visitor.visit_log(base, argument);
+            walk(base, visitor);
+            walk(argument, visitor);
+        }
+        Node::Minus(value) => {
+            visitor.visit_minus(value);
+            walk(value, visitor);
+        }
+        Node::Abs(value) => {
+            visitor.visit_abs(value);
+            walk(value, visitor);
+        }
+        Node::Exp(value) => {
+            visitor.visit_exp(value);
+            walk(value, visitor);
+        }
+        Node::FloorDiv(lhs, rhs) => {
+            visitor.visit_floor_div(lhs, rhs);
+            walk(lhs, visitor);
+            walk(rhs, visitor);
+        }
+        Node::Mod(lhs, rhs) => {
+            visitor.visit_mod(lhs, rhs);
+            walk(lhs, visitor);
+            walk(rhs, visitor);
+        }
+    }
+}
+
+/// Number of times a variable occurs in an expression, counted without simplifying. A new kind of
+/// node implements it by adding up the occurrences in its children
+pub trait CountOccurrences {
+    fn count_occurrences(&self, variable: char) -> i64;
+}
+
+impl CountOccurrences for EquationComponentType {
+    fn count_occurrences(&self, variable: char) -> i64 {
+        let mut occurrences = 0;
+
+        match self {
+            EquationComponentType::VariableNode(i) if *i == variable => occurrences += 1,
+            EquationComponentType::ConstantNode(_) | EquationComponentType::VariableNode(_) => {}
+            EquationComponentType::AddNode { lhs, rhs } => {
+                occurrences += lhs.count_occurrences(variable);
+                occurrences += rhs.count_occurrences(variable);
+            }
+            EquationComponentType::SubNode { lhs, rhs } => {
+                occurrences += lhs.count_occurrences(variable);
+                occurrences += rhs.count_occurrences(variable);
+            }
+            EquationComponentType::MulNode { lhs, rhs } => {
+                occurrences += lhs.count_occurrences(variable);
+                occurrences += rhs.count_occurrences(variable);
+            }
+            EquationComponentType::DivNode {
+                numerator,
+                denominator,
+            } => {
+                occurrences += numerator.count_occurrences(variable);
+                occurrences += denominator.count_occurrences(variable);
+            }
+            EquationComponentType::PowNode { base, exponent } => {
+                occurrences += base.count_occurrences(variable);
+                occurrences += exponent.count_occurrences(variable);
+            }
+            EquationComponentType::LogNode { base, argument } => {
+                occurrences += base.count_occurrences(variable);
+                occurrences += argument.count_occurrences(variable);
+            }
+            EquationComponentType::MinusNode(value) => {
+                occurrences += value.count_occurrences(variable);
+            }
+            EquationComponentType::AbsNode(value) => {
+                occurrences += value.count_occurrences(variable);
+            }
+            EquationComponentType::ExpNode(value) => {
+                occurrences += value.count_occurrences(variable);
+            }
+            EquationComponentType::FloorDivNode { lhs, rhs }
+            | EquationComponentType::ModNode { lhs, rhs } => {
+                occurrences += lhs.count_occurrences(variable);
+                occurrences += rhs.count_occurrences(variable);
+            }
+        }
+
+        occurrences
+    }
+}
+
+impl CountOccurrences for Subtree<'_> {
+    fn count_occurrences(&self, variable: char) -> i64 {
+        self.0.count_occurrences(variable)
+    }
+}
+
+impl CountOccurrences for PartEquation {
+    fn count_occurrences(&self, variable: char) -> i64 {
+        self.eq.count_occurrences(variable)
+    }
+}
+
+impl CountOccurrences for Equation {
+    fn count_occurrences(&self, variable: char) -> i64 {
+        self.lhs.count_occurrences(variable) + self.rhs.count_occurrences(variable)
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl EquationComponentType {
+    // unsimplified tree, leaves are variables or integers in -5..=5 and exponents in 0..=3
+    fn random<R: Rng>(variables: &[char], max_depth: usize, rng: &mut R) -> Self {
+        // a leaf every so often before the depth runs out keeps the trees uneven
+        if max_depth == 0 || rng.below(4) == 0 {
+            if variables.is_empty() || rng.below(2) == 0 {
+                return EquationComponentType::ConstantNode(Number::from(rng.below(11) as i64 - 5));
+            }
+            let variable: char = variables[rng.below(variables.len() as u64) as usize];
+            return EquationComponentType::VariableNode(variable);
+        }
+
+        let (kind, exponent): (u64, u64) = (rng.below(10), rng.below(4));
+        let mut child = || Arc::new(Self::random(variables, max_depth - 1, rng));
+        match kind {
+            0 | 1 => EquationComponentType::AddNode {
+                lhs: child(),
+                rhs: child(),
+            },
+            2 => EquationComponentType::SubNode {
+                lhs: child(),
+                rhs: child(),
+            },
+            3 | 4 => EquationComponentType::MulNode {
+                lhs: child(),
+                rhs: child(),
+            },
+            5 => EquationComponentType::DivNode {
+                numerator: child(),
+                denominator: child(),
+            },
+            6 => EquationComponentType::PowNode {
+                base: child(),
+                exponent: Arc::new(EquationComponentType::ConstantNode(Number::from(exponent))),
+            },
+            7 => EquationComponentType::MinusNode(child()),
+            8 => EquationComponentType::AbsNode(child()),
+            _ => EquationComponentType::LogNode {
+                base: Arc::new(EquationComponentType::ConstantNode(Number::from(2))),
+                argument: child(),
+            },
+        }
+    }
+}
+
+/// An expression. Clones share their subtrees through `Arc`, so cloning is cheap
+/// and an expression can be sent to and shared between threads
+#[derive(Debug, Clone)]
+pub struct PartEquation {
+    eq: EquationComponentType,
+}
+
+impl PartEquation {
+    pub fn substitute(&self, variable: char, value: &PartEquation) -> PartEquation {
+        PartEquation {
+            eq: self.eq.substitute(variable, &value.eq).simplify().order(),
+        }
+    }
+
+    /// Substitutes the variable with the given name, a single letter or the name of a greek letter
+    /// like `theta` for `θ`. `None` for a name that is neither
+    pub fn substitute_named(&self, name: &str, value: &PartEquation) -> Option<PartEquation> {
+        named_variable(name).map(|variable| self.substitute(variable, value))
+    }
+
+    /// Returns the canonical form of the expression: simplified, with terms in a fixed order.
+    /// Two expressions compare equal exactly when their canonical forms are identical.
+    ///
+    /// ```
+    /// use math_engine::equation::PartEquation;
+    ///
+    /// let x = PartEquation::from('x');
+    /// let eq = &x + &x + 3 - 1;
+    ///
+    /// assert_eq!(eq.simplify().to_string(), "((2 * x) + 2)");
+    /// ```
+    pub fn simplify(&self) -> Self {
+        self.simplify_with(&SimplifyOptions::default())
+    }
+
+    /// Simplifies the expression with the rewrites selected in `options`, see `SimplifyOptions`
+    pub fn simplify_with(&self, options: &SimplifyOptions) -> Self {
+        let mut eq: EquationComponentType = self.eq.simplify_with(options).order();
+
+        if options.fixpoint {
+            for _ in 0..FIXPOINT_PASSES {
+                let next: EquationComponentType = eq.simplify_with(options).order();
+                if next == eq {
+                    break;
+                }
+                eq = next;
+            }
+        }
+
+        PartEquation { eq }
+    }
+
+    /// Hash of the canonical form, equal expressions have equal keys
+    pub fn hash_key(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Empties this thread's cache of `simplify` results. Only whole expressions are cached, the
+    /// subexpressions simplified along the way are not
+    #[cfg(feature = "cache")]
+    pub fn clear_simplify_cache() {
+        SIMPLIFY_CACHE.with(|cache| cache.borrow_mut().clear());
+    }
+
+    /// Checks if `sub` occurs anywhere in the simplified form of the expression
+    pub fn contains(&self, sub: &PartEquation) -> bool {
+        self.simplify().eq.contains(&sub.simplify().eq)
+    }
+
+    /// Checks if `variable` does not occur in the simplified form of the expression,
+    /// so `x - x + y` is free of `x`
+    pub fn free_of(&self, variable: char) -> bool {
+        self.eq.simplify().free_of(variable)
+    }
+
+    /// Replaces every occurrence of `target` in the simplified form of the expression with `replacement`
+    pub fn replace(&self, target: &PartEquation, replacement: &PartEquation) -> PartEquation {
+        PartEquation {
+            eq: self
+                .simplify()
+                .eq
+                .replace(&target.simplify().eq, &replacement.eq)
+                .simplify()
+                .order(),
+        }
+    }
+
+    /// Highest power of `variable` in the expanded expression, 0 if the expression is free of it.
+    /// Returns `None` if `variable` appears under a non-polynomial operation such as a log
+    pub fn degree(&self, variable: char) -> Option<i64> {
+        let eq: EquationComponentType = self.eq.simplify();
+
+        // expanding lets terms cancel: (x + 1)^2 - x^2 has degree 1
+        if let Some(polynomial) = eq.to_polynomial(variable) {
+            return Some(polynomial.degree().unwrap_or(0) as i64);
+        }
+        eq.degree(variable)
+    }
+
+    /// Whether the expression is `a * variable + b` with `a` and `b` free of `variable`,
+    /// i.e. a polynomial of degree 1 in it
+    pub fn is_linear(&self, variable: char) -> bool {
+        self.degree(variable) == Some(1)
+    }
+
+    /// Whether the expression is a polynomial in `variable`: after expansion the variable only
+    /// appears in sums, products and non-negative integer powers. Other variables count as constants
+    pub fn is_polynomial(&self, variable: char) -> bool {
+        self.degree(variable).is_some()
+    }
+
+    /// Derivative of the expression with respect to `variable`, other variables are constants
+    pub fn differentiate(&self, variable: char) -> PartEquation {
+        PartEquation {
+            eq: self
+                .eq
+                .simplify()
+                .differentiate(variable)
+                .simplify()
+                .order(),
+        }
+    }
+
+    /// Differentiates `n` times with respect to `variable`, simplifying after every step.
+    /// The 0th derivative is the simplified expression
+    pub fn nth_derivative(&self, variable: char, n: usize) -> PartEquation {
+        let mut derivative: PartEquation = self.simplify();
+        for _ in 0..n {
+            derivative = derivative.differentiate(variable);
+        }
+        derivative
+    }
+
+    /// Taylor polynomial of the expression in `variable` about `about`, up to and including the
+    /// `(variable - about) ^ order` term. Fails if a derivative cannot be evaluated at `about`
+    ///
+    /// ```
+    /// use math_engine::equation::PartEquation;
+    /// use math_engine::number::Number;
+    ///
+    /// let x = PartEquation::from('x');
+    /// let eq = x.pow(&PartEquation::from(3));
+    ///
+    /// assert_eq!(
+    ///     eq.taylor('x', &Number::from(1), 1).unwrap(),
+    ///     (&x - 1) * 3 + 1
+    /// );
+    /// ```
+    pub fn taylor(
+        &self,
+        variable: char,
+        about: &Number,
+        order: usize,
+    ) -> Result<PartEquation, MathError> {
+        let point: PartEquation = PartEquation::from(about.clone());
+        let offset: PartEquation = PartEquation::from(variable) - &point;
+
+        let mut derivative: PartEquation = self.simplify();
+        let mut factorial: Number = Number::from(1);
+        let mut result: PartEquation = PartEquation::from(0);
+
+        for n in 0..=order {
+            if n > 0 {
+                derivative = derivative.differentiate(variable);
+                factorial *= Number::from(n);
+            }
+
+            let coefficient: Number = derivative.substitute(variable, &point).evaluate()?;
+            if coefficient == Number::from(0) {
+                continue;
+            }
+
+            let term: PartEquation =
+                PartEquation::from((coefficient / factorial.clone()).normalize());
+            if n == 0 {
+                result = result + term;
+            } else {
+                result = result + term * offset.pow(&PartEquation::from(Number::from(n)));
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Antiderivative of the expression with respect to `variable`, without the integration constant.
+    /// Handles sums, constant factors and powers of `variable`, with `1/x` integrating to `ln(x)`.
+    /// Other forms, such as products of non-constant factors, fail with `NotYetImplemented`
+    ///
+    /// ```
+    /// use math_engine::equation::PartEquation;
+    ///
+    /// let x = PartEquation::from('x');
+    /// let eq = &x * 2 + 1;
+    ///
+    /// assert_eq!(eq.integrate('x').unwrap(), x.pow(&PartEquation::from(2)) + &x);
+    /// ```
+    pub fn integrate(&self, variable: char) -> Result<PartEquation, MathError> {
+        Ok(PartEquation {
+            eq: self.eq.simplify().integrate(variable)?.simplify().order(),
+        })
+    }
+
+    /// Definite integral of the expression over `variable` from `lower` to `upper`,
+    /// the antiderivative from `integrate` evaluated at both bounds
+    pub fn integrate_between(
+        &self,
+        variable: char,
+        lower: &Number,
+        upper: &Number,
+    ) -> Result<Number, MathError> {
+        let antiderivative: PartEquation = self.integrate(variable)?;
+
+        let upper: Number = antiderivative
+            .substitute(variable, &PartEquation::from(upper.clone()))
+            .evaluate()?;
+        let lower: Number = antiderivative
+            .substitute(variable, &PartEquation::from(lower.clone()))
+            .evaluate()?;
+        Ok((upper - lower).normalize())
+    }
+
+    /// Splits the expression into `(numerator, denominator)` after combining its fractions,
+    /// `x + 1/x` gives `(x^2 + 1, x)`. The denominator is 1 if there is no division at the top
+    pub fn as_fraction(&self) -> (PartEquation, PartEquation) {
+        let options: SimplifyOptions = SimplifyOptions::new().combine_fractions(true);
+
+        match self.simplify_with(&options).eq {
+            EquationComponentType::DivNode {
+                numerator,
+                denominator,
+            } => (
+                PartEquation {
+                    eq: Arc::unwrap_or_clone(numerator),
+                }
+                .simplify(),
+                PartEquation {
+                    eq: Arc::unwrap_or_clone(denominator),
+                }
+                .simplify(),
+            ),
+            eq => (PartEquation { eq }, PartEquation::from(1)),
+        }
+    }
+
+    /// Degrees of the numerator and denominator in `variable`, see `as_fraction`.
+    /// Returns `None` if either of them is not a polynomial in `variable`
+    pub fn numer_denom_degree(&self, variable: char) -> Option<(i64, i64)> {
+        let (numerator, denominator) = self.as_fraction();
+        Some((numerator.degree(variable)?, denominator.degree(variable)?))
+    }
+
+    /// Converts the expression to a polynomial in `variable`, expanding products and powers.
+    /// Returns `None` if the expression has other variables, non-integer powers or float coefficients
+    pub fn to_polynomial(&self, variable: char) -> Option<Polynomial> {
+        self.eq.to_polynomial(variable)
+    }
+
+    /// Coefficient of the highest power of `variable` after expansion, see `to_polynomial`.
+    /// The zero polynomial has leading coefficient 0
+    pub fn leading_coefficient(&self, variable: char) -> Option<Number> {
+        let polynomial: Polynomial = self.to_polynomial(variable)?;
+        Some(
+            polynomial
+                .coefficients()
+                .last()
+                .cloned()
+                .unwrap_or(Number::from(0)),
+        )
+    }
+
+    /// Term free of `variable` after expansion, see `to_polynomial`
+    pub fn constant_term(&self, variable: char) -> Option<Number> {
+        let polynomial: Polynomial = self.to_polynomial(variable)?;
+        Some(
+            polynomial
+                .coefficients()
+                .first()
+                .cloned()
+                .unwrap_or(Number::from(0)),
+        )
+    }
+
+    /// Expands the expression into a map from monomials, each a list of variable powers sorted
+    /// by variable, to their coefficients, the inverse of `from_monomials`. Expressions with equal
+    /// maps are equal as polynomials: `x * (y + 1)` and `x * y + x` both give
+    /// `{[('x', 1)]: 1, [('x', 1), ('y', 1)]: 1}`. Negative powers come from dividing by a single
+    /// monomial. Returns `None` for logs, `abs`, `exp`, non-integer powers and division by a sum
+    pub fn monomials(&self) -> Option<BTreeMap<Vec<(char, i64)>, Number>> {
+        self.eq.monomials()
+    }
+
+    /// Builds the sum of the given monomials, each a coefficient and a list of variable powers
+    pub fn from_monomials(terms: &[(Number, Vec<(char, i64)>)]) -> PartEquation {
+        let mut result: EquationComponentType =
+            EquationComponentType::ConstantNode(Number::from(0));
+
+        for (coefficient, powers) in terms.iter() {
+            let mut term: EquationComponentType =
+                EquationComponentType::ConstantNode(coefficient.clone());
+
+            for (variable, exponent) in powers.iter() {
+                term = EquationComponentType::MulNode {
+                    lhs: Arc::new(term),
+                    rhs: Arc::new(EquationComponentType::PowNode {
+                        base: Arc::new(EquationComponentType::VariableNode(*variable)),
+                        exponent: Arc::new(EquationComponentType::ConstantNode(Number::from(
+                            *exponent,
+                        ))),
+                    }),
+                };
+            }
+
+            result = EquationComponentType::AddNode {
+                lhs: Arc::new(result),
+                rhs: Arc::new(term),
+            };
+        }
+
+        PartEquation {
+            eq: result.simplify().order(),
+        }
+    }
+
+    /// Simplifies the expression, additionally applying rewrites that are only valid under
+    /// `assumptions`, such as `sqrt(x ^ 2) -> x` for `x > 0` or `e ^ ln(x) -> x` for `x > 0`.
+    /// Without assumptions `sqrt(x ^ 2)` simplifies to `|x|`
+    pub fn simplify_with_assumptions(&self, assumptions: &Assumptions) -> Self {
+        PartEquation {
+            eq: self.eq.simplify().assume(assumptions).simplify().order(),
+        }
+    }
+
+    pub fn pi() -> Self {
+        PartEquation::from(Number::pi())
+    }
+
+    pub fn e() -> Self {
+        PartEquation::from(Number::e())
+    }
+
+    /// Plain infix form readable by Python and SymPy: `**` for powers, explicit `*`, parentheses
+    /// only where precedence needs them and `log`, `exp`, `Abs`, `pi`, `E` and `I` for the
+    /// functions and constants, so `x^2 + 1` gives `x**2 + 1`
+    pub fn to_infix(&self) -> String {
+        self.eq.to_infix().0
+    }
+
+    /// The number the expression consists of, if it is a single constant
+    pub fn as_number(&self) -> Option<&Number> {
+        match &self.eq {
+            EquationComponentType::ConstantNode(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// `Display` form broken over several lines if it is wider than `width` characters and a sum:
+    /// as many terms as fit on each line, every line but the first starting with the `+` or `-`
+    /// of its first term so that the operators line up in the first column, the first line
+    /// indented to match. Other expressions print as with `Display`
+    pub fn to_pretty(&self, width: usize) -> String {
+        let line: String = self.to_string();
+        if line.chars().count() <= width {
+            return line;
+        }
+
+        let mut terms: Vec<(bool, EquationComponentType)> = Vec::new();
+        self.eq.signed_terms(false, &mut terms);
+        if terms.len() < 2 {
+            return line;
+        }
+
+        let mut lines: Vec<String> = Vec::new();
+        let mut current: String = String::new();
+        for (i, (negated, term)) in terms.iter().enumerate() {
+            let operator: char = match (i, negated) {
+                (_, true) => '-',
+                (0, false) => ' ',
+                (_, false) => '+',
+            };
+            let piece: String = format!("{} {}", operator, term);
+
+            // a term wider than `width` gets a line of its own
+            if current.is_empty() {
+                current = piece;
+            } else if current.chars().count() + 1 + piece.chars().count() <= width {
+                current.push(' ');
+                current.push_str(&piece);
+            } else {
+                lines.push(current);
+                current = piece;
+            }
+        }
+        lines.push(current);
+
+        lines.join("\n")
+    }
+
+    /// Evaluates an expression without variables to a number
+    pub fn evaluate(&self) -> Result<Number, MathError> {
+        Ok(self.eq.evaluate()?.normalize())
+    }
+
+    /// Visits every node of the expression with `visitor`, parents before their children.
+    /// Custom analyses implement `Visitor`:
+    ///
+    /// ```
+    /// use math_engine::equation::{PartEquation, Subtree, Visitor};
+    ///
+    /// struct CountPowers(usize);
+    ///
+    /// impl Visitor for CountPowers {
+    ///     fn visit_pow(&mut self, _base: Subtree, _exponent: Subtree) {
+    ///         self.0 += 1;
+    ///     }
+    /// }
+    ///
+    /// let x = PartEquation::from('x');
+    /// let mut counter = CountPowers(0);
+    /// (x.pow(&PartEquation::from(2)) + x.sqrt()).walk(&mut counter);
+    ///
+    /// assert_eq!(counter.0, 2);
+    /// ```
+    pub fn walk<V: Visitor>(&self, visitor: &mut V) {
+        walk(Subtree(&self.eq), visitor);
+    }
+
+    /// Returns the root node of the expression, see `walk` for visiting all of them
+    pub fn node(&self) -> Node<'_> {
+        Subtree(&self.eq).node()
+    }
+
+    /// Pulls the factors common to every term out of a sum, the reverse of distributing:
+    /// `x * y + x * z -> x * (y + z)` and `2 * x + 4 -> 2 * (x + 2)`. Only integer
+    /// coefficients are factored numerically. Not part of `simplify`, which would not
+    /// keep the product apart
+    pub fn factor(&self) -> Self {
+        let eq: EquationComponentType = self.eq.simplify();
+        PartEquation {
+            eq: eq.factor().unwrap_or(eq).order(),
+        }
+    }
+
+    /// Subtrees other than single variables and constants that occur more than once, with the
+    /// number of occurrences. Inner subtrees come before the ones containing them, so they can be
+    /// bound in order when compiling
+    pub fn common_subexpressions(&self) -> Vec<(PartEquation, usize)> {
+        let mut counts: HashMap<EquationComponentType, usize> = HashMap::new();
+        let mut order: Vec<EquationComponentType> = Vec::new();
+        self.eq.order().count_subtrees(&mut counts, &mut order);
+
+        order
+            .into_iter()
+            .filter_map(|eq| {
+                let count: usize = counts[&eq];
+                if count > 1 {
+                    Some((PartEquation { eq }, count))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Prepares the expression for fast repeated evaluation at `f64` precision, e.g. for plotting.
+    /// The tree is simplified and traversed once, the returned function only does the arithmetic.
+    /// Any variable other than `variable` makes evaluation fail with `EquationMismatchError`, a
+    /// value that would be complex fails with `DomainError` as in `Program::run`
+    ///
+    /// ```
+    /// use math_engine::equation::PartEquation;
+    ///
+    /// let x = PartEquation::from('x');
+    /// let f = (x.pow(&PartEquation::from(2)) + 1).compile('x');
+    ///
+    /// assert_eq!(f(3.0).unwrap(), 10.0);
+    /// ```
+    pub fn compile(&self, variable: char) -> impl Fn(f64) -> Result<f64, MathError> {
+        self.eq.simplify().compile(variable)
+    }
+
+    /// Lowers the simplified expression to a stack machine program, for evaluating it with
+    /// many different variable values
+    ///
+    /// ```
+    /// use math_engine::equation::PartEquation;
+    ///
+    /// let x = PartEquation::from('x');
+    /// let y = PartEquation::from('y');
+    /// let program = (&x * &y + 1).to_program();
+    ///
+    /// assert_eq!(program.run(&[('x', 2.0), ('y', 3.0)]).unwrap(), 7.0);
+    /// ```
+    pub fn to_program(&self) -> Program {
+        let mut code: Vec<OpCode> = Vec::new();
+        self.eq.simplify().lower(&mut code);
+        Program::new(code)
+    }
+
+    /// Checks that both expressions agree at `samples` pseudo-random points in `[-10, 10)` for every
+    /// variable, up to `tolerance` relative to the larger value. Points where either side is undefined
+    /// are skipped, if neither is defined at any point the expressions are not considered equal
+    ///
+    /// ```
+    /// use math_engine::equation::PartEquation;
+    ///
+    /// let x = PartEquation::from('x');
+    /// let lhs = (&x + 1).pow(&PartEquation::from(2));
+    /// let rhs = x.pow(&PartEquation::from(2)) + &x * 2 + 1;
+    ///
+    /// assert!(lhs.numerically_equal(&rhs, 20, 1e-9));
+    /// ```
+    pub fn numerically_equal(&self, other: &PartEquation, samples: usize, tolerance: f64) -> bool {
+        let mut variables: Vec<char> = Vec::new();
+        self.eq.collect_variables(&mut variables);
+        other.eq.collect_variables(&mut variables);
+
+        let (lhs, rhs) = (self.to_program(), other.to_program());
+
+        // a fixed seed, so that a failure can be reproduced
+        let mut rng: XorShift = XorShift::new(0x9E37_79B9_7F4A_7C15);
+        let mut next = || (rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64 * 20.0 - 10.0;
+
+        let mut compared: usize = 0;
+        for _ in 0..samples {
+            let point: Vec<(char, f64)> = variables.iter().map(|v| (*v, next())).collect();
+
+            if let (Ok(lhs), Ok(rhs)) = (lhs.run(&point), rhs.run(&point)) {
+                if lhs.is_nan() && rhs.is_nan() {
+                    continue;
+                }
+                if (lhs - rhs).abs() > tolerance * lhs.abs().max(rhs.abs()).max(1.0) {
+                    return false;
+                }
+                compared += 1;
+            }
+        }
+
+        compared > 0
+    }
+
+    /// Random expression of at most `max_depth` levels over `variables`, for fuzzing.
+    /// The tree is not simplified, and may be undefined for some or all values, e.g. `1 / (x - x)`
+    #[cfg(any(test, feature = "test-util"))]
+    pub fn random<R: Rng>(variables: &[char], max_depth: usize, rng: &mut R) -> PartEquation {
+        PartEquation {
+            eq: EquationComponentType::random(variables, max_depth, rng),
+        }
+    }
+
+    /// Evaluates the expression at each of `points`, substituting them for `variable`
+    pub fn subs(&self, variable: char, points: &[f64]) -> Result<Vec<f64>, MathError> {
+        let f = self.compile(variable);
+        points.iter().map(|x| f(*x)).collect()
+    }
+
+    /// Logarithm of the expression to the given base
+    pub fn log(&self, base: &PartEquation) -> Self {
+        PartEquation {
+            eq: EquationComponentType::LogNode {
+                base: Arc::new(base.eq.clone()),
+                argument: Arc::new(self.eq.clone()),
+            }
+            .simplify()
+            .order(),
+        }
+    }
+
+    pub fn ln(&self) -> Self {
+        self.log(&PartEquation::e())
+    }
+
+    /// e raised to the expression
+    pub fn exp(&self) -> Self {
+        PartEquation {
+            eq: EquationComponentType::ExpNode(Arc::new(self.eq.clone()))
+                .simplify()
+                .order(),
+        }
+    }
+
+    /// Absolute value of the expression
+    pub fn abs(&self) -> Self {
+        PartEquation {
+            eq: EquationComponentType::AbsNode(Arc::new(self.eq.clone()))
+                .simplify()
+                .order(),
+        }
+    }
+
+    pub fn sqrt(&self) -> Self {
+        self.pow(&(PartEquation::from(1) / PartEquation::from(2)))
+    }
+
+    pub fn pow(&self, exponent: &PartEquation) -> Self {
+        PartEquation {
+            eq: EquationComponentType::PowNode {
+                base: Arc::new(self.eq.clone()),
+                exponent: Arc::new(exponent.eq.clone()),
+            }
+            .simplify()
+            .order(),
+        }
+    }
+
+    /// Floor division `floor(self / divisor)`, computed when both sides are numbers:
+    /// `floor(7 / 2)` is `3` and `floor(-7 / 2)` is `-4`
+    pub fn floordiv(&self, divisor: &PartEquation) -> Self {
+        PartEquation {
+            eq: EquationComponentType::FloorDivNode {
+                lhs: Arc::new(self.eq.clone()),
+                rhs: Arc::new(divisor.eq.clone()),
+            }
+            .simplify()
+            .order(),
+        }
+    }
+
+    /// Remainder of `floordiv`, `self - divisor * floor(self / divisor)`, which takes the
+    /// sign of the divisor. Computed when both sides are numbers, `x mod 2` is kept as is
+    pub fn rem(&self, divisor: &PartEquation) -> Self {
+        PartEquation {
+            eq: EquationComponentType::ModNode {
+                lhs: Arc::new(self.eq.clone()),
+                rhs: Arc::new(divisor.eq.clone()),
+            }
+            .simplify()
+            .order(),
+        }
+    }
+}
+
+impl Display for PartEquation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.eq)
+    }
+}
+
+impl PartialEq for PartEquation {
+    fn eq(&self, other: &Self) -> bool {
+        self.eq.simplify().order() == other.eq.simplify().order()
+    }
+}
+
+impl Eq for PartEquation {}
+
+impl Hash for PartEquation {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.eq.simplify().order().hash(state);
+    }
+}
+
+/// The constant `0`
+impl Default for PartEquation {
+    fn default() -> Self {
+        PartEquation::from(0)
+    }
+}
+
+impl From<char> for PartEquation {
+    fn from(value: char) -> Self {
+        PartEquation {
+            eq: EquationComponentType::VariableNode(value),
+        }
+    }
+}
+
+impl From<Number> for PartEquation {
+    fn from(value: Number) -> Self {
+        PartEquation {
+            eq: EquationComponentType::ConstantNode(value),
+        }
+    }
+}
+
+impl From<i8> for PartEquation {
+    fn from(value: i8) -> Self {
+        PartEquation {
+            eq: EquationComponentType::ConstantNode(Number::from(value)),
+        }
+    }
+}
+
+impl From<i16> for PartEquation {
+    fn from(value: i16) -> Self {
+        PartEquation {
+            eq: EquationComponentType::ConstantNode(Number::from(value)),
+        }
+    }
+}
+
+impl From<i32> for PartEquation {
+    fn from(value: i32) -> Self {
+        PartEquation {
+            eq: EquationComponentType::ConstantNode(Number::from(value)),
+        }
+    }
+}
+
+impl From<i64> for PartEquation {
+    fn from(value: i64) -> Self {
+        PartEquation {
+            eq: EquationComponentType::ConstantNode(Number::from(value)),
+        }
+    }
+}
+
+impl From<i128> for PartEquation {
+    fn from(value: i128) -> Self {
+        PartEquation {
+            eq: EquationComponentType::ConstantNode(Number::from(value)),
+        }
+    }
+}
+
+impl From<u8> for PartEquation {
+    fn from(value: u8) -> Self {
+        PartEquation {
+            eq: EquationComponentType::ConstantNode(Number::from(value)),
+        }
+    }
+}
+
+impl From<u16> for PartEquation {
+    fn from(value: u16) -> Self {
+        PartEquation {
+            eq: EquationComponentType::ConstantNode(Number::from(value)),
+        }
+    }
+}
+
+impl From<u32> for PartEquation {
+    fn from(value: u32) -> Self {
+        PartEquation {
+            eq: EquationComponentType::ConstantNode(Number::from(value)),
+        }
+    }
+}
+
+impl From<u64> for PartEquation {
+    fn from(value: u64) -> Self {
+        PartEquation {
+            eq: EquationComponentType::ConstantNode(Number::from(value)),
+        }
+    }
+}
+
+impl From<u128> for PartEquation {
+    fn from(value: u128) -> Self {
+        PartEquation {
+            eq: EquationComponentType::ConstantNode(Number::from(value)),
+        }
+    }
+}
+
+impl From<f32> for PartEquation {
+    fn from(value: f32) -> Self {
+        PartEquation {
+            eq: EquationComponentType::ConstantNode(Number::from(value)),
+        }
+    }
+}
+
+impl From<f64> for PartEquation {
+    fn from(value: f64) -> Self {
+        PartEquation {
+            eq: EquationComponentType::ConstantNode(Number::from(value)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EquationClass {
+    /// Holds for every value of the variables, e.g. `x = x`
+    Identity,
+    /// Holds for no value of the variables, e.g. `x + 1 = x`
+    Contradiction,
+    Solvable,
+}
+
+/// Two expressions that are equal, shared between clones and threads like `PartEquation`
+#[derive(Debug, Clone)]
+pub struct Equation {
+    lhs: EquationComponentType,
+    rhs: EquationComponentType,
+}
+
+enum AntiOperations {
+    AddLHS,
+    AddRHS,
+    SubLHS,
+    SubRHS,
+    MulNumerator,
+    MulDenominator,
+    DivLHS,
+    DivRHS,
+    PowLHS,
+    PowRHS,
+    LogLHS,
+    LogRHS,
+    Minus,
+    Ln,
+}
+
+impl Equation {
+    pub fn new(lhs: &PartEquation, rhs: &PartEquation) -> Self {
+        Equation {
+            lhs: lhs.eq.clone(),
+            rhs: rhs.eq.clone(),
+        }
+    }
+
+    /// Same as `new`, taking the sides by value so they are not cloned
+    pub fn from_parts(lhs: PartEquation, rhs: PartEquation) -> Self {
+        Equation {
+            lhs: lhs.eq,
+            rhs: rhs.eq,
+        }
+    }
+
+    /// Replaces every occurrence of `variable` on both sides with `value`, then simplifies each side
+    pub fn substitute(&self, variable: char, value: &PartEquation) -> Equation {
+        Equation {
+            lhs: self.lhs.substitute(variable, &value.eq).simplify().order(),
+            rhs: self.rhs.substitute(variable, &value.eq).simplify().order(),
+        }
+    }
+
+    /// Builds the equation `expr = 0`
+    pub fn from_expr(expr: &PartEquation) -> Self {
+        Equation::new(expr, &PartEquation::from(0))
+    }
+
+    /// Returns the simplified `lhs - rhs`, the expression that is zero when the equation holds
+    pub fn to_zero_form(&self) -> PartEquation {
+        PartEquation {
+            eq: self.difference().order(),
+        }
+    }
+
+    /// Multiplies both sides by the product of their denominators after combining fractions,
+    /// see `PartEquation::as_fraction`, so `1/x + 1/y = 1` becomes `y + x = x * y`.
+    /// Values that make a denominator zero may become solutions of the result
+    pub fn clear_denominators(&self) -> Equation {
+        let (a, b) = PartEquation {
+            eq: self.lhs.clone(),
+        }
+        .as_fraction();
+        let (c, d) = PartEquation {
+            eq: self.rhs.clone(),
+        }
+        .as_fraction();
+
+        let (lhs, rhs): (PartEquation, PartEquation) = if b == d { (a, c) } else { (a * d, c * b) };
+        Equation {
+            lhs: lhs.eq.order(),
+            rhs: rhs.eq.order(),
+        }
+    }
+
+    /// Classifies the equation by simplifying `lhs - rhs`
+    pub fn classify(&self) -> EquationClass {
+        match self.difference() {
+            EquationComponentType::ConstantNode(i) => {
+                if i == Number::from(0) {
+                    EquationClass::Identity
+                } else {
+                    EquationClass::Contradiction
+                }
+            }
+            _ => EquationClass::Solvable,
+        }
+    }
+
+    /// Solves for `variable` by applying inverse operations, which requires `variable` to occur
+    /// exactly once after simplifying `lhs - rhs`. Every node on the path to it is inverted:
+    /// sums, differences, products, quotients, negation, powers in the base (`x ^ n`) or the
+    /// exponent (`n ^ x`), and logs in the argument (`log_b(x)`) or the base (`log_x(a)`).
+    /// Equations linear in `variable` are solved directly, see `solve_linear`. Quadratics with
+    /// more than one occurrence of `variable` give the first root of `solve_quadratic`.
+    ///
+    /// Powers are inverted with the principal root: `x ^ 2 = 9` gives `3`. For a rational
+    /// exponent `p/q`, `x ^ (p/q)` is read as the real `q`-th root of `x` raised to `p`, so
+    /// `x ^ (3/2) = 8` gives `4` and `x ^ (1/3) = -8` gives `-512`, while a negative value
+    /// is `NoSolution` when `p` or `q` is even, as in `x ^ (2/3) = -4`
+    pub fn solve(&self, variable: char) -> Result<PartEquation, MathError> {
+        if self.is_linear(variable) {
+            return self.solve_linear(variable);
+        }
+        match self.solve_with_steps(variable) {
+            Ok((result, _)) => Ok(result),
+            Err(MathError::NotYetImplemented) if self.is_quadratic(variable) => {
+                let (root, _) = self.solve_quadratic(variable)?;
+                Ok(root)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Solves for the expression `target` instead of a variable by replacing it with an unused
+    /// variable in both sides, see `PartEquation::replace`, and solving for that, so
+    /// `x + y ^ 2 = 5` gives `y ^ 2 = 5 - x`. Occurrences of `target` are matched in the
+    /// simplified sides, the result may still contain variables of `target` that occur
+    /// outside of it. Fails with `EquationMismatchError` if `target` does not occur
+    pub fn solve_for(&self, target: &PartEquation) -> Result<PartEquation, MathError> {
+        let target: PartEquation = target.simplify();
+        if let EquationComponentType::VariableNode(variable) = target.eq {
+            return self.solve(variable);
+        }
+
+        let mut variables: Vec<char> = Vec::new();
+        self.lhs.collect_variables(&mut variables);
+        self.rhs.collect_variables(&mut variables);
+        target.eq.collect_variables(&mut variables);
+        let unknown: char = match ('a'..='z')
+            .chain('A'..='Z')
+            .find(|c| !variables.contains(c))
+        {
+            Some(unknown) => unknown,
+            None => return Err(MathError::NotYetImplemented),
+        };
+
+        let replacement: PartEquation = PartEquation::from(unknown);
+        let lhs: PartEquation = PartEquation {
+            eq: self.lhs.clone(),
+        }
+        .replace(&target, &replacement);
+        let rhs: PartEquation = PartEquation {
+            eq: self.rhs.clone(),
+        }
+        .replace(&target, &replacement);
+
+        let solution: PartEquation = Equation::from_parts(lhs, rhs).solve(unknown)?;
+        Ok(solution.substitute(unknown, &target))
+    }
+
+    /// Checks if `variable = candidate` satisfies the equation by substituting it into both
+    /// sides and evaluating them. Integers and rationals are compared exactly, a float or complex
+    /// side up to a relative difference of 1e-9. Fails as `PartEquation::evaluate` does, with
+    /// `EquationMismatchError` if another variable remains or `ZeroDivisionError` if the
+    /// candidate makes a denominator zero
+    pub fn check(&self, variable: char, candidate: &Number) -> Result<bool, MathError> {
+        let value: PartEquation = PartEquation::from(candidate.clone());
+        let lhs: Number = PartEquation {
+            eq: self.lhs.clone(),
+        }
+        .substitute(variable, &value)
+        .evaluate()?;
+        let rhs: Number = PartEquation {
+            eq: self.rhs.clone(),
+        }
+        .substitute(variable, &value)
+        .evaluate()?;
+
+        if let (
+            Number::Integer(_) | Number::Rational(_),
+            Number::Integer(_) | Number::Rational(_),
+        ) = (&lhs, &rhs)
+        {
+            return Ok(lhs == rhs);
+        }
+
+        let abs = |n: &Number| match n {
+            Number::Complex(c) => c.real().clone().to_f64().hypot(c.imag().clone().to_f64()),
+            n => n.to_f64().abs(),
+        };
+        let difference: f64 = abs(&(&lhs - &rhs));
+        Ok(difference <= CHECK_TOLERANCE * abs(&lhs).max(abs(&rhs)).max(1.0))
+    }
+
+    /// Degree of the zero form `lhs - rhs` in `variable`, see `PartEquation::degree`
+    pub fn degree(&self, variable: char) -> Option<i64> {
+        self.to_zero_form().degree(variable)
+    }
+
+    /// Whether the zero form has degree 1 in `variable`
+    pub fn is_linear(&self, variable: char) -> bool {
+        self.degree(variable) == Some(1)
+    }
+
+    /// Whether the zero form has degree 2 in `variable`
+    pub fn is_quadratic(&self, variable: char) -> bool {
+        self.degree(variable) == Some(2)
+    }
+
+    /// Solves `a * x + b = 0` as `x = -b / a`, reading `a` as the derivative of the zero form and
+    /// `b` as its value at `x = 0`, so `x` may occur any number of times. A zero `a` gives
+    /// `InfiniteSolutions` or `NoSolution`, fails with `EquationMismatchError` when the zero form
+    /// is not a polynomial of degree at most 1 in `variable`
+    pub fn solve_linear(&self, variable: char) -> Result<PartEquation, MathError> {
+        let eq: PartEquation = self.to_zero_form();
+        if !matches!(eq.degree(variable), Some(0) | Some(1)) {
+            return Err(MathError::EquationMismatchError);
+        }
+
+        let a: PartEquation = eq.differentiate(variable);
+        let b: PartEquation = eq.substitute(variable, &PartEquation::from(0));
+        let zero: PartEquation = PartEquation::from(0);
+        if a == zero {
+            if b == zero {
+                return Err(MathError::InfiniteSolutions);
+            }
+            return Err(MathError::NoSolution);
+        }
+
+        Ok(PartEquation {
+            eq: (-b / a).eq.order(),
+        })
+    }
+
+    /// Same as `solve`, also returning the inverse operations applied to `lhs = rhs` as readable
+    /// steps, e.g. `2 * x + 1 = 5` gives "subtract 1 from both sides" and "divide both sides by 2".
+    /// A variable on both sides is first moved to the left by subtracting the right side
+    pub fn solve_with_steps(
+        &self,
+        variable: char,
+    ) -> Result<(PartEquation, Vec<String>), MathError> {
+        match self.classify() {
+            EquationClass::Identity => return Err(MathError::InfiniteSolutions),
+            EquationClass::Contradiction => return Err(MathError::NoSolution),
+            EquationClass::Solvable => {}
+        }
+
+        let lhs: EquationComponentType = self.lhs.simplify().order();
+        let rhs: EquationComponentType = self.rhs.simplify().order();
+        let mut steps: Vec<String> = Vec::new();
+
+        // the side holding the variable is inverted down to it, with the other side as the start
+        let (eq, other_side): (EquationComponentType, EquationComponentType) = match (
+            lhs.count_occurrences(variable),
+            rhs.count_occurrences(variable),
+        ) {
+            (_, 0) => (lhs, rhs),
+            (0, _) => (rhs, lhs),
+            _ => {
+                steps.push(Self::sum_step(&rhs, true));
+                (
+                    self.difference().order(),
+                    EquationComponentType::ConstantNode(Number::from(0)),
+                )
+            }
+        };
+
+        if eq.count_occurrences(variable) > 1 {
+            // TODO: Implement numeric approximation
+            return Err(MathError::NotYetImplemented);
+        } else if eq.count_occurrences(variable) == 0 {
+            return Err(MathError::EquationMismatchError);
+        }
+
+        let result: EquationComponentType =
+            Self::do_inverse(&eq, other_side, variable, &mut steps)?;
+        Ok((PartEquation { eq: result }, steps))
+    }
+
+    /// Solves for `variable` and formats the result as `variable = solution`
+    pub fn display_solution(&self, variable: char) -> Result<String, MathError> {
+        let solution: PartEquation = self.solve(variable)?;
+        Ok(Equation::from_parts(PartEquation::from(variable), solution).to_string())
+    }
+
+    /// Solves `a x^2 + b x + c = 0` with the quadratic formula. The two roots are complex
+    /// conjugates when the discriminant is negative. Fails with `EquationMismatchError`
+    /// when the zero form is not a polynomial of degree 2 in `variable`.
+    pub fn solve_quadratic(
+        &self,
+        variable: char,
+    ) -> Result<(PartEquation, PartEquation), MathError> {
+        let polynomial: Polynomial = match self.to_zero_form().to_polynomial(variable) {
+            Some(polynomial) if polynomial.degree() == Some(2) => polynomial,
+            _ => return Err(MathError::EquationMismatchError),
+        };
+
+        let (c, b, a) = match polynomial.coefficients() {
+            [c, b, a] => (c, b, a),
+            _ => return Err(MathError::InternalError),
+        };
+
+        let discriminant: Number = b * b - Number::from(4) * a.clone() * c.clone();
+        let root: Number = discriminant
+            .pow(&(Number::from(1) / Number::from(2)))
+            .normalize();
+        let denominator: Number = Number::from(2) * a.clone();
+
+        Ok((
+            PartEquation::from((-b + root.clone()) / denominator.clone()),
+            PartEquation::from((-b - root) / denominator),
+        ))
+    }
+
+    /// Number of real solutions to expect for `variable` before solving: 1 for a linear equation,
+    /// and 2, 1 or 0 for a quadratic as its discriminant is positive, zero or negative, a double
+    /// root counting once. `None` for other equations, including quadratics whose coefficients
+    /// are not numbers
+    pub fn num_solutions_hint(&self, variable: char) -> Option<usize> {
+        match self.degree(variable)? {
+            1 => Some(1),
+            2 => {
+                let polynomial: Polynomial = self.to_zero_form().to_polynomial(variable)?;
+                let (c, b, a) = match polynomial.coefficients() {
+                    [c, b, a] => (c, b, a),
+                    _ => return None,
+                };
+
+                let discriminant: Number = b * b - Number::from(4) * a.clone() * c.clone();
+                if matches!(discriminant, Number::Complex(_)) {
+                    return None;
+                }
+                match discriminant.cmp(&Number::from(0)) {
+                    Ordering::Greater => Some(2),
+                    Ordering::Equal => Some(1),
+                    Ordering::Less => Some(0),
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Solves a polynomial equation in `variable`. Rational roots are found with
+    /// `Polynomial::rational_roots` and divided out, each listed as many times as it repeats;
+    /// a remaining factor of degree 1 or 2 is solved exactly, higher degrees fail with
+    /// `NotYetImplemented`. Fails with `EquationMismatchError` if the zero form is not a
+    /// polynomial in `variable`
+    pub fn solve_polynomial(&self, variable: char) -> Result<Vec<PartEquation>, MathError> {
+        let mut polynomial: Polynomial = match self.to_zero_form().to_polynomial(variable) {
+            Some(polynomial) => polynomial,
+            None => return Err(MathError::EquationMismatchError),
+        };
+
+        match polynomial.degree() {
+            None => return Err(MathError::InfiniteSolutions),
+            Some(0) => return Err(MathError::NoSolution),
+            Some(_) => {}
+        }
+
+        let mut roots: Vec<PartEquation> = Vec::new();
+
+        for root in polynomial.rational_roots() {
+            let factor: Polynomial = Polynomial::new(variable, vec![-&root, Number::from(1)]);
+            loop {
+                let (quotient, remainder) = polynomial.div_rem(&factor)?;
+                if !remainder.is_zero() {
+                    break;
+                }
+                polynomial = quotient;
+                roots.push(PartEquation::from(root.clone()));
+            }
+        }
+
+        match polynomial.degree() {
+            Some(0) | None => {}
+            Some(1) => {
+                let (c, b) = (&polynomial.coefficients()[0], &polynomial.coefficients()[1]);
+                roots.push(PartEquation::from((-c / b.clone()).normalize()));
+            }
+            Some(2) => {
+                let (a, b) = Equation::from_expr(&polynomial.to_part_equation())
+                    .solve_quadratic(variable)?;
+                roots.push(a);
+                roots.push(b);
+            }
+            Some(_) => return Err(MathError::NotYetImplemented),
+        }
+
+        Ok(roots)
+    }
+
+    fn difference(&self) -> EquationComponentType {
+        EquationComponentType::AddNode {
+            lhs: Arc::new(self.lhs.simplify()),
+            rhs: Arc::new(EquationComponentType::MinusNode(Arc::new(
+                self.rhs.simplify(),
+            ))),
+        }
+        .simplify()
+    }
+
+    fn make_anti_operations_list(
+        eq: &EquationComponentType,
+        variable: char,
+        list: &mut Vec<AntiOperations>,
+    ) -> bool {
+        match eq {
+            EquationComponentType::VariableNode(i) => {
+                if *i == variable {
+                    true
+                } else {
+                    false
+                }
+            }
+            EquationComponentType::AddNode { lhs, rhs } => {
+                if Self::make_anti_operations_list(lhs, variable, list) {
+                    list.push(AntiOperations::SubRHS);
+                    true
+                } else if Self::make_anti_operations_list(rhs, variable, list) {
+                    list.push(AntiOperations::SubLHS);
+                    true
+                } else {
+                    false
+                }
+            }
+            EquationComponentType::SubNode { lhs, rhs } => {
+                if Self::make_anti_operations_list(lhs, variable, list) {
+                    list.push(AntiOperations::AddRHS);
+                    true
+                } else if Self::make_anti_operations_list(rhs, variable, list) {
+                    list.push(AntiOperations::AddLHS);
+                    true
+                } else {
+                    false
+                }
+            }
+            EquationComponentType::MulNode { lhs, rhs } => {
+                if Self::make_anti_operations_list(lhs, variable, list) {
+                    list.push(AntiOperations::DivRHS);
+                    true
+                } else if Self::make_anti_operations_list(rhs, variable, list) {
+                    list.push(AntiOperations::DivLHS);
+                    true
+                } else {
+                    false
+                }
+            }
+            EquationComponentType::DivNode {
+                numerator,
+                denominator,
+            } => {
+                if Self::make_anti_operations_list(numerator, variable, list) {
+                    list.push(AntiOperations::MulDenominator);
+                    true
+                } else if Self::make_anti_operations_list(denominator, variable, list) {
+                    list.push(AntiOperations::MulNumerator);
+                    true
+                } else {
+                    false
+                }
+            }
+            EquationComponentType::PowNode { base, exponent } => {
+                if Self::make_anti_operations_list(base, variable, list) {
+                    list.push(AntiOperations::PowRHS);
+                    true
+                } else if Self::make_anti_operations_list(exponent, variable, list) {
+                    list.push(AntiOperations::LogLHS);
+                    true
+                } else {
+                    false
+                }
+            }
+            EquationComponentType::LogNode { base, argument } => {
+                if Self::make_anti_operations_list(base, variable, list) {
+                    list.push(AntiOperations::LogRHS);
+                    true
+                } else if Self::make_anti_operations_list(argument, variable, list) {
+                    list.push(AntiOperations::PowLHS);
+                    true
+                } else {
+                    false
+                }
+            }
+            EquationComponentType::MinusNode(value) => {
+                if Self::make_anti_operations_list(value, variable, list) {
+                    list.push(AntiOperations::Minus);
+                    true
+                } else {
+                    false
+                }
+            }
+            EquationComponentType::ExpNode(value) => {
+                if Self::make_anti_operations_list(value, variable, list) {
+                    list.push(AntiOperations::Ln);
+                    true
+                } else {
+                    false
+                }
+            }
+            _ => false,
+        }
+    }
+
+    // step for undoing `+ term`, or `- term` when `subtract` is false
+    fn sum_step(term: &EquationComponentType, subtract: bool) -> String {
+        let (term, subtract): (EquationComponentType, bool) = match term.without_leading_minus() {
+            Some(term) => (term, !subtract),
+            None => (term.clone(), subtract),
+        };
+        if subtract {
+            format!("subtract {} from both sides", term)
+        } else {
+            format!("add {} to both sides", term)
+        }
+    }
+
+    // value ^ (1 / exponent) with the principal root, for a rational exponent p/q the value
+    // is raised to q/p as a real root: (-8) ^ 3 for x ^ (1/3), no solution for x ^ (2/3) = -4
+    fn invert_power(
+        value: EquationComponentType,
+        exponent: Arc<EquationComponentType>,
+    ) -> Result<EquationComponentType, MathError> {
+        if let EquationComponentType::ConstantNode(e @ Number::Rational(r)) = exponent.as_ref() {
+            if let EquationComponentType::ConstantNode(value) = value.simplify() {
+                let even: bool = !r.numer().is_odd() || !r.denom().is_odd();
+                if value.signum() == Number::from(-1) && even {
+                    return Err(MathError::NoSolution);
+                }
+                let root: Number = value.real_pow(&(Number::from(1) / e.clone()))?;
+                return Ok(EquationComponentType::ConstantNode(root.normalize()));
+            }
+        }
+
+        Ok(EquationComponentType::PowNode {
+            base: Arc::new(value),
+            exponent: Arc::new(EquationComponentType::DivNode {
+                numerator: Arc::new(EquationComponentType::ConstantNode(Number::from(1))),
+                denominator: exponent,
+            }),
+        })
+    }
+
+    // solves `eq = other_side` for the single occurrence of `variable` in `eq`
+    fn do_inverse(
+        eq: &EquationComponentType,
+        other_side: EquationComponentType,
+        variable: char,
+        steps: &mut Vec<String>,
+    ) -> Result<EquationComponentType, MathError> {
+        // Step 1: make a list of anti operations to perform
+        let mut anti_ops: Vec<AntiOperations> = Vec::new();
+        if !Self::make_anti_operations_list(&eq, variable, &mut anti_ops) {
+            // the variable is under a node without an inverse, like |x|
+            return Err(MathError::NotYetImplemented);
+        }
+
+        let mut result: EquationComponentType = other_side;
+        let mut eq: EquationComponentType = eq.clone();
+
+        // Step 2: perform the anti operations`
+        for _ in 0..anti_ops.len() {
+            match anti_ops.pop().unwrap() {
+                AntiOperations::AddLHS => {
+                    if let EquationComponentType::SubNode { lhs, rhs } = eq {
+                        steps.push(Self::sum_step(&lhs, true));
+                        eq = Arc::unwrap_or_clone(rhs);
+                        result = EquationComponentType::AddNode {
+                            lhs: Arc::new(result),
+                            rhs: Arc::new(EquationComponentType::MinusNode(lhs)),
+                        }
+                    } else {
+                        return Err(MathError::InternalError);
+                    }
+                }
+                AntiOperations::AddRHS => {
+                    if let EquationComponentType::SubNode { lhs, rhs } = eq {
+                        steps.push(Self::sum_step(&rhs, false));
+                        eq = Arc::unwrap_or_clone(lhs);
+                        result = EquationComponentType::AddNode {
+                            lhs: Arc::new(result),
+                            rhs: rhs,
+                        }
+                    } else {
+                        return Err(MathError::InternalError);
+                    }
+                }
+                AntiOperations::SubLHS => {
+                    if let EquationComponentType::AddNode { lhs, rhs } = eq {
+                        steps.push(Self::sum_step(&lhs, true));
+                        eq = Arc::unwrap_or_clone(rhs);
+                        result = EquationComponentType::SubNode {
+                            lhs: Arc::new(result),
+                            rhs: lhs,
+                        }
+                    } else {
+                        return Err(MathError::InternalError);
+                    }
+                }
+                AntiOperations::SubRHS => {
+                    if let EquationComponentType::AddNode { lhs, rhs } = eq {
+                        steps.push(Self::sum_step(&rhs, true));
+                        eq = Arc::unwrap_or_clone(lhs);
+                        result = EquationComponentType::SubNode {
+                            lhs: Arc::new(result),
+                            rhs: rhs,
+                        }
+                    } else {
+                        return Err(MathError::InternalError);
+                    }
+                }
+                AntiOperations::MulNumerator => {
+                    if let EquationComponentType::DivNode {
+                        numerator,
+                        denominator,
+                    } = eq
+                    {
+                        // a / d = r -> a = r * d -> d = a / r
+                        steps.push(format!("multiply both sides by {}", denominator));
+                        steps.push(format!("divide both sides by {}", result.simplify()));
+                        eq = Arc::unwrap_or_clone(denominator);
+                        result = EquationComponentType::DivNode {
+                            numerator: numerator,
+                            denominator: Arc::new(result),
+                        }
+                    } else {
+                        return Err(MathError::InternalError);
+                    }
+                }
+                AntiOperations::MulDenominator => {
+                    if let EquationComponentType::DivNode {
+                        numerator,
+                        denominator,
+                    } = eq
+                    {
+                        steps.push(format!("multiply both sides by {}", denominator));
+                        eq = Arc::unwrap_or_clone(numerator);
+                        result = EquationComponentType::MulNode {
+                            lhs: Arc::new(result),
+                            rhs: denominator,
+                        }
+                    } else {
+                        return Err(MathError::InternalError);
+                    }
+                }
+                AntiOperations::DivLHS => {
+                    if let EquationComponentType::MulNode { lhs, rhs } = eq {
+                        steps.push(format!("divide both sides by {}", lhs));
+                        eq = Arc::unwrap_or_clone(rhs);
+                        result = EquationComponentType::DivNode {
+                            numerator: Arc::new(result),
+                            denominator: lhs,
+                        }
+                    } else {
+                        return Err(MathError::InternalError);
+                    }
+                }
+                AntiOperations::DivRHS => {
+                    if let EquationComponentType::MulNode { lhs, rhs } = eq {
+                        steps.push(format!("divide both sides by {}", rhs));
+                        eq = Arc::unwrap_or_clone(lhs);
+                        result = EquationComponentType::DivNode {
+                            numerator: Arc::new(result),
+                            denominator: rhs,
+                        }
+                    } else {
+                        return Err(MathError::InternalError);
+                    }
+                }
+                AntiOperations::PowLHS => {
+                    if let EquationComponentType::LogNode { base, argument } = eq {
+                        steps.push(format!("raise {} to the power of both sides", base));
+                        eq = Arc::unwrap_or_clone(argument);
+                        result = EquationComponentType::PowNode {
+                            base: base,
+                            exponent: Arc::new(result),
+                        }
+                    } else {
+                        return Err(MathError::InternalError);
+                    }
+                }
+                AntiOperations::PowRHS => {
+                    if let EquationComponentType::PowNode { base, exponent } = eq {
+                        steps.push(format!("raise both sides to the power of 1 / {}", exponent));
+                        eq = Arc::unwrap_or_clone(base);
+                        result = Self::invert_power(result, exponent)?;
+                    } else {
+                        return Err(MathError::InternalError);
+                    }
+                }
+                AntiOperations::LogLHS => {
+                    if let EquationComponentType::PowNode { base, exponent } = eq {
+                        steps.push(format!("take the log base {} of both sides", base));
+                        eq = Arc::unwrap_or_clone(exponent);
+                        result = EquationComponentType::LogNode {
+                            base: base,
+                            argument: Arc::new(result),
+                        }
+                    } else {
+                        return Err(MathError::InternalError);
+                    }
+                }
+                AntiOperations::LogRHS => {
+                    // log_b(a) = r -> b = a ^ (1 / r)
+                    if let EquationComponentType::LogNode { base, argument } = eq {
+                        steps.push(format!(
+                            "raise {} to the reciprocal of both sides",
+                            argument
+                        ));
+                        eq = Arc::unwrap_or_clone(base);
+                        result = EquationComponentType::PowNode {
+                            base: argument,
+                            exponent: Arc::new(EquationComponentType::DivNode {
+                                numerator: Arc::new(EquationComponentType::ConstantNode(
+                                    Number::from(1),
+                                )),
+                                denominator: Arc::new(result),
+                            }),
+                        }
+                    } else {
+                        return Err(MathError::InternalError);
+                    }
+                }
+                AntiOperations::Minus => {
+                    if let EquationComponentType::MinusNode(v) = eq {
+                        steps.push(String::from("negate both sides"));
+                        eq = Arc::unwrap_or_clone(v);
+                        result = EquationComponentType::MinusNode(Arc::new(result));
+                    } else {
+                        return Err(MathError::InternalError);
+                    }
+                }
+                AntiOperations::Ln => {
+                    if let EquationComponentType::ExpNode(v) = eq {
+                        steps.push(String::from("take the natural log of both sides"));
+                        eq = Arc::unwrap_or_clone(v);
+                        result = EquationComponentType::LogNode {
+                            base: Arc::new(EquationComponentType::ConstantNode(Number::e())),
+                            argument: Arc::new(result),
+                        }
+                    } else {
+                        return Err(MathError::InternalError);
+                    }
+                }
+            }
+        }
+
+        // every operation around the variable has been undone
+        if eq != EquationComponentType::VariableNode(variable) {
+            return Err(MathError::InternalError);
+        }
+
+        // Step 3: return the simplified answer
+        return Ok(result.simplify().order());
+    }
+
+    /// Applies the operation with the operand to both sides
+    fn on_both_sides(
+        &self,
+        operand: &PartEquation,
+        operation: impl Fn(&PartEquation, &PartEquation) -> PartEquation,
+    ) -> Equation {
+        let side = |eq: &EquationComponentType| {
+            operation(&PartEquation { eq: eq.clone() }, operand)
+                .eq
+                .order()
+        };
+        Equation {
+            lhs: side(&self.lhs),
+            rhs: side(&self.rhs),
+        }
+    }
+
+    /// Like `on_both_sides`, rejecting a zero operand with the error as the result would not
+    /// have the same solutions
+    fn scale_both_sides(
+        &self,
+        operand: &PartEquation,
+        operation: impl Fn(&PartEquation, &PartEquation) -> PartEquation,
+        zero_error: MathError,
+    ) -> Result<Equation, MathError> {
+        if *operand == PartEquation::from(0) {
+            return Err(zero_error);
+        }
+        Ok(self.on_both_sides(operand, operation))
+    }
+}
+
+impl Display for Equation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} = {}",
+            self.lhs.to_display().0,
+            self.rhs.to_display().0
+        )
+    }
+}
+
+impl PartialEq for Equation {
+    /// Two equations are equal if their zero forms `lhs - rhs` differ by a nonzero constant factor,
+    /// so `x = 5`, `2 * x = 10` and `x - 5 = 0` are all equal
+    fn eq(&self, other: &Self) -> bool {
+        let lhs: PartEquation = self.to_zero_form();
+        let rhs: PartEquation = other.to_zero_form();
+
+        let zero: PartEquation = PartEquation::from(0);
+        if lhs == zero || rhs == zero {
+            return lhs == rhs;
+        }
+
+        // the factor, if any, maps the first term of lhs onto one of the terms of rhs
+        let mut lhs_terms: Vec<EquationComponentType> = Vec::new();
+        let mut rhs_terms: Vec<EquationComponentType> = Vec::new();
+        lhs.eq.separate_terms(&mut lhs_terms);
+        rhs.eq.separate_terms(&mut rhs_terms);
+
+        if lhs_terms.len() != rhs_terms.len() {
+            return false;
+        }
+        let first: Number = lhs_terms[0].coefficient();
+
+        rhs_terms.iter().any(|term| {
+            let factor: Number = (term.coefficient() / first.clone()).normalize();
+            if factor == Number::from(0) {
+                return false;
+            }
+
+            // scaling term by term, products over sums are not distributed by simplify
+            let scaled: Vec<EquationComponentType> = lhs_terms
+                .iter()
+                .map(|i| EquationComponentType::MulNode {
+                    lhs: Arc::new(i.clone()),
+                    rhs: Arc::new(EquationComponentType::ConstantNode(factor.clone())),
+                })
+                .collect();
+            EquationComponentType::construct_from_terms(scaled)
+                .simplify()
+                .order()
+                == rhs.eq
+        })
+    }
+}
+
+impl Eq for Equation {}
+
+/// The identity `0 = 0`
+impl Default for Equation {
+    fn default() -> Self {
+        Equation::from_parts(PartEquation::default(), PartEquation::default())
+    }
+}
+
+impl From<(PartEquation, PartEquation)> for Equation {
+    fn from(value: (PartEquation, PartEquation)) -> Self {
+        Equation::from_parts(value.0, value.1)
+    }
+}
+
+/// Equations that hold simultaneously
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct System {
+    equations: Vec<Equation>,
+}
+
+impl System {
+    pub fn new(equations: Vec<Equation>) -> Self {
+        System { equations }
+    }
+
+    pub fn equations(&self) -> &[Equation] {
+        &self.equations
+    }
+
+    /// Solves a linear system for `variables` by Gauss-Jordan elimination over exact numbers.
+    /// Fails with `NotYetImplemented` if an equation is not linear in `variables` with numeric
+    /// coefficients, `NoSolution` if the equations are inconsistent and `InfiniteSolutions`
+    /// if they do not determine every variable
+    pub fn solve_system(
+        &self,
+        variables: &[char],
+    ) -> Result<HashMap<char, PartEquation>, MathError> {
+        // augmented matrix, row i is `a_i1 x_1 + ... + a_in x_n = b_i`
+        let mut rows: Vec<Vec<Number>> = Vec::with_capacity(self.equations.len());
+
+        for equation in self.equations.iter() {
+            let zero_form: PartEquation = equation.to_zero_form();
+            let mut row: Vec<Number> = Vec::with_capacity(variables.len() + 1);
+
+            for v in variables.iter() {
+                if !matches!(zero_form.degree(*v), Some(0) | Some(1)) {
+                    return Err(MathError::NotYetImplemented);
+                }
+                match zero_form.differentiate(*v).evaluate() {
+                    Ok(coefficient) => row.push(coefficient),
+                    Err(_) => return Err(MathError::NotYetImplemented),
+                }
+            }
+
+            let mut constant: PartEquation = zero_form;
+            for v in variables.iter() {
+                constant = constant.substitute(*v, &PartEquation::from(0));
+            }
+            match constant.evaluate() {
+                Ok(constant) => row.push(-constant),
+                Err(_) => return Err(MathError::NotYetImplemented),
+            }
+
+            rows.push(row);
+        }
+
+        let mut pivots: Vec<usize> = Vec::new();
+        for column in 0..variables.len() {
+            let row: usize = pivots.len();
+            let pivot: usize = match (row..rows.len()).find(|r| rows[*r][column] != Number::from(0))
+            {
+                Some(pivot) => pivot,
+                None => continue,
+            };
+            rows.swap(row, pivot);
+
+            let leading: Number = rows[row][column].clone();
+            for c in rows[row].iter_mut() {
+                *c /= &leading;
+            }
+
+            let pivot_row: Vec<Number> = rows[row].clone();
+            for (r, other) in rows.iter_mut().enumerate() {
+                if r == row || other[column] == Number::from(0) {
+                    continue;
+                }
+                let factor: Number = other[column].clone();
+                for (c, p) in other.iter_mut().zip(pivot_row.iter()).skip(column) {
+                    *c -= &(&factor * p);
+                }
+            }
+            pivots.push(column);
+        }
+
+        // rows left without a pivot read 0 = b
+        if rows[pivots.len()..]
+            .iter()
+            .any(|row| row[variables.len()] != Number::from(0))
+        {
+            return Err(MathError::NoSolution);
+        }
+        if pivots.len() < variables.len() {
+            return Err(MathError::InfiniteSolutions);
+        }
+
+        Ok(pivots
+            .iter()
+            .zip(rows.iter())
+            .map(|(column, row)| {
+                (
+                    variables[*column],
+                    PartEquation::from(row[variables.len()].clone().normalize()),
+                )
+            })
+            .collect())
+    }
+}
+
+/// A number that can stand on the right of an arithmetic operator with a `PartEquation`. The
+/// integer widths and `f64` also work on the left, where an unsuffixed integer literal is an
+/// `i32`. A method called straight on such a result needs the literal or the result typed,
+/// `(1i64 / &x).simplify()` or `PartEquation::from(1) / &x`
+pub trait Scalar: Into<Number> {}
+
+macro_rules! scalar {
+    ($($t:ty),*) => {
+        $(impl Scalar for $t {})*
+    };
+}
+
+scalar!(i32, i64, u32, i128, u128, f64, Number);
+
+impl ops::Add<PartEquation> for PartEquation {
+    type Output = PartEquation;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::AddNode {
+                lhs: Arc::new(self.eq),
+                rhs: Arc::new(rhs.eq),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl<'a> ops::Add<&'a PartEquation> for &'a PartEquation {
+    type Output = PartEquation;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::AddNode {
+                lhs: Arc::new(self.eq.clone()),
+                rhs: Arc::new(rhs.eq.clone()),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl<'a> ops::Add<PartEquation> for &'a PartEquation {
+    type Output = PartEquation;
+
+    fn add(self, rhs: PartEquation) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::AddNode {
+                lhs: Arc::new(self.eq.clone()),
+                rhs: Arc::new(rhs.eq),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl<'a> ops::Add<&'a PartEquation> for PartEquation {
+    type Output = PartEquation;
+
+    fn add(self, rhs: &'a PartEquation) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::AddNode {
+                lhs: Arc::new(self.eq),
+                rhs: Arc::new(rhs.eq.clone()),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl<T: Scalar> ops::Add<T> for PartEquation {
+    type Output = PartEquation;
+
+    fn add(self, rhs: T) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::AddNode {
+                lhs: Arc::new(self.eq),
+                rhs: Arc::new(EquationComponentType::ConstantNode(rhs.into())),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl ops::Add<PartEquation> for i64 {
+    type Output = PartEquation;
+
+    fn add(self, rhs: PartEquation) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::AddNode {
+                lhs: Arc::new(EquationComponentType::ConstantNode(Number::from(self))),
+                rhs: Arc::new(rhs.eq),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl ops::Add<PartEquation> for f64 {
+    type Output = PartEquation;
+
+    fn add(self, rhs: PartEquation) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::AddNode {
+                lhs: Arc::new(EquationComponentType::ConstantNode(Number::from(self))),
+                rhs: Arc::new(rhs.eq),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl<'a, T: Scalar> ops::Add<T> for &'a PartEquation {
+    type Output = PartEquation;
+
+    fn add(self, rhs: T) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::AddNode {
+                lhs: Arc::new(self.eq.clone()),
+                rhs: Arc::new(EquationComponentType::ConstantNode(rhs.into())),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl<'a> ops::Add<&'a PartEquation> for i64 {
+    type Output = PartEquation;
+
+    fn add(self, rhs: &PartEquation) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::AddNode {
+                lhs: Arc::new(EquationComponentType::ConstantNode(Number::from(self))),
+                rhs: Arc::new(rhs.eq.clone()),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl<'a> ops::Add<&'a PartEquation> for f64 {
+    type Output = PartEquation;
+
+    fn add(self, rhs: &PartEquation) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::AddNode {
+                lhs: Arc::new(EquationComponentType::ConstantNode(Number::from(self))),
+                rhs: Arc::new(rhs.eq.clone()),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl ops::Sub<PartEquation> for PartEquation {
+    type Output = PartEquation;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::SubNode {
+                lhs: Arc::new(self.eq),
+                rhs: Arc::new(rhs.eq),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl<'a> ops::Sub<&'a PartEquation> for &'a PartEquation {
+    type Output = PartEquation;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::SubNode {
+                lhs: Arc::new(self.eq.clone()),
+                rhs: Arc::new(rhs.eq.clone()),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl<'a> ops::Sub<PartEquation> for &'a PartEquation {
+    type Output = PartEquation;
+
+    fn sub(self, rhs: PartEquation) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::SubNode {
+                lhs: Arc::new(self.eq.clone()),
+                rhs: Arc::new(rhs.eq),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl<'a> ops::Sub<&'a PartEquation> for PartEquation {
+    type Output = PartEquation;
+
+    fn sub(self, rhs: &'a PartEquation) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::SubNode {
+                lhs: Arc::new(self.eq),
+                rhs: Arc::new(rhs.eq.clone()),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl<T: Scalar> ops::Sub<T> for PartEquation {
+    type Output = PartEquation;
+
+    fn sub(self, rhs: T) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::SubNode {
+                lhs: Arc::new(self.eq),
+                rhs: Arc::new(EquationComponentType::ConstantNode(rhs.into())),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl ops::Sub<PartEquation> for i64 {
+    type Output = PartEquation;
+
+    fn sub(self, rhs: PartEquation) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::SubNode {
+                lhs: Arc::new(EquationComponentType::ConstantNode(Number::from(self))),
+                rhs: Arc::new(rhs.eq),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl ops::Sub<PartEquation> for f64 {
+    type Output = PartEquation;
+
+    fn sub(self, rhs: PartEquation) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::SubNode {
+                lhs: Arc::new(EquationComponentType::ConstantNode(Number::from(self))),
+                rhs: Arc::new(rhs.eq),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl<'a, T: Scalar> ops::Sub<T> for &'a PartEquation {
+    type Output = PartEquation;
+
+    fn sub(self, rhs: T) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::SubNode {
+                lhs: Arc::new(self.eq.clone()),
+                rhs: Arc::new(EquationComponentType::ConstantNode(rhs.into())),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl<'a> ops::Sub<&'a PartEquation> for i64 {
+    type Output = PartEquation;
+
+    fn sub(self, rhs: &PartEquation) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::SubNode {
+                lhs: Arc::new(EquationComponentType::ConstantNode(Number::from(self))),
+                rhs: Arc::new(rhs.eq.clone()),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl<'a> ops::Sub<&'a PartEquation> for f64 {
+    type Output = PartEquation;
+
+    fn sub(self, rhs: &PartEquation) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::SubNode {
+                lhs: Arc::new(EquationComponentType::ConstantNode(Number::from(self))),
+                rhs: Arc::new(rhs.eq.clone()),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl ops::Mul<PartEquation> for PartEquation {
+    type Output = PartEquation;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::MulNode {
+                lhs: Arc::new(self.eq),
+                rhs: Arc::new(rhs.eq),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl<'a> ops::Mul<&'a PartEquation> for &'a PartEquation {
+    type Output = PartEquation;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::MulNode {
+                lhs: Arc::new(self.eq.clone()),
+                rhs: Arc::new(rhs.eq.clone()),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl<'a> ops::Mul<PartEquation> for &'a PartEquation {
+    type Output = PartEquation;
+
+    fn mul(self, rhs: PartEquation) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::MulNode {
+                lhs: Arc::new(self.eq.clone()),
+                rhs: Arc::new(rhs.eq),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl<'a> ops::Mul<&'a PartEquation> for PartEquation {
+    type Output = PartEquation;
+
+    fn mul(self, rhs: &'a PartEquation) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::MulNode {
+                lhs: Arc::new(self.eq),
+                rhs: Arc::new(rhs.eq.clone()),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl<T: Scalar> ops::Mul<T> for PartEquation {
+    type Output = PartEquation;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::MulNode {
+                lhs: Arc::new(self.eq),
+                rhs: Arc::new(EquationComponentType::ConstantNode(rhs.into())),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl ops::Mul<PartEquation> for i64 {
+    type Output = PartEquation;
+
+    fn mul(self, rhs: PartEquation) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::MulNode {
+                lhs: Arc::new(EquationComponentType::ConstantNode(Number::from(self))),
+                rhs: Arc::new(rhs.eq),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl ops::Mul<PartEquation> for f64 {
+    type Output = PartEquation;
+
+    fn mul(self, rhs: PartEquation) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::MulNode {
+                lhs: Arc::new(EquationComponentType::ConstantNode(Number::from(self))),
+                rhs: Arc::new(rhs.eq),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl<'a, T: Scalar> ops::Mul<T> for &'a PartEquation {
+    type Output = PartEquation;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::MulNode {
+                lhs: Arc::new(self.eq.clone()),
+                rhs: Arc::new(EquationComponentType::ConstantNode(rhs.into())),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl<'a> ops::Mul<&'a PartEquation> for i64 {
+    type Output = PartEquation;
+
+    fn mul(self, rhs: &PartEquation) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::MulNode {
+                lhs: Arc::new(EquationComponentType::ConstantNode(Number::from(self))),
+                rhs: Arc::new(rhs.eq.clone()),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl<'a> ops::Mul<&'a PartEquation> for f64 {
+    type Output = PartEquation;
+
+    fn mul(self, rhs: &PartEquation) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::MulNode {
+                lhs: Arc::new(EquationComponentType::ConstantNode(Number::from(self))),
+                rhs: Arc::new(rhs.eq.clone()),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl ops::Div<PartEquation> for PartEquation {
+    type Output = PartEquation;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::DivNode {
+                numerator: Arc::new(self.eq),
+                denominator: Arc::new(rhs.eq),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl<'a> ops::Div<&'a PartEquation> for &'a PartEquation {
+    type Output = PartEquation;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::DivNode {
+                numerator: Arc::new(self.eq.clone()),
+                denominator: Arc::new(rhs.eq.clone()),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl<'a> ops::Div<PartEquation> for &'a PartEquation {
+    type Output = PartEquation;
+
+    fn div(self, rhs: PartEquation) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::DivNode {
+                numerator: Arc::new(self.eq.clone()),
+                denominator: Arc::new(rhs.eq),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl<'a> ops::Div<&'a PartEquation> for PartEquation {
+    type Output = PartEquation;
+
+    fn div(self, rhs: &'a PartEquation) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::DivNode {
+                numerator: Arc::new(self.eq),
+                denominator: Arc::new(rhs.eq.clone()),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl<T: Scalar> ops::Div<T> for PartEquation {
+    type Output = PartEquation;
+
+    fn div(self, rhs: T) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::DivNode {
+                numerator: Arc::new(self.eq),
+                denominator: Arc::new(EquationComponentType::ConstantNode(rhs.into())),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl ops::Div<PartEquation> for i64 {
+    type Output = PartEquation;
+
+    fn div(self, rhs: PartEquation) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::DivNode {
+                numerator: Arc::new(EquationComponentType::ConstantNode(Number::from(self))),
+                denominator: Arc::new(rhs.eq),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl ops::Div<PartEquation> for f64 {
+    type Output = PartEquation;
+
+    fn div(self, rhs: PartEquation) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::DivNode {
+                numerator: Arc::new(EquationComponentType::ConstantNode(Number::from(self))),
+                denominator: Arc::new(rhs.eq),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl<'a, T: Scalar> ops::Div<T> for &'a PartEquation {
+    type Output = PartEquation;
+
+    fn div(self, rhs: T) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::DivNode {
+                numerator: Arc::new(self.eq.clone()),
+                denominator: Arc::new(EquationComponentType::ConstantNode(rhs.into())),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl<'a> ops::Div<&'a PartEquation> for i64 {
+    type Output = PartEquation;
+
+    fn div(self, rhs: &PartEquation) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::DivNode {
+                numerator: Arc::new(EquationComponentType::ConstantNode(Number::from(self))),
+                denominator: Arc::new(rhs.eq.clone()),
+            },
+        }
+        .simplify()
+    }
+}
+
+impl<'a> ops::Div<&'a PartEquation> for f64 {
+    type Output = PartEquation;
+
+    fn div(self, rhs: &PartEquation) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::DivNode {
+                numerator: Arc::new(EquationComponentType::ConstantNode(Number::from(self))),
+                denominator: Arc::new(rhs.eq.clone()),
+            },
+        }
+        .simplify()
+    }
+}
+
+// the constant on the left for the other integer widths, on the right any `Scalar` works
+macro_rules! constant_lhs_operators {
+    ($trait:ident, $method:ident, $node:ident, $lhs:ident, $rhs:ident, $($t:ty),*) => {
+        $(
+            impl ops::$trait<PartEquation> for $t {
+                type Output = PartEquation;
+
+                fn $method(self, rhs: PartEquation) -> Self::Output {
+                    PartEquation {
+                        eq: EquationComponentType::$node {
+                            $lhs: Arc::new(EquationComponentType::ConstantNode(Number::from(self))),
+                            $rhs: Arc::new(rhs.eq),
+                        },
+                    }
+                    .simplify()
+                }
+            }
+
+            impl<'a> ops::$trait<&'a PartEquation> for $t {
+                type Output = PartEquation;
+
+                fn $method(self, rhs: &PartEquation) -> Self::Output {
+                    PartEquation {
+                        eq: EquationComponentType::$node {
+                            $lhs: Arc::new(EquationComponentType::ConstantNode(Number::from(self))),
+                            $rhs: Arc::new(rhs.eq.clone()),
+                        },
+                    }
+                    .simplify()
+                }
+            }
+        )*
+    };
+}
+
+constant_lhs_operators!(Add, add, AddNode, lhs, rhs, i32, u32, i128, u128);
+constant_lhs_operators!(Sub, sub, SubNode, lhs, rhs, i32, u32, i128, u128);
+constant_lhs_operators!(Mul, mul, MulNode, lhs, rhs, i32, u32, i128, u128);
+constant_lhs_operators!(
+    Div,
+    div,
+    DivNode,
+    numerator,
+    denominator,
+    i32,
+    u32,
+    i128,
+    u128
+);
+
+impl ops::Neg for PartEquation {
+    type Output = PartEquation;
+
+    fn neg(self) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::MinusNode(Arc::new(self.eq)),
+        }
+        .simplify()
+    }
+}
+
+impl<'a> ops::Neg for &'a PartEquation {
+    type Output = PartEquation;
+
+    fn neg(self) -> Self::Output {
+        PartEquation {
+            eq: EquationComponentType::MinusNode(Arc::new(self.eq.clone())),
+        }
+        .simplify()
+    }
+}
+
+// operations on both sides of an equation, a multiplication or division by zero is an error
+macro_rules! equation_operators {
+    ($trait:ident, $method:ident, $output:ty, |$eq:ident, $operand:ident| $apply:expr) => {
+        impl<T: Into<PartEquation>> ops::$trait<T> for Equation {
+            type Output = $output;
+
+            fn $method(self, rhs: T) -> Self::Output {
+                let ($eq, $operand): (&Equation, &PartEquation) = (&self, &rhs.into());
+                $apply
+            }
+        }
+
+        impl<'a, T: Into<PartEquation>> ops::$trait<T> for &'a Equation {
+            type Output = $output;
+
+            fn $method(self, rhs: T) -> Self::Output {
+                let ($eq, $operand): (&Equation, &PartEquation) = (self, &rhs.into());
+                $apply
+            }
+        }
+
+        impl<'a> ops::$trait<&'a PartEquation> for Equation {
+            type Output = $output;
+
+            fn $method(self, rhs: &PartEquation) -> Self::Output {
+                let ($eq, $operand): (&Equation, &PartEquation) = (&self, rhs);
+                $apply
+            }
+        }
+
+        impl<'a, 'b> ops::$trait<&'b PartEquation> for &'a Equation {
+            type Output = $output;
+
+            fn $method(self, rhs: &PartEquation) -> Self::Output {
+                let ($eq, $operand): (&Equation, &PartEquation) = (self, rhs);
+                $apply
+            }
+        }
+    };
+}
+
+equation_operators!(Add, add, Equation, |eq, operand| {
+    eq.on_both_sides(operand, |a: &PartEquation, b: &PartEquation| a + b)
+});
+equation_operators!(Sub, sub, Equation, |eq, operand| {
+    eq.on_both_sides(operand, |a: &PartEquation, b: &PartEquation| a - b)
+});
+equation_operators!(Mul, mul, Result<Equation, MathError>, |eq, operand| {
+    eq.scale_both_sides(
+        operand,
+        |a: &PartEquation, b: &PartEquation| a * b,
+        MathError::UnsupportedOperation,
+    )
+});
+equation_operators!(Div, div, Result<Equation, MathError>, |eq, operand| {
+    eq.scale_both_sides(
+        operand,
+        |a: &PartEquation, b: &PartEquation| a / b,
+        MathError::ZeroDivisionError,
+    )
+});
+
+impl ops::Neg for Equation {
+    type Output = Equation;
+
+    fn neg(self) -> Self::Output {
+        -&self
+    }
+}
+
+impl<'a> ops::Neg for &'a Equation {
+    type Output = Equation;
+
+    fn neg(self) -> Self::Output {
+        Equation {
+            lhs: (-PartEquation {
+                eq: self.lhs.clone(),
+            })
+            .eq
+            .order(),
+            rhs: (-PartEquation {
+                eq: self.rhs.clone(),
+            })
+            .eq
+            .order(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solving_equation_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let eq: Equation = Equation::new(&x, &PartEquation::from(12));
+
+        if let EquationComponentType::ConstantNode(i) = eq.solve('x').unwrap().eq {
+            assert_eq!(i, Number::from(12));
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_solving_equation_2() {
+        let x: PartEquation = PartEquation::from('x');
+        let eq: Equation = Equation::new(&PartEquation::from(3.14), &x);
+
+        if let EquationComponentType::ConstantNode(i) = eq.solve('x').unwrap().eq {
+            assert_eq!(i, Number::from(3.14));
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_solving_equation_3() {
+        let x: PartEquation = PartEquation::from('x');
+        let eq: Equation = Equation::new(&PartEquation::from(3), &(x * 2));
+
+        if let EquationComponentType::ConstantNode(i) = eq.solve('x').unwrap().eq {
+            assert_eq!(i, Number::from(1.5));
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_solving_equation_4() {
+        let x: PartEquation = PartEquation::from('x');
+        let eq: Equation = Equation::new(&PartEquation::from(3), &(x + 2));
+
+        if let EquationComponentType::ConstantNode(i) = eq.solve('x').unwrap().eq {
+            assert_eq!(i, Number::from(1));
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_solving_equation_5() {
+        let x: PartEquation = PartEquation::from('x');
+        let eq: Equation = Equation::new(&PartEquation::from(3), &(x / 2));
+
+        if let EquationComponentType::ConstantNode(i) = eq.solve('x').unwrap().eq {
+            assert_eq!(i, Number::from(6));
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_solving_equation_6() {
+        let x: PartEquation = PartEquation::from('x');
+        let eq: Equation = Equation::new(&PartEquation::from(9), &(&x.pow(&PartEquation::from(2))));
+
+        if let EquationComponentType::ConstantNode(i) = eq.solve('x').unwrap().eq {
+            assert_eq!(i, Number::from(3));
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_solving_equation_7() {
+        // TODO: evaluate log
+        let x: PartEquation = PartEquation::from('x');
+        let eq: Equation = Equation::new(&PartEquation::from(8), &(&PartEquation::from(2).pow(&x)));
+
+        if let EquationComponentType::LogNode { base, argument } = eq.solve('x').unwrap().eq {
+            if let EquationComponentType::ConstantNode(i) = &*base {
+                assert_eq!(*i, Number::from(2));
+            } else {
+                assert!(false);
+            }
+
+            if let EquationComponentType::ConstantNode(i) = &*argument {
+                assert_eq!(*i, Number::from(8));
+            } else {
+                assert!(false);
+            }
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_solving_equation_8() {
+        let x: PartEquation = PartEquation::from('x');
+        let eq: Equation = Equation::new(&(-x), &PartEquation::from(1));
+
+        if let EquationComponentType::ConstantNode(i) = eq.solve('x').unwrap().eq {
+            assert_eq!(i, Number::from(-1));
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_solving_equation_9() {
+        let x: PartEquation = PartEquation::from('x');
+        let eq: Equation = Equation::new(&(&x + 5), &(2 * &x));
+
+        if let EquationComponentType::ConstantNode(i) = eq.solve('x').unwrap().eq {
+            assert_eq!(i, Number::from(5));
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_solving_equation_10() {
+        let x: PartEquation = PartEquation::from('x');
+        let eq: Equation = Equation::new(&(-&x + 5), &(2 * &x));
+
+        if let EquationComponentType::ConstantNode(i) = eq.solve('x').unwrap().eq {
+            assert_eq!(i, Number::from(5) / Number::from(3));
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_solving_equation_11() {
+        let x: PartEquation = PartEquation::from('x');
+        let power = |numerator: i32, denominator: i32| {
+            x.pow(&(PartEquation::from(numerator) / PartEquation::from(denominator)))
+        };
+
+        let solve =
+            |lhs: PartEquation, rhs: i32| Equation::new(&lhs, &PartEquation::from(rhs)).solve('x');
+
+        assert_eq!(
+            solve(power(3, 2), 8).unwrap().eq,
+            EquationComponentType::ConstantNode(Number::from(4))
+        );
+        assert!(matches!(
+            solve(power(3, 2), 8).unwrap().eq,
+            EquationComponentType::ConstantNode(Number::Integer(_))
+        ));
+        assert_eq!(solve(power(2, 3), 4).unwrap(), PartEquation::from(8));
+        assert_eq!(solve(power(5, 2), 32).unwrap(), PartEquation::from(4));
+        assert_eq!(
+            solve(power(-1, 2), 2).unwrap(),
+            PartEquation::from(1) / PartEquation::from(4)
+        );
+
+        // the real cube root is defined for negative numbers, even roots are not
+        assert_eq!(solve(power(1, 3), -8).unwrap(), PartEquation::from(-512));
+        assert_eq!(solve(power(3, 5), -8).unwrap(), PartEquation::from(-32));
+        assert!(matches!(solve(power(2, 3), -4), Err(MathError::NoSolution)));
+        assert!(matches!(solve(power(3, 2), -8), Err(MathError::NoSolution)));
+    }
+
+    #[test]
+    fn test_equality_for_part_equation_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
+        let z: PartEquation = PartEquation::from('z');
+
+        let eq1 = &x + &y + &z;
+
+        assert_eq!(eq1, &x + &z + &y);
+        assert_eq!(eq1, &y + &x + &z);
+        assert_eq!(eq1, &y + &z + &x);
+        assert_eq!(eq1, &z + &y + &x);
+        assert_eq!(eq1, &z + &x + &y);
+    }
+
+    #[test]
+    fn test_equality_for_part_equation_2() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
+        let z: PartEquation = PartEquation::from('z');
+
+        let eq1 = &x * &y * &z;
+
+        assert_eq!(eq1, &x * &z * &y);
+        assert_eq!(eq1, &y * &x * &z);
+        assert_eq!(eq1, &y * &z * &x);
+        assert_eq!(eq1, &z * &y * &x);
+        assert_eq!(eq1, &z * &x * &y);
+    }
+
+    #[test]
+    fn test_equality_for_part_equation_3() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
+        let z: PartEquation = PartEquation::from('z');
+
+        let eq1 = &x * (&y + &z);
+
+        assert_eq!(eq1, &x * (&z + &y));
+        assert_eq!(eq1, (&y + &z) * (&x));
+        assert_eq!(eq1, (&z + &y) * (&x));
+    }
+
+    #[test]
+    fn test_contains_part_equation_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
+
+        let eq = x.pow(&PartEquation::from(2)) + 1;
+
+        assert!(eq.contains(&x.pow(&PartEquation::from(2))));
+        assert!(eq.contains(&x));
+        assert!(!eq.contains(&y));
+    }
+
+    #[test]
+    fn test_replace_part_equation_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
+
+        let eq = x.pow(&PartEquation::from(2)) + x.pow(&PartEquation::from(2));
+
+        assert_eq!(eq.replace(&x.pow(&PartEquation::from(2)), &y), 2 * &y);
+    }
+
+    #[test]
+    fn test_replace_part_equation_2() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
+
+        let eq = &x * &y + 5;
+
+        assert_eq!(
+            eq.replace(&(&y * &x), &PartEquation::from(2)),
+            PartEquation::from(7)
+        );
+        assert_eq!(eq.replace(&x, &y), y.pow(&PartEquation::from(2)) + 5);
+    }
+
+    #[test]
+    fn test_polynomial_division_1() {
+        let x: PartEquation = PartEquation::from('x');
+
+        let eq = (x.pow(&PartEquation::from(2)) - 1) / (&x - 1);
+        assert_eq!(eq, &x + 1);
+
+        let eq = (x.pow(&PartEquation::from(3)) - 8) / (&x - 2);
+        assert_eq!(eq, x.pow(&PartEquation::from(2)) + 2 * &x + 4);
+    }
+
+    #[test]
+    fn test_polynomial_division_2() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
+
+        // inexact division is left untouched
+        let eq = (x.pow(&PartEquation::from(2)) + 1) / (&x - 1);
+        assert!(matches!(
+            eq.eq,
+            EquationComponentType::DivNode {
+                numerator: _,
+                denominator: _
+            }
+        ));
+
+        // more than one variable is left untouched
+        let eq = (&x * &y) / (&x - 1);
+        assert!(matches!(
+            eq.eq,
+            EquationComponentType::DivNode {
+                numerator: _,
+                denominator: _
+            }
+        ));
+    }
+
+    #[test]
+    fn test_polynomial_division_3() {
+        let x: EquationComponentType = EquationComponentType::VariableNode('x');
+        let power = |exponent: i64| EquationComponentType::PowNode {
+            base: Arc::new(x.clone()),
+            exponent: Arc::new(EquationComponentType::ConstantNode(Number::from(exponent))),
+        };
+
+        // degrees past the limit are not expanded into coefficients
+        assert_eq!(
+            EquationComponentType::divide_polynomials(&power(4_000_000_000), &x),
+            None
+        );
+        assert_eq!(
+            EquationComponentType::divide_polynomials(&x, &power(MAX_DIVISION_DEGREE + 1)),
+            None
+        );
+
+        assert_eq!(
+            EquationComponentType::divide_polynomials(&power(MAX_DIVISION_DEGREE), &x),
+            Some(power(MAX_DIVISION_DEGREE - 1))
+        );
+    }
+
+    #[test]
+    fn test_polynomial_fraction_1() {
+        let x: PartEquation = PartEquation::from('x');
+
+        let eq = (x.pow(&PartEquation::from(2)) - 1) / (x.pow(&PartEquation::from(2)) - 2 * &x + 1);
+        assert_eq!(eq, (&x + 1) / (&x - 1));
+
+        let eq = (x.pow(&PartEquation::from(2)) + 3 * &x + 2) / (x.pow(&PartEquation::from(2)) - 4);
+        assert_eq!(eq, (&x + 1) / (&x - 2));
+    }
+
+    #[test]
+    fn test_polynomial_fraction_2() {
+        let x: PartEquation = PartEquation::from('x');
+
+        let eq = (x.pow(&PartEquation::from(3)) - &x) / (x.pow(&PartEquation::from(2)) + &x);
+        assert_eq!(eq, &x - 1);
+
+        let eq = (&x + 1) / (&x + 2);
+        assert_eq!(eq.to_string(), "((x + 1) / (x + 2))");
+    }
+
+    #[test]
+    fn test_normalize_constants_1() {
+        let eq = PartEquation::from(4) / PartEquation::from(2);
+        assert_eq!(eq.to_string(), "2");
+        assert!(matches!(
+            eq.eq,
+            EquationComponentType::ConstantNode(Number::Integer(_))
+        ));
+
+        let x: PartEquation = PartEquation::from('x');
+        let eq = &x + PartEquation::from(6) / PartEquation::from(3);
+        assert_eq!(eq.to_string(), "(x + 2)");
+    }
+
+    #[test]
+    fn test_fold_mixed_constants_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let half = || PartEquation::from(1) / PartEquation::from(2);
+
+        let eq = half() + half();
+        assert_eq!(eq.to_string(), "1");
+        assert!(matches!(
+            eq.eq,
+            EquationComponentType::ConstantNode(Number::Integer(_))
+        ));
+
+        let eq = &x + half() + half();
+        assert_eq!(eq.to_string(), "(x + 1)");
+
+        let eq = half() * 4 * &x;
+        assert_eq!(eq.to_string(), "(2 * x)");
+
+        let eq = PartEquation::from(0.5) + half() + &x;
+        assert_eq!(eq.to_string(), "(x + 1)");
+    }
+
+    #[test]
+    fn test_evaluate_1() {
+        let eq = (PartEquation::from(3) + 5) * 2;
+        assert_eq!(eq.evaluate().unwrap(), Number::from(16));
+
+        let eq = PartEquation::from(8).log(&PartEquation::from(2));
+        assert_eq!(eq.evaluate().unwrap(), Number::from(3));
+
+        let eq = PartEquation::from(9).sqrt();
+        assert_eq!(eq.evaluate().unwrap(), Number::from(3));
+
+        let eq = PartEquation::from(-8).pow(&(PartEquation::from(1) / PartEquation::from(3)));
+        assert_eq!(eq.evaluate().unwrap(), Number::from(-2));
+
+        assert!(matches!(
+            PartEquation::from('x').evaluate(),
+            Err(MathError::EquationMismatchError)
+        ));
+    }
+
+    #[test]
+    fn test_evaluate_domain_error_1() {
+        assert!(matches!(
+            PartEquation::from(-1).ln().evaluate(),
+            Err(MathError::DomainError)
+        ));
+        assert!(matches!(
+            PartEquation::from(0)
+                .log(&PartEquation::from(10))
+                .evaluate(),
+            Err(MathError::DomainError)
+        ));
+        assert!(matches!(
+            PartEquation::from(-16)
+                .pow(&(PartEquation::from(1) / PartEquation::from(4)))
+                .evaluate(),
+            Err(MathError::DomainError)
+        ));
+    }
+
+    #[test]
+    fn test_solving_minus_product_1() {
+        let x: PartEquation = PartEquation::from('x');
+
+        let eq: Equation = Equation::new(&-(&x * 3), &PartEquation::from(6));
+        assert_eq!(eq.solve('x').unwrap(), PartEquation::from(-2));
+
+        let eq: Equation = Equation::new(&-(&x / 2), &PartEquation::from(4));
+        assert_eq!(eq.solve('x').unwrap(), PartEquation::from(-8));
+
+        let eq: Equation =
+            Equation::new(&PartEquation::from(1), &(-(PartEquation::from(3) / &x) - 2));
+        assert_eq!(eq.solve('x').unwrap(), PartEquation::from(-1));
+
+        // unsimplified trees keep the MinusNode above the product
+        let constant = |n: i32| Arc::new(EquationComponentType::ConstantNode(Number::from(n)));
+        let variable = Arc::new(EquationComponentType::VariableNode('x'));
+        let trees = [
+            (
+                EquationComponentType::MulNode {
+                    lhs: constant(3),
+                    rhs: variable.clone(),
+                },
+                6,
+                -2,
+            ),
+            (
+                EquationComponentType::DivNode {
+                    numerator: variable.clone(),
+                    denominator: constant(2),
+                },
+                4,
+                -8,
+            ),
+            (
+                EquationComponentType::DivNode {
+                    numerator: constant(6),
+                    denominator: variable.clone(),
+                },
+                3,
+                -2,
+            ),
+        ];
+        for (product, rhs, solution) in trees {
+            let eq = EquationComponentType::SubNode {
+                lhs: Arc::new(EquationComponentType::MinusNode(Arc::new(product))),
+                rhs: constant(rhs),
+            };
+            assert_eq!(
+                Equation::do_inverse(
+                    &eq,
+                    EquationComponentType::ConstantNode(Number::from(0)),
+                    'x',
+                    &mut Vec::new()
+                )
+                .unwrap(),
+                EquationComponentType::ConstantNode(Number::from(solution))
+            );
+        }
+    }
+
+    #[test]
+    fn test_solving_unreachable_variable_1() {
+        let x: PartEquation = PartEquation::from('x');
+
+        let eq: Equation = Equation::new(
+            &(x.pow(&PartEquation::from(2)).sqrt() + 1),
+            &PartEquation::from(4),
+        );
+        assert!(matches!(eq.solve('x'), Err(MathError::NotYetImplemented)));
     }
-}
 
-impl Display for Equation {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{} = {}", self.lhs, self.rhs)
+    #[test]
+    fn test_simplify_pow_negative_exponent_1() {
+        let constant = |n: i32| Arc::new(EquationComponentType::ConstantNode(Number::from(n)));
+
+        // 2 ^ (-(2))
+        let eq = EquationComponentType::PowNode {
+            base: constant(2),
+            exponent: Arc::new(EquationComponentType::MinusNode(constant(2))),
+        };
+        assert_eq!(
+            eq.simplify(),
+            EquationComponentType::ConstantNode(Number::from(1) / Number::from(4))
+        );
+
+        // (-2) ^ 2
+        let eq = EquationComponentType::PowNode {
+            base: Arc::new(EquationComponentType::MinusNode(constant(2))),
+            exponent: constant(2),
+        };
+        assert_eq!(
+            eq.simplify(),
+            EquationComponentType::ConstantNode(Number::from(4))
+        );
+
+        let eq: PartEquation = PartEquation::from(2).pow(&-PartEquation::from(2));
+        assert_eq!(eq, PartEquation::from(1) / PartEquation::from(4));
+        assert!(matches!(
+            PartEquation::from(0)
+                .pow(&PartEquation::from(-1))
+                .evaluate(),
+            Err(MathError::ZeroDivisionError)
+        ));
     }
-}
 
-impl ops::Add<PartEquation> for PartEquation {
-    type Output = PartEquation;
+    #[test]
+    fn test_integer_width_operators_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let expected: PartEquation = &x * 10 + 5;
+
+        let eq: PartEquation = 5u32 + &x * 10i32;
+        assert_eq!(eq, expected);
+        let eq: PartEquation = x.clone() * 10i128 + 5u128;
+        assert_eq!(eq, expected);
+        let eq: PartEquation = 10u128 * x.clone() - (-5i128);
+        assert_eq!(eq, expected);
+        let eq: PartEquation = 10i32 * &x + 5u32;
+        assert_eq!(eq, expected);
+        let eq: PartEquation = 10i128 * x.clone() + 5;
+        assert_eq!(eq, expected);
+
+        let eq: PartEquation = (&x * 6 - 12u32) / 2i128;
+        assert_eq!(
+            eq.substitute('x', &PartEquation::from(5)),
+            PartEquation::from(9)
+        );
+        let eq: PartEquation = 1i32 - (2u32 / x.clone());
+        assert_eq!(eq, 1 - 2 / &x);
+        let eq: PartEquation = 12i128 / (&x - 3u128);
+        assert_eq!(
+            eq.substitute('x', &PartEquation::from(7)),
+            PartEquation::from(3)
+        );
+        let eq: PartEquation = 2u32 * (&x + 1.5) / 4i32;
+        assert_eq!(
+            eq.substitute('x', &PartEquation::from(2)),
+            PartEquation::from(1.75)
+        );
+    }
 
-    fn add(self, rhs: Self) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::AddNode {
-                lhs: Box::new(self.eq),
-                rhs: Box::new(rhs.eq),
-            },
-        }
-        .simplify()
+    #[test]
+    fn test_sqrt_negative_1() {
+        let result: Number = PartEquation::from(-4).sqrt().evaluate().unwrap();
+        assert!(matches!(result, Number::Complex(_)));
+        assert_eq!(result.to_string(), "2i");
+        assert_eq!(result.pow(&Number::from(2)), Number::from(-4));
+
+        assert_eq!(PartEquation::from(-4).sqrt().to_string(), "(2i)");
+        assert_eq!(
+            PartEquation::from(4).sqrt().evaluate().unwrap(),
+            Number::from(2)
+        );
     }
-}
 
-impl<'a> ops::Add<&'a PartEquation> for &'a PartEquation {
-    type Output = PartEquation;
+    #[test]
+    fn test_solve_with_steps_1() {
+        let x: PartEquation = PartEquation::from('x');
 
-    fn add(self, rhs: Self) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::AddNode {
-                lhs: Box::new(self.eq.clone()),
-                rhs: Box::new(rhs.eq.clone()),
-            },
-        }
-        .simplify()
+        let eq: Equation = Equation::new(&(2 * &x + 1), &PartEquation::from(5));
+        let (solution, steps) = eq.solve_with_steps('x').unwrap();
+        assert_eq!(solution, PartEquation::from(2));
+        assert_eq!(
+            steps,
+            vec!["subtract 1 from both sides", "divide both sides by 2"]
+        );
+
+        let eq: Equation = Equation::new(&(-(&x) + 3), &PartEquation::from(1));
+        let (solution, steps) = eq.solve_with_steps('x').unwrap();
+        assert_eq!(solution, PartEquation::from(2));
+        assert_eq!(
+            steps,
+            vec!["subtract 3 from both sides", "divide both sides by -1"]
+        );
+
+        // the variable on the right is solved for in the same way
+        let eq: Equation = Equation::new(&PartEquation::from(7), &(&x * 3 - 2));
+        let (solution, steps) = eq.solve_with_steps('x').unwrap();
+        assert_eq!(solution, PartEquation::from(3));
+        assert_eq!(steps, vec!["add 2 to both sides", "divide both sides by 3"]);
+
+        // on both sides it is first moved to the left: 3x + 1 - (x + 5) = 0
+        let eq: Equation = Equation::new(&(3 * &x + 1), &(&x + 5));
+        let (solution, steps) = eq.solve_with_steps('x').unwrap();
+        assert_eq!(solution, PartEquation::from(2));
+        assert_eq!(
+            steps,
+            vec![
+                "subtract (x + 5) from both sides",
+                "add 4 to both sides",
+                "divide both sides by 2"
+            ]
+        );
+
+        let eq: Equation = Equation::new(&(6 / (&x + 1)), &PartEquation::from(2));
+        let (solution, steps) = eq.solve_with_steps('x').unwrap();
+        assert_eq!(solution, PartEquation::from(2));
+        assert_eq!(
+            steps,
+            vec![
+                "multiply both sides by (x + 1)",
+                "divide both sides by 2",
+                "subtract 1 from both sides"
+            ]
+        );
+
+        let eq: Equation = Equation::new(&x, &x);
+        assert!(matches!(
+            eq.solve_with_steps('x'),
+            Err(MathError::InfiniteSolutions)
+        ));
     }
-}
 
-impl<'a> ops::Add<PartEquation> for &'a PartEquation {
-    type Output = PartEquation;
+    #[test]
+    fn test_solve_polynomial_1() {
+        let x: PartEquation = PartEquation::from('x');
 
-    fn add(self, rhs: PartEquation) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::AddNode {
-                lhs: Box::new(self.eq.clone()),
-                rhs: Box::new(rhs.eq),
-            },
+        let eq: Equation = Equation::new(
+            &(x.pow(&PartEquation::from(2)) - 5 * &x),
+            &PartEquation::from(-6),
+        );
+        assert_eq!(
+            eq.solve_polynomial('x').unwrap(),
+            vec![PartEquation::from(2), PartEquation::from(3)]
+        );
+
+        // (x - 1) ^ 2 (x ^ 2 - 2)
+        let eq: Equation = Equation::from_expr(
+            &((&x - 1).pow(&PartEquation::from(2)) * (x.pow(&PartEquation::from(2)) - 2)),
+        );
+        let roots: Vec<PartEquation> = eq.solve_polynomial('x').unwrap();
+        assert_eq!(roots.len(), 4);
+        assert_eq!(roots[0], PartEquation::from(1));
+        assert_eq!(roots[1], PartEquation::from(1));
+        for root in roots[2..].iter() {
+            let value: f64 = root.evaluate().unwrap().to_f64();
+            assert!((value.abs() - 2f64.sqrt()).abs() < 1e-12);
         }
-        .simplify()
+
+        let eq: Equation = Equation::new(&(2 * &x), &PartEquation::from(3));
+        assert_eq!(
+            eq.solve_polynomial('x').unwrap(),
+            vec![PartEquation::from(3) / PartEquation::from(2)]
+        );
+
+        let eq: Equation = Equation::from_expr(&(x.pow(&PartEquation::from(3)) - 2));
+        assert!(matches!(
+            eq.solve_polynomial('x'),
+            Err(MathError::NotYetImplemented)
+        ));
+        let eq: Equation = Equation::from_expr(&x.ln());
+        assert!(matches!(
+            eq.solve_polynomial('x'),
+            Err(MathError::EquationMismatchError)
+        ));
     }
-}
 
-impl<'a> ops::Add<&'a PartEquation> for PartEquation {
-    type Output = PartEquation;
+    #[test]
+    fn test_solve_system_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
+        let z: PartEquation = PartEquation::from('z');
 
-    fn add(self, rhs: &'a PartEquation) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::AddNode {
-                lhs: Box::new(self.eq),
-                rhs: Box::new(rhs.eq.clone()),
-            },
-        }
-        .simplify()
+        let system: System = System::new(vec![
+            Equation::new(&(&x + &y), &PartEquation::from(3)),
+            Equation::new(&(&x - &y), &PartEquation::from(1)),
+        ]);
+        let solution = system.solve_system(&['x', 'y']).unwrap();
+        assert_eq!(solution.len(), 2);
+        assert_eq!(solution[&'x'], PartEquation::from(2));
+        assert_eq!(solution[&'y'], PartEquation::from(1));
+
+        let system: System = System::new(vec![
+            Equation::new(&(2 * &x + &y - &z), &PartEquation::from(8)),
+            Equation::new(&(-3 * &x - &y + 2 * &z), &PartEquation::from(-11)),
+            Equation::new(&(-2 * &x + &y + 2 * &z), &PartEquation::from(-3)),
+        ]);
+        let solution = system.solve_system(&['x', 'y', 'z']).unwrap();
+        assert_eq!(solution[&'x'], PartEquation::from(2));
+        assert_eq!(solution[&'y'], PartEquation::from(3));
+        assert_eq!(solution[&'z'], PartEquation::from(-1));
+
+        let system: System = System::new(vec![
+            Equation::new(&(2 * &x), &(&y + 1)),
+            Equation::new(&(3 * &y), &PartEquation::from(2)),
+        ]);
+        let solution = system.solve_system(&['x', 'y']).unwrap();
+        assert_eq!(
+            solution[&'x'],
+            PartEquation::from(5) / PartEquation::from(6)
+        );
+
+        let system: System = System::new(vec![
+            Equation::new(&(&x + &y), &PartEquation::from(3)),
+            Equation::new(&(2 * &x + 2 * &y), &PartEquation::from(5)),
+        ]);
+        assert!(matches!(
+            system.solve_system(&['x', 'y']),
+            Err(MathError::NoSolution)
+        ));
+
+        let system: System = System::new(vec![
+            Equation::new(&(&x + &y), &PartEquation::from(3)),
+            Equation::new(&(2 * &x + 2 * &y), &PartEquation::from(6)),
+        ]);
+        assert!(matches!(
+            system.solve_system(&['x', 'y']),
+            Err(MathError::InfiniteSolutions)
+        ));
+
+        let system: System = System::new(vec![
+            Equation::new(&(&x * &y), &PartEquation::from(3)),
+            Equation::new(&(&x - &y), &PartEquation::from(1)),
+        ]);
+        assert!(matches!(
+            system.solve_system(&['x', 'y']),
+            Err(MathError::NotYetImplemented)
+        ));
     }
-}
 
-impl ops::Add<i64> for PartEquation {
-    type Output = PartEquation;
+    #[test]
+    fn test_num_solutions_hint_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
+        let square: PartEquation = x.pow(&PartEquation::from(2));
 
-    fn add(self, rhs: i64) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::AddNode {
-                lhs: Box::new(self.eq),
-                rhs: Box::new(EquationComponentType::ConstantNode(Number::from(rhs))),
-            },
-        }
-        .simplify()
+        let eq: Equation = Equation::new(&(3 * &x + 1), &(&x - 5));
+        assert_eq!(eq.num_solutions_hint('x'), Some(1));
+
+        // x^2 - 5x + 6 = 0, roots 2 and 3
+        let eq: Equation = Equation::new(&(&square + 6), &(5 * &x));
+        assert_eq!(eq.num_solutions_hint('x'), Some(2));
+        assert_eq!(eq.solve_polynomial('x').unwrap().len(), 2);
+
+        let eq: Equation = Equation::from_expr(&(&square + 2 * &x + 5));
+        assert_eq!(eq.num_solutions_hint('x'), Some(0));
+
+        let eq: Equation = Equation::from_expr(&(&square - 4 * &x + 4));
+        assert_eq!(eq.num_solutions_hint('x'), Some(1));
+
+        let eq: Equation = Equation::from_expr(&(&square + &y));
+        assert_eq!(eq.num_solutions_hint('x'), None);
+        let eq: Equation = Equation::from_expr(&(&square * &x - 1));
+        assert_eq!(eq.num_solutions_hint('x'), None);
+        let eq: Equation = Equation::new(&x.ln(), &PartEquation::from(2));
+        assert_eq!(eq.num_solutions_hint('x'), None);
     }
-}
 
-impl ops::Add<f64> for PartEquation {
-    type Output = PartEquation;
+    #[test]
+    fn test_simplify_divide_by_one_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
 
-    fn add(self, rhs: f64) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::AddNode {
-                lhs: Box::new(self.eq),
-                rhs: Box::new(EquationComponentType::ConstantNode(Number::from(rhs))),
-            },
-        }
-        .simplify()
+        assert_eq!(&x / 1, x);
+        assert_eq!((&x + &y) / PartEquation::from(1), &x + &y);
+        assert_eq!((&x + &y) / 1, &x + &y);
+
+        // the coefficient of y is 1, the solution is not left divided by it
+        let eq: Equation = Equation::new(&(&x + &y), &PartEquation::from(5));
+        assert_eq!(eq.solve('y').unwrap(), 5 - &x);
+        assert_eq!(eq.solve('y').unwrap().to_string(), "(5 + (-1 * x))");
     }
-}
 
-impl ops::Add<PartEquation> for i64 {
-    type Output = PartEquation;
+    #[test]
+    fn test_solve_for_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
+        let square: PartEquation = y.pow(&PartEquation::from(2));
 
-    fn add(self, rhs: PartEquation) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::AddNode {
-                lhs: Box::new(EquationComponentType::ConstantNode(Number::from(self))),
-                rhs: Box::new(rhs.eq),
-            },
-        }
-        .simplify()
+        let eq: Equation = Equation::new(&(&x + &square), &PartEquation::from(5));
+        assert_eq!(eq.solve_for(&square).unwrap(), 5 - &x);
+
+        let eq: Equation = Equation::new(&(3 * &square - 1), &(&x + 2));
+        assert_eq!(eq.solve_for(&square).unwrap(), (&x + 3) / 3);
+
+        let reciprocal: PartEquation = PartEquation::from(1) / &x;
+        let eq: Equation = Equation::new(&(&reciprocal + 2), &y);
+        assert_eq!(eq.solve_for(&reciprocal).unwrap(), &y - 2);
+
+        let eq: Equation = Equation::new(&(2 * &x + 1), &PartEquation::from(7));
+        assert_eq!(eq.solve_for(&x).unwrap(), PartEquation::from(3));
+
+        let eq: Equation = Equation::new(&(&x + 1), &y);
+        assert!(matches!(
+            eq.solve_for(&square),
+            Err(MathError::EquationMismatchError)
+        ));
     }
-}
 
-impl ops::Add<PartEquation> for f64 {
-    type Output = PartEquation;
+    #[test]
+    fn test_check_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
 
-    fn add(self, rhs: PartEquation) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::AddNode {
-                lhs: Box::new(EquationComponentType::ConstantNode(Number::from(self))),
-                rhs: Box::new(rhs.eq),
-            },
-        }
-        .simplify()
+        let eq: Equation = Equation::new(&(2 * &x + 1), &PartEquation::from(5));
+        let solution: Number = eq.solve('x').unwrap().evaluate().unwrap();
+        assert!(eq.check('x', &solution).unwrap());
+        assert!(!eq.check('x', &Number::from(3)).unwrap());
+
+        // exact values are compared exactly
+        let eq: Equation = Equation::new(&(3 * &x), &PartEquation::from(1));
+        let solution: Number = eq.solve('x').unwrap().evaluate().unwrap();
+        assert!(eq.check('x', &solution).unwrap());
+        assert!(!eq.check('x', &Number::from(0.333333)).unwrap());
+
+        // the float root of x^2 = 2 passes within the tolerance
+        let eq: Equation = Equation::new(&x.pow(&PartEquation::from(2)), &PartEquation::from(2));
+        let solution: Number = eq.solve('x').unwrap().evaluate().unwrap();
+        assert!(matches!(solution, Number::Float(_)));
+        assert!(eq.check('x', &solution).unwrap());
+        assert!(!eq.check('x', &Number::from(1.414)).unwrap());
+
+        let eq: Equation = Equation::new(&(PartEquation::from(1) / &x), &PartEquation::from(1));
+        assert!(matches!(
+            eq.check('x', &Number::from(0)),
+            Err(MathError::ZeroDivisionError)
+        ));
+        let eq: Equation = Equation::new(&(&x + &y), &PartEquation::from(1));
+        assert!(matches!(
+            eq.check('x', &Number::from(1)),
+            Err(MathError::EquationMismatchError)
+        ));
     }
-}
 
-impl<'a> ops::Add<i64> for &'a PartEquation {
-    type Output = PartEquation;
+    #[test]
+    fn test_solve_quadratic_1() {
+        let x: PartEquation = PartEquation::from('x');
 
-    fn add(self, rhs: i64) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::AddNode {
-                lhs: Box::new(self.eq.clone()),
-                rhs: Box::new(EquationComponentType::ConstantNode(Number::from(rhs))),
-            },
-        }
-        .simplify()
+        // x^2 + 2x + 5 = 0
+        let eq: Equation = Equation::from_expr(&(x.pow(&PartEquation::from(2)) + 2 * &x + 5));
+        let (first, second) = eq.solve_quadratic('x').unwrap();
+        assert_eq!(first.evaluate().unwrap().to_string(), "-1 + 2i");
+        assert_eq!(second.evaluate().unwrap().to_string(), "-1 - 2i");
+
+        // x^2 = 9
+        let eq: Equation = Equation::new(&x.pow(&PartEquation::from(2)), &PartEquation::from(9));
+        let (first, second) = eq.solve_quadratic('x').unwrap();
+        assert_eq!(first.evaluate().unwrap(), Number::from(3));
+        assert_eq!(second.evaluate().unwrap(), Number::from(-3));
+        assert!(!matches!(first.evaluate().unwrap(), Number::Complex(_)));
+
+        assert!(matches!(
+            Equation::from_expr(&(2 * &x + 1)).solve_quadratic('x'),
+            Err(MathError::EquationMismatchError)
+        ));
     }
-}
 
-impl<'a> ops::Add<f64> for &'a PartEquation {
-    type Output = PartEquation;
+    #[test]
+    fn test_display_minus_1() {
+        let x: PartEquation = PartEquation::from('x');
+        assert_eq!((-&x).to_string(), "-x");
 
-    fn add(self, rhs: f64) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::AddNode {
-                lhs: Box::new(self.eq.clone()),
-                rhs: Box::new(EquationComponentType::ConstantNode(Number::from(rhs))),
-            },
-        }
-        .simplify()
+        let eq = PartEquation {
+            eq: EquationComponentType::MinusNode(Arc::new(EquationComponentType::AddNode {
+                lhs: Arc::new(EquationComponentType::VariableNode('x')),
+                rhs: Arc::new(EquationComponentType::VariableNode('y')),
+            })),
+        };
+        assert_eq!(eq.to_string(), "-(x + y)");
+
+        let eq = PartEquation {
+            eq: EquationComponentType::MinusNode(Arc::new(EquationComponentType::MinusNode(
+                Arc::new(EquationComponentType::VariableNode('x')),
+            ))),
+        };
+        assert_eq!(eq.to_string(), "-(-x)");
+
+        let eq = PartEquation {
+            eq: EquationComponentType::MinusNode(Arc::new(EquationComponentType::ConstantNode(
+                Number::from(-2),
+            ))),
+        };
+        assert_eq!(eq.to_string(), "-(-2)");
     }
-}
 
-impl<'a> ops::Add<&'a PartEquation> for i64 {
-    type Output = PartEquation;
+    #[test]
+    fn test_display_abs_1() {
+        let abs = |value: EquationComponentType| EquationComponentType::AbsNode(Arc::new(value));
+        let x = || EquationComponentType::VariableNode('x');
 
-    fn add(self, rhs: &PartEquation) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::AddNode {
-                lhs: Box::new(EquationComponentType::ConstantNode(Number::from(self))),
-                rhs: Box::new(rhs.eq.clone()),
-            },
-        }
-        .simplify()
+        // a bar right inside bars would close them, so the inner value is parenthesized
+        let eq = PartEquation { eq: abs(abs(x())) };
+        assert_eq!(eq.to_string(), "|(|x|)|");
+        assert!(eq.to_string().parse::<PartEquation>().is_ok());
+
+        let eq = PartEquation {
+            eq: abs(EquationComponentType::MinusNode(Arc::new(abs(x())))),
+        };
+        assert_eq!(eq.to_string(), "|(-|x|)|");
+        assert!(eq.to_string().parse::<PartEquation>().is_ok());
+
+        let eq = PartEquation {
+            eq: abs(EquationComponentType::AddNode {
+                lhs: Arc::new(abs(x())),
+                rhs: Arc::new(EquationComponentType::ConstantNode(Number::from(1))),
+            }),
+        };
+        assert_eq!(eq.to_string(), "|(|x| + 1)|");
+        assert_eq!(PartEquation { eq: abs(x()) }.to_string(), "|x|");
     }
-}
 
-impl<'a> ops::Add<&'a PartEquation> for f64 {
-    type Output = PartEquation;
+    #[test]
+    fn test_classify_equation_1() {
+        let x: PartEquation = PartEquation::from('x');
 
-    fn add(self, rhs: &PartEquation) -> Self::Output {
-        PartEquation {
+        let eq: Equation = Equation::new(&x, &x);
+        assert_eq!(eq.classify(), EquationClass::Identity);
+        assert!(matches!(eq.solve('x'), Err(MathError::InfiniteSolutions)));
+
+        let eq: Equation = Equation::new(&(&x + 1), &x);
+        assert_eq!(eq.classify(), EquationClass::Contradiction);
+        assert!(matches!(eq.solve('x'), Err(MathError::NoSolution)));
+
+        let eq: Equation = Equation::new(&(2 * &x + 1), &(&x + 3));
+        assert_eq!(eq.classify(), EquationClass::Solvable);
+        assert_eq!(eq.solve('x').unwrap(), PartEquation::from(2));
+    }
+
+    #[test]
+    fn test_degree_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
+
+        assert_eq!((x.pow(&PartEquation::from(3)) + &x).degree('x'), Some(3));
+        assert_eq!(PartEquation::from(5).degree('x'), Some(0));
+        assert_eq!((&x * &y + &y).degree('x'), Some(1));
+        assert_eq!((&x * &y + &y).degree('y'), Some(1));
+        assert_eq!(
+            ((&x + 1).pow(&PartEquation::from(2)) - x.pow(&PartEquation::from(2))).degree('x'),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_degree_2() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
+
+        assert_eq!(x.ln().degree('x'), None);
+        assert_eq!(x.ln().degree('y'), Some(0));
+        assert_eq!(x.sqrt().degree('x'), None);
+        assert_eq!((&y / &x).degree('x'), None);
+        assert_eq!(PartEquation::from(2).pow(&x).degree('x'), None);
+    }
+
+    #[test]
+    fn test_simplify_options_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
+        let z: PartEquation = PartEquation::from('z');
+        let distribute: SimplifyOptions = SimplifyOptions::new().distribute(true);
+
+        let eq: PartEquation = &x * (&y + &z);
+        assert!(matches!(
+            eq.simplify().eq,
+            EquationComponentType::MulNode { .. }
+        ));
+        assert!(matches!(
+            eq.simplify_with(&distribute).eq,
+            EquationComponentType::AddNode { .. }
+        ));
+        assert_ne!(eq.simplify().eq, eq.simplify_with(&distribute).eq);
+        assert_eq!(eq.simplify_with(&distribute), &x * &y + &x * &z);
+
+        let eq: PartEquation = (&x + 1) * (&x - 1);
+        assert_eq!(
+            eq.simplify_with(&distribute),
+            x.pow(&PartEquation::from(2)) - 1
+        );
+    }
+
+    #[test]
+    fn test_simplify_options_2() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
+        let constant = |n: f64| Arc::new(EquationComponentType::ConstantNode(Number::from(n)));
+
+        // x + 1.5 + 2.5, as parsed before any simplification
+        let eq: PartEquation = PartEquation {
             eq: EquationComponentType::AddNode {
-                lhs: Box::new(EquationComponentType::ConstantNode(Number::from(self))),
-                rhs: Box::new(rhs.eq.clone()),
+                lhs: Arc::new(EquationComponentType::AddNode {
+                    lhs: Arc::new(EquationComponentType::VariableNode('x')),
+                    rhs: constant(1.5),
+                }),
+                rhs: constant(2.5),
             },
-        }
-        .simplify()
+        };
+        assert_eq!(eq.simplify(), &x + 4);
+        let kept: PartEquation = eq.simplify_with(&SimplifyOptions::new().fold_floats(false));
+        assert!(kept
+            .eq
+            .contains(&EquationComponentType::ConstantNode(Number::from(1.5))));
+        assert!(kept
+            .eq
+            .contains(&EquationComponentType::ConstantNode(Number::from(2.5))));
+
+        let combine: SimplifyOptions = SimplifyOptions::new().combine_fractions(true);
+        let eq: PartEquation = &x / 2 + &y / 3;
+        assert!(matches!(
+            eq.simplify().eq,
+            EquationComponentType::AddNode { .. }
+        ));
+        assert_eq!(eq.simplify_with(&combine), (&x * 3 + &y * 2) / 6);
+        let eq: PartEquation = &x / 4 + &y / 6;
+        assert_eq!(eq.simplify_with(&combine), (&x * 3 + &y * 2) / 12);
+        let eq: PartEquation = &x / 3 + PartEquation::from(1) / PartEquation::from(2);
+        assert_eq!(eq.simplify_with(&combine), (&x * 2 + 3) / 6);
+        let (numerator, denominator) =
+            (&x / 4 + PartEquation::from(3) / PartEquation::from(4)).as_fraction();
+        assert_eq!((numerator, denominator), (&x + 3, PartEquation::from(4)));
+        let eq: PartEquation = PartEquation::from(1) / &x + 1;
+        assert_eq!(eq.simplify_with(&combine), (&x + 1) / &x);
+
+        let fixpoint: SimplifyOptions = SimplifyOptions::new().distribute(true).fixpoint(true);
+        let eq: PartEquation = (&x + &y) * (&x - &y) * 2;
+        let once: PartEquation = eq.simplify_with(&fixpoint);
+        assert_eq!(once.simplify_with(&fixpoint).eq, once.eq);
+        assert_eq!(eq.simplify_with(&SimplifyOptions::new()), eq.simplify());
     }
-}
 
-impl ops::Sub<PartEquation> for PartEquation {
-    type Output = PartEquation;
+    #[test]
+    fn test_is_polynomial_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
 
-    fn sub(self, rhs: Self) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::SubNode {
-                lhs: Box::new(self.eq),
-                rhs: Box::new(rhs.eq),
-            },
-        }
-        .simplify()
+        assert!((x.pow(&PartEquation::from(3)) * 2 - &x + 7).is_polynomial('x'));
+        assert!((&x * &y + y.ln()).is_polynomial('x'));
+        assert!(PartEquation::from(5).is_polynomial('x'));
+        assert!(((x.pow(&PartEquation::from(2)) - 1) / (&x - 1)).is_polynomial('x'));
+        assert!((&x / 2).is_polynomial('x'));
+
+        assert!(!(PartEquation::from(1) / &x).is_polynomial('x'));
+        assert!(!x.sqrt().is_polynomial('x'));
+        assert!(!x.ln().is_polynomial('x'));
+        assert!(!x.log(&PartEquation::from(10)).is_polynomial('x'));
+        assert!(!x.pow(&PartEquation::from(-2)).is_polynomial('x'));
+        assert!(!PartEquation::from(2).pow(&x).is_polynomial('x'));
+        assert!(!x.pow(&y).is_polynomial('x'));
     }
-}
 
-impl<'a> ops::Sub<&'a PartEquation> for &'a PartEquation {
-    type Output = PartEquation;
+    #[test]
+    fn test_free_of_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
 
-    fn sub(self, rhs: Self) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::SubNode {
-                lhs: Box::new(self.eq.clone()),
-                rhs: Box::new(rhs.eq.clone()),
-            },
-        }
-        .simplify()
+        assert!((&y + 1).free_of('x'));
+        assert!(!(&x * &y).free_of('x'));
+        assert!(!(&x * &y).free_of('y'));
+        assert!(PartEquation::from(3).free_of('x'));
+        assert!((&x - &x + &y).free_of('x'));
+        assert!(!(&y + 1).log(&x).free_of('x'));
     }
-}
 
-impl<'a> ops::Sub<PartEquation> for &'a PartEquation {
-    type Output = PartEquation;
+    #[test]
+    fn test_exp_1() {
+        let x: PartEquation = PartEquation::from('x');
 
-    fn sub(self, rhs: PartEquation) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::SubNode {
-                lhs: Box::new(self.eq.clone()),
-                rhs: Box::new(rhs.eq),
-            },
-        }
-        .simplify()
+        assert_eq!(PartEquation::from(0).exp(), PartEquation::from(1));
+        assert_eq!(x.exp().ln(), x);
+        assert_eq!(x.ln().exp(), x);
+        assert_eq!((&x + 1).ln().exp(), &x + 1);
+        assert_eq!(PartEquation::e().pow(&x), x.exp());
+        assert_eq!(x.exp().to_string(), "(e ^ x)");
+        assert_eq!(PartEquation::from(1).exp().evaluate().unwrap(), Number::e());
+
+        let eq: PartEquation = (&x * 2).exp();
+        assert_eq!(
+            eq.substitute('x', &PartEquation::from(0)),
+            PartEquation::from(1)
+        );
+        assert_eq!(eq.differentiate('x'), eq.clone() * 2);
+        assert_eq!(x.exp().integrate('x').unwrap(), x.exp());
+        assert!(eq.contains(&(&x * 2)));
+        assert_eq!(eq.degree('x'), None);
+
+        let f = eq.compile('x');
+        assert!((f(1.5).unwrap() - 3f64.exp()).abs() < 1e-9);
+        assert!((eq.to_program().run(&[('x', 1.5)]).unwrap() - 3f64.exp()).abs() < 1e-9);
+
+        let solution: Number = Equation::new(&(x.exp() + 1), &PartEquation::from(6))
+            .solve('x')
+            .unwrap()
+            .evaluate()
+            .unwrap();
+        assert!((solution.to_f64() - 5f64.ln()).abs() < 1e-9);
     }
-}
 
-impl<'a> ops::Sub<&'a PartEquation> for PartEquation {
-    type Output = PartEquation;
+    #[test]
+    fn test_floordiv_rem_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let two: PartEquation = PartEquation::from(2);
+
+        assert_eq!(PartEquation::from(7).floordiv(&two), PartEquation::from(3));
+        assert_eq!(
+            PartEquation::from(-7).floordiv(&two),
+            PartEquation::from(-4)
+        );
+        assert_eq!(
+            PartEquation::from(-7).rem(&PartEquation::from(3)),
+            PartEquation::from(2)
+        );
+        assert_eq!(
+            PartEquation::from(7).rem(&PartEquation::from(-3)),
+            PartEquation::from(-2)
+        );
+        assert_eq!(
+            (PartEquation::from(7) / 2).rem(&PartEquation::from(1)),
+            PartEquation::from(1) / 2
+        );
+
+        // a division by zero is left for evaluate to report
+        let eq: PartEquation = PartEquation::from(1).floordiv(&PartEquation::from(0));
+        assert!(matches!(eq.evaluate(), Err(MathError::ZeroDivisionError)));
+
+        let eq: PartEquation = x.rem(&two);
+        assert_eq!(eq.to_string(), "(x mod 2)");
+        assert_eq!(eq.simplify().to_string(), "(x mod 2)");
+        assert_eq!(
+            eq.substitute('x', &PartEquation::from(-7)),
+            PartEquation::from(1)
+        );
+        assert_eq!(eq.to_program().run(&[('x', -7.0)]).unwrap(), 1.0);
+        assert_eq!(eq.differentiate('x'), PartEquation::from(1));
+        assert_eq!(eq.degree('x'), None);
+        assert!(eq.contains(&x));
+
+        let eq: PartEquation = (&x + 1).floordiv(&two);
+        assert_eq!(eq.to_string(), "floor((x + 1) / 2)");
+        assert_eq!(eq.to_infix(), "(x + 1)//2");
+        assert_eq!(
+            eq.substitute('x', &PartEquation::from(6)),
+            PartEquation::from(3)
+        );
+        assert_eq!((eq.compile('x'))(-2.5).unwrap(), -1.0);
+        assert_eq!(eq.differentiate('x'), PartEquation::from(0));
+    }
 
-    fn sub(self, rhs: &'a PartEquation) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::SubNode {
-                lhs: Box::new(self.eq),
-                rhs: Box::new(rhs.eq.clone()),
-            },
-        }
-        .simplify()
+    #[test]
+    fn test_numerically_equal_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
+        let z: PartEquation = PartEquation::from('z');
+
+        assert!((&x * (&y + &z)).numerically_equal(&(&x * &y + &x * &z), 50, 1e-9));
+        assert!(!(&x * (&y + &z)).numerically_equal(&(&x * &y + &z), 50, 1e-9));
+        assert!((x.pow(&PartEquation::from(2)).sqrt()).numerically_equal(
+            &(&x * &x).pow(&(PartEquation::from(1) / PartEquation::from(2))),
+            50,
+            1e-9
+        ));
+        assert!(!(&x + 1).numerically_equal(&(&x + 1.001), 50, 1e-9));
+        assert!((&x + 1).numerically_equal(&(&x + 1.001), 50, 1e-2));
+
+        // ln(x * y) is defined where ln(x) + ln(y) is not, but agrees where both are
+        assert!((&x * &y)
+            .ln()
+            .numerically_equal(&(x.ln() + y.ln()), 50, 1e-9));
+        assert!(!(x.pow(&PartEquation::from(2)) + 1).ln().numerically_equal(
+            &(-x.pow(&PartEquation::from(2)) - 1).ln(),
+            50,
+            1e-9
+        ));
     }
-}
 
-impl ops::Sub<i64> for PartEquation {
-    type Output = PartEquation;
+    #[test]
+    fn test_nth_derivative_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
+
+        let eq: PartEquation = x.pow(&PartEquation::from(3));
+        assert_eq!(eq.nth_derivative('x', 0), eq);
+        assert_eq!(eq.nth_derivative('x', 1), 3 * x.pow(&PartEquation::from(2)));
+        assert_eq!(eq.nth_derivative('x', 2), 6 * &x);
+        assert_eq!(eq.nth_derivative('x', 3), PartEquation::from(6));
+        assert_eq!(eq.nth_derivative('x', 4), PartEquation::from(0));
+
+        let eq: PartEquation = x.exp() * &y;
+        assert_eq!(eq.nth_derivative('x', 5), eq);
+        assert_eq!(eq.nth_derivative('y', 2), PartEquation::from(0));
+    }
+
+    #[test]
+    fn test_substitute_named_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let theta: PartEquation = PartEquation::from('θ');
+
+        let eq: PartEquation = 2 * &theta + &x;
+        assert_eq!(
+            eq.substitute_named("theta", &PartEquation::from(3)),
+            Some(&x + 6)
+        );
+        assert_eq!(
+            eq.substitute_named("θ", &PartEquation::from(3)),
+            Some(&x + 6)
+        );
+        assert_eq!(
+            eq.substitute_named("x", &PartEquation::from(1)),
+            Some(eq.substitute('x', &PartEquation::from(1)))
+        );
+        assert_eq!(eq.substitute_named("radius", &PartEquation::from(1)), None);
+    }
+
+    #[test]
+    fn test_differentiate_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
+
+        assert_eq!(
+            x.pow(&PartEquation::from(3)).differentiate('x'),
+            x.pow(&PartEquation::from(2)) * 3
+        );
+        assert_eq!((&x * 5 + &y).differentiate('x'), PartEquation::from(5));
+        assert_eq!((&x * &y).differentiate('y'), x.clone());
+        assert_eq!(y.ln().differentiate('x'), PartEquation::from(0));
+
+        // checked by value where the canonical form is not obvious
+        let at = |eq: PartEquation, value: i64| {
+            eq.substitute('x', &PartEquation::from(value))
+                .evaluate()
+                .unwrap()
+                .to_f64()
+        };
+        let close = |lhs: f64, rhs: f64| (lhs - rhs).abs() < 1e-9;
+
+        let eq: PartEquation = (&x * x.ln()).differentiate('x');
+        assert!(close(at(eq, 3), 3f64.ln() + 1.0));
+
+        let eq: PartEquation = ((&x + 1) / (&x - 1)).differentiate('x');
+        assert!(close(at(eq, 3), -0.5));
+
+        let eq: PartEquation = PartEquation::e().pow(&(&x * 2)).differentiate('x');
+        assert!(close(at(eq, 1), 2.0 * 2f64.exp()));
 
-    fn sub(self, rhs: i64) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::SubNode {
-                lhs: Box::new(self.eq),
-                rhs: Box::new(EquationComponentType::ConstantNode(Number::from(rhs))),
-            },
-        }
-        .simplify()
-    }
-}
+        let eq: PartEquation = x.pow(&x).differentiate('x');
+        assert!(close(at(eq, 2), 4.0 * (2f64.ln() + 1.0)));
 
-impl ops::Sub<f64> for PartEquation {
-    type Output = PartEquation;
+        let eq: PartEquation = x.log(&PartEquation::from(10)).differentiate('x');
+        assert!(close(at(eq, 4), 1.0 / (4.0 * 10f64.ln())));
 
-    fn sub(self, rhs: f64) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::SubNode {
-                lhs: Box::new(self.eq),
-                rhs: Box::new(EquationComponentType::ConstantNode(Number::from(rhs))),
-            },
-        }
-        .simplify()
+        let eq: PartEquation = (&x - 5)
+            .pow(&PartEquation::from(2))
+            .sqrt()
+            .differentiate('x');
+        assert!(close(at(eq.clone(), 3), -1.0));
+        assert!(close(at(eq, 7), 1.0));
     }
-}
 
-impl ops::Sub<PartEquation> for i64 {
-    type Output = PartEquation;
+    #[test]
+    fn test_taylor_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let half: PartEquation = PartEquation::from(1) / PartEquation::from(2);
+
+        let eq: PartEquation = x.exp();
+        assert_eq!(
+            eq.taylor('x', &Number::from(0), 2).unwrap(),
+            x.pow(&PartEquation::from(2)) * &half + &x + 1
+        );
+
+        let eq: PartEquation = x.pow(&PartEquation::from(2)) * 3 + &x;
+        assert_eq!(eq.taylor('x', &Number::from(0), 5).unwrap(), eq);
+        assert_eq!(eq.taylor('x', &Number::from(0), 1).unwrap(), x.clone());
+
+        assert!(matches!(
+            x.ln().taylor('x', &Number::from(0), 1),
+            Err(MathError::DomainError)
+        ));
+    }
 
-    fn sub(self, rhs: PartEquation) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::SubNode {
-                lhs: Box::new(EquationComponentType::ConstantNode(Number::from(self))),
-                rhs: Box::new(rhs.eq),
-            },
-        }
-        .simplify()
+    #[test]
+    fn test_integrate_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
+        let third: PartEquation = PartEquation::from(1) / PartEquation::from(3);
+
+        assert_eq!(
+            x.pow(&PartEquation::from(2)).integrate('x').unwrap(),
+            x.pow(&PartEquation::from(3)) * &third
+        );
+        assert_eq!(
+            (&x * 2 + 1).integrate('x').unwrap(),
+            x.pow(&PartEquation::from(2)) + &x
+        );
+        assert_eq!((PartEquation::from(1) / &x).integrate('x').unwrap(), x.ln());
+        assert_eq!((&y * 3).integrate('x').unwrap(), &x * &y * 3);
+        assert_eq!(
+            (&y / x.pow(&PartEquation::from(3))).integrate('x').unwrap(),
+            &y * (x.pow(&PartEquation::from(-2))
+                * (PartEquation::from(-1) / PartEquation::from(2)))
+        );
+        assert_eq!(
+            x.sqrt().integrate('x').unwrap(),
+            x.pow(&(PartEquation::from(3) / PartEquation::from(2)))
+                * (PartEquation::from(2) / PartEquation::from(3))
+        );
     }
-}
 
-impl ops::Sub<PartEquation> for f64 {
-    type Output = PartEquation;
+    #[test]
+    fn test_integrate_2() {
+        let x: PartEquation = PartEquation::from('x');
 
-    fn sub(self, rhs: PartEquation) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::SubNode {
-                lhs: Box::new(EquationComponentType::ConstantNode(Number::from(self))),
-                rhs: Box::new(rhs.eq),
-            },
-        }
-        .simplify()
+        assert!(matches!(
+            (&x * x.ln()).integrate('x'),
+            Err(MathError::NotYetImplemented)
+        ));
+        assert!(matches!(
+            PartEquation::from(2).pow(&x).integrate('x'),
+            Err(MathError::NotYetImplemented)
+        ));
+        assert!(matches!(
+            (PartEquation::from(1) / (&x + 1)).integrate('x'),
+            Err(MathError::NotYetImplemented)
+        ));
     }
-}
 
-impl<'a> ops::Sub<i64> for &'a PartEquation {
-    type Output = PartEquation;
+    #[test]
+    fn test_integrate_between_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
 
-    fn sub(self, rhs: i64) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::SubNode {
-                lhs: Box::new(self.eq.clone()),
-                rhs: Box::new(EquationComponentType::ConstantNode(Number::from(rhs))),
-            },
-        }
-        .simplify()
+        assert_eq!(
+            x.integrate_between('x', &Number::from(0), &Number::from(2))
+                .unwrap(),
+            Number::from(2)
+        );
+        assert_eq!(
+            (x.pow(&PartEquation::from(2)) * 3 - 1)
+                .integrate_between('x', &Number::from(-1), &Number::from(2))
+                .unwrap(),
+            Number::from(6)
+        );
+        assert_eq!(
+            x.integrate_between('x', &Number::from(1), &Number::from(0))
+                .unwrap(),
+            Number::from(-1) / Number::from(2)
+        );
+
+        assert!(matches!(
+            (&x * x.ln()).integrate_between('x', &Number::from(1), &Number::from(2)),
+            Err(MathError::NotYetImplemented)
+        ));
+        assert!(matches!(
+            (&x * &y).integrate_between('x', &Number::from(0), &Number::from(1)),
+            Err(MathError::EquationMismatchError)
+        ));
     }
-}
 
-impl<'a> ops::Sub<f64> for &'a PartEquation {
-    type Output = PartEquation;
+    #[test]
+    fn test_equation_from_parts_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let lhs: PartEquation = &x * 2 + 1;
+        let rhs: PartEquation = PartEquation::from(7);
 
-    fn sub(self, rhs: f64) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::SubNode {
-                lhs: Box::new(self.eq.clone()),
-                rhs: Box::new(EquationComponentType::ConstantNode(Number::from(rhs))),
-            },
-        }
-        .simplify()
+        let eq: Equation = Equation::new(&lhs, &rhs);
+        assert_eq!(Equation::from_parts(lhs.clone(), rhs.clone()), eq);
+        assert_eq!(Equation::from((lhs, rhs)), eq);
+        assert_eq!(eq.solve('x').unwrap(), PartEquation::from(3));
     }
-}
 
-impl<'a> ops::Sub<&'a PartEquation> for i64 {
-    type Output = PartEquation;
+    #[test]
+    fn test_equation_operators_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let sides = |eq: &Equation| {
+            (
+                PartEquation { eq: eq.lhs.clone() },
+                PartEquation { eq: eq.rhs.clone() },
+            )
+        };
 
-    fn sub(self, rhs: &PartEquation) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::SubNode {
-                lhs: Box::new(EquationComponentType::ConstantNode(Number::from(self))),
-                rhs: Box::new(rhs.eq.clone()),
-            },
-        }
-        .simplify()
+        let eq: Equation = Equation::new(&(&x - 5), &PartEquation::from(0));
+        let shifted: Equation = &eq + 5;
+        assert_eq!(sides(&shifted), (x.clone(), PartEquation::from(5)));
+        assert_eq!(shifted.to_string(), "x = 5");
+
+        let eq: Equation = Equation::new(&(&x * 2 + 3), &PartEquation::from(7));
+        let eq: Equation = eq - 3.0 - &x;
+        assert_eq!(sides(&eq), (x.clone(), 4 - &x));
+
+        let halved: Equation = (&eq / 2).unwrap();
+        assert_eq!(sides(&halved), (&x / 2, (4 - &x) / 2));
+        assert!(matches!(&eq / 0, Err(MathError::ZeroDivisionError)));
+        assert!(matches!(
+            eq.clone() * 0.0,
+            Err(MathError::UnsupportedOperation)
+        ));
+        assert_eq!(sides(&-&eq), (-&x, &x - 4));
+        assert_eq!(sides(&(eq * &x).unwrap()), (&x * &x, (4 - &x) * &x));
     }
-}
 
-impl<'a> ops::Sub<&'a PartEquation> for f64 {
-    type Output = PartEquation;
-
-    fn sub(self, rhs: &PartEquation) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::SubNode {
-                lhs: Box::new(EquationComponentType::ConstantNode(Number::from(self))),
-                rhs: Box::new(rhs.eq.clone()),
-            },
-        }
-        .simplify()
+    #[test]
+    fn test_clear_denominators_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
+        let one: PartEquation = PartEquation::from(1);
+
+        let eq: Equation = Equation::new(&(&one / &x + &one / &y), &one).clear_denominators();
+        assert_eq!(PartEquation { eq: eq.lhs.clone() }, &y + &x);
+        assert_eq!(PartEquation { eq: eq.rhs.clone() }, &x * &y);
+
+        // equal denominators are dropped instead of multiplied through
+        let eq: Equation = Equation::new(&(&x / (&y + 1)), &(PartEquation::from(2) / (&y + 1)))
+            .clear_denominators();
+        assert_eq!(eq.to_string(), "x = 2");
+
+        let eq: Equation = Equation::new(&(&x + &one / &x), &PartEquation::from(2));
+        assert_eq!(
+            eq.clear_denominators().solve_polynomial('x').unwrap(),
+            vec![PartEquation::from(1), PartEquation::from(1)]
+        );
     }
-}
 
-impl ops::Mul<PartEquation> for PartEquation {
-    type Output = PartEquation;
+    #[test]
+    fn test_equation_degree_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
 
-    fn mul(self, rhs: Self) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::MulNode {
-                lhs: Box::new(self.eq),
-                rhs: Box::new(rhs.eq),
-            },
-        }
-        .simplify()
+        let eq: Equation = Equation::new(&x.pow(&PartEquation::from(2)), &PartEquation::from(4));
+        assert_eq!(eq.degree('x'), Some(2));
+        assert!(eq.is_quadratic('x'));
+        assert!(!eq.is_linear('x'));
+
+        let eq: Equation = Equation::new(&(&x * 2), &PartEquation::from(6));
+        assert_eq!(eq.degree('x'), Some(1));
+        assert!(eq.is_linear('x'));
+        assert!(!eq.is_quadratic('x'));
+        assert_eq!(eq.solve('x').unwrap(), PartEquation::from(3));
+
+        // x^2 on both sides cancels
+        let eq: Equation = Equation::new(&(&x * &x + &x), &(x.pow(&PartEquation::from(2)) + 1));
+        assert_eq!(eq.degree('x'), Some(1));
+        assert_eq!(eq.solve('x').unwrap(), PartEquation::from(1));
+        assert_eq!(Equation::new(&x.ln(), &y).degree('x'), None);
+
+        // x occurs twice, solved with the quadratic formula
+        let eq: Equation = Equation::new(&(&x * &x + &x * 2), &PartEquation::from(3));
+        assert!(eq.is_quadratic('x'));
+        assert_eq!(eq.solve('x').unwrap(), PartEquation::from(1));
     }
-}
 
-impl<'a> ops::Mul<&'a PartEquation> for &'a PartEquation {
-    type Output = PartEquation;
+    #[test]
+    fn test_solve_linear_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
 
-    fn mul(self, rhs: Self) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::MulNode {
-                lhs: Box::new(self.eq.clone()),
-                rhs: Box::new(rhs.eq.clone()),
-            },
+        let equations: Vec<Equation> = vec![
+            Equation::new(&(&x * 2 - 4), &PartEquation::from(0)),
+            Equation::new(&PartEquation::from(3), &(&x * 2)),
+            Equation::new(&(-&x + 3), &PartEquation::from(1)),
+            Equation::new(&((&x + 1) / 4), &PartEquation::from(2.5)),
+            Equation::new(&(&x * &y + 3), &y),
+        ];
+        for eq in equations.iter() {
+            assert!(eq.to_zero_form().is_linear('x'));
+            let (general, _) = eq.solve_with_steps('x').unwrap();
+            assert_eq!(eq.solve_linear('x').unwrap(), general);
+            assert_eq!(eq.solve('x').unwrap(), general);
         }
-        .simplify()
+
+        // the general solver needs a single occurrence of the variable
+        let eq: Equation = Equation::new(&((&x + 1) * 3 + &x), &PartEquation::from(5));
+        assert!(matches!(
+            eq.solve_with_steps('x'),
+            Err(MathError::NotYetImplemented)
+        ));
+        assert_eq!(eq.solve('x').unwrap(), PartEquation::from(1) / 2);
+
+        assert!(matches!(
+            Equation::new(&(&y + 1), &PartEquation::from(0)).solve_linear('x'),
+            Err(MathError::NoSolution)
+        ));
+        assert!(matches!(
+            Equation::new(&(&x - &x), &PartEquation::from(0)).solve_linear('x'),
+            Err(MathError::InfiniteSolutions)
+        ));
+        assert!(!(&x * &x).is_linear('x'));
+        assert!(matches!(
+            Equation::new(&(&x * &x), &PartEquation::from(4)).solve_linear('x'),
+            Err(MathError::EquationMismatchError)
+        ));
     }
-}
 
-impl<'a> ops::Mul<PartEquation> for &'a PartEquation {
-    type Output = PartEquation;
+    #[test]
+    fn test_equation_display_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
 
-    fn mul(self, rhs: PartEquation) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::MulNode {
-                lhs: Box::new(self.eq.clone()),
-                rhs: Box::new(rhs.eq),
-            },
-        }
-        .simplify()
+        let eq: Equation = Equation::new(&(2 * &x + 3), &PartEquation::from(1));
+        assert_eq!(eq.to_string(), "2 * x + 3 = 1");
+
+        let eq: Equation = Equation::new(&x.pow(&PartEquation::from(2)), &(&x / (&y + 1)));
+        assert_eq!(eq.to_string(), "x ^ 2 = x / (y + 1)");
+
+        let eq: Equation = Equation::new(&x, &(PartEquation::from(1) / PartEquation::from(2)));
+        assert_eq!(eq.to_string(), "x = 1/2");
+
+        // only the parentheses that precedence needs, and none around atoms
+        let eq: Equation = Equation::new(
+            &((&x + 1) * (&y - 2) - -x.pow(&PartEquation::from(2))),
+            &(PartEquation::from(1) / PartEquation::from(2) * &y),
+        );
+        assert_eq!(eq.to_string(), "x ^ 2 + (x + 1) * (y + -2) = 1/2 * y");
+        let eq: Equation = Equation::new(&x, &PartEquation::from(-4).sqrt());
+        assert_eq!(eq.to_string(), "x = 2i");
+
+        let eq: Equation = Equation::new(&(2 * &x + 1), &PartEquation::from(5));
+        assert_eq!(eq.display_solution('x').unwrap(), "x = 2");
+
+        let eq: Equation = Equation::new(&(&x * 2 + &y * 3), &PartEquation::from(5));
+        assert_eq!(eq.display_solution('x').unwrap(), "x = (5 + -3 * y) / 2");
+        assert!(matches!(
+            eq.display_solution('z'),
+            Err(MathError::EquationMismatchError)
+        ));
     }
-}
 
-impl<'a> ops::Mul<&'a PartEquation> for PartEquation {
-    type Output = PartEquation;
+    #[test]
+    fn test_minus_chain_1() {
+        let x: PartEquation = PartEquation::from('x');
 
-    fn mul(self, rhs: &'a PartEquation) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::MulNode {
-                lhs: Box::new(self.eq),
-                rhs: Box::new(rhs.eq.clone()),
-            },
+        assert_eq!(-(-(&x)), x);
+        assert_eq!((-(-(&x))).to_string(), "x");
+        assert_eq!((-(-(-(&x)))).to_string(), "-x");
+        assert_eq!((-(-(-(-(&x))))).to_string(), "x");
+        assert_eq!(-(-(-(-(-(&x))))), -(&x));
+
+        // chains built directly, without simplifying each negation
+        for depth in 1..=6 {
+            let mut eq = EquationComponentType::VariableNode('x');
+            for _ in 0..depth {
+                eq = EquationComponentType::MinusNode(Arc::new(eq));
+            }
+
+            let expected = if depth % 2 == 0 {
+                EquationComponentType::VariableNode('x')
+            } else {
+                EquationComponentType::MinusNode(Arc::new(EquationComponentType::VariableNode('x')))
+            };
+            assert_eq!(eq.simplify(), expected);
         }
-        .simplify()
     }
-}
-
-impl ops::Mul<i64> for PartEquation {
-    type Output = PartEquation;
 
-    fn mul(self, rhs: i64) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::MulNode {
-                lhs: Box::new(self.eq),
-                rhs: Box::new(EquationComponentType::ConstantNode(Number::from(rhs))),
-            },
+    #[test]
+    fn test_walk_1() {
+        #[derive(Default)]
+        struct Counter {
+            powers: usize,
+            variables: Vec<char>,
+            constants: Vec<Number>,
+            bases: Vec<String>,
         }
-        .simplify()
-    }
-}
 
-impl ops::Mul<f64> for PartEquation {
-    type Output = PartEquation;
+        impl Visitor for Counter {
+            fn visit_constant(&mut self, value: &Number) {
+                self.constants.push(value.clone());
+            }
 
-    fn mul(self, rhs: f64) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::MulNode {
-                lhs: Box::new(self.eq),
-                rhs: Box::new(EquationComponentType::ConstantNode(Number::from(rhs))),
-            },
+            fn visit_variable(&mut self, variable: char) {
+                self.variables.push(variable);
+            }
+
+            fn visit_pow(&mut self, base: Subtree, _exponent: Subtree) {
+                self.powers += 1;
+                self.bases.push(base.to_string());
+            }
         }
-        .simplify()
-    }
-}
 
-impl ops::Mul<PartEquation> for i64 {
-    type Output = PartEquation;
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
 
-    fn mul(self, rhs: PartEquation) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::MulNode {
-                lhs: Box::new(EquationComponentType::ConstantNode(Number::from(self))),
-                rhs: Box::new(rhs.eq),
-            },
-        }
-        .simplify()
-    }
-}
+        let eq: PartEquation = (&x + 1).pow(&y) * 3;
+        let mut counter: Counter = Counter::default();
+        eq.walk(&mut counter);
 
-impl ops::Mul<PartEquation> for f64 {
-    type Output = PartEquation;
+        assert_eq!(counter.powers, 1);
+        assert_eq!(counter.bases, vec!["(x + 1)"]);
+        assert_eq!(counter.variables, vec!['x', 'y']);
+        assert_eq!(counter.constants, vec![Number::from(3), Number::from(1)]);
 
-    fn mul(self, rhs: PartEquation) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::MulNode {
-                lhs: Box::new(EquationComponentType::ConstantNode(Number::from(self))),
-                rhs: Box::new(rhs.eq),
-            },
+        match eq.node() {
+            Node::Mul(lhs, rhs) => {
+                assert_eq!(lhs.to_part_equation(), PartEquation::from(3));
+                assert!(matches!(rhs.node(), Node::Pow { .. }));
+            }
+            _ => panic!("expected a product, got {}", eq),
         }
-        .simplify()
     }
-}
 
-impl<'a> ops::Mul<i64> for &'a PartEquation {
-    type Output = PartEquation;
+    #[test]
+    fn test_nested_fractions_1() {
+        let [a, b, c, d] = ['a', 'b', 'c', 'd'].map(PartEquation::from);
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
+        let z: PartEquation = PartEquation::from('z');
 
-    fn mul(self, rhs: i64) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::MulNode {
-                lhs: Box::new(self.eq.clone()),
-                rhs: Box::new(EquationComponentType::ConstantNode(Number::from(rhs))),
-            },
-        }
-        .simplify()
+        assert_eq!(&x / (&y / &z), (&x * &z) / &y);
+        assert_eq!((&a / &b) / (&c / &d), (&a * &d) / (&b * &c));
+        assert_eq!((&x / &y) / 2, &x / (&y * 2));
+
+        let eq: PartEquation = &x / (PartEquation::from(2) / PartEquation::from(3));
+        assert_eq!(eq.to_string(), "((3/2) * x)");
+        assert!(eq.numerically_equal(&(3 * &x / 2), 10, 1e-12));
+
+        let eq: PartEquation = (&x / (&y / &z)).substitute('x', &PartEquation::from(4));
+        assert_eq!(
+            eq.substitute('y', &PartEquation::from(6))
+                .substitute('z', &PartEquation::from(3))
+                .evaluate()
+                .unwrap(),
+            Number::from(2)
+        );
     }
-}
 
-impl<'a> ops::Mul<f64> for &'a PartEquation {
-    type Output = PartEquation;
+    #[test]
+    fn test_factor_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
+        let z: PartEquation = PartEquation::from('z');
 
-    fn mul(self, rhs: f64) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::MulNode {
-                lhs: Box::new(self.eq.clone()),
-                rhs: Box::new(EquationComponentType::ConstantNode(Number::from(rhs))),
-            },
-        }
-        .simplify()
-    }
-}
+        let expand = SimplifyOptions::new().distribute(true);
 
-impl<'a> ops::Mul<&'a PartEquation> for i64 {
-    type Output = PartEquation;
+        let eq: PartEquation = &x * &y + &x * &z;
+        assert_eq!(eq.factor().to_string(), "((z + y) * x)");
+        assert_eq!(eq.factor().simplify_with(&expand), eq);
 
-    fn mul(self, rhs: &PartEquation) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::MulNode {
-                lhs: Box::new(EquationComponentType::ConstantNode(Number::from(self))),
-                rhs: Box::new(rhs.eq.clone()),
-            },
-        }
-        .simplify()
-    }
-}
+        let eq: PartEquation = 2 * &x + 4;
+        assert_eq!(eq.factor().to_string(), "(2 * (x + 2))");
+        assert_eq!(eq.factor().simplify_with(&expand), eq);
 
-impl<'a> ops::Mul<&'a PartEquation> for f64 {
-    type Output = PartEquation;
+        let eq: PartEquation = 6 * &x * &y - 9 * &x;
+        assert_eq!(eq.factor(), (2 * &y - 3) * (3 * &x));
+        assert!(eq.factor().numerically_equal(&eq, 20, 1e-9));
 
-    fn mul(self, rhs: &PartEquation) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::MulNode {
-                lhs: Box::new(EquationComponentType::ConstantNode(Number::from(self))),
-                rhs: Box::new(rhs.eq.clone()),
-            },
-        }
-        .simplify()
+        let eq: PartEquation = &x + &y;
+        assert_eq!(eq.factor(), eq);
+        assert_eq!(x.factor(), x);
     }
-}
 
-impl ops::Div<PartEquation> for PartEquation {
-    type Output = PartEquation;
+    #[test]
+    fn test_common_subexpressions_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
 
-    fn div(self, rhs: Self) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::DivNode {
-                numerator: Box::new(self.eq),
-                denominator: Box::new(rhs.eq),
-            },
-        }
-        .simplify()
+        let eq: PartEquation = (&x + 1) * (&x + 1) + (&x + 1);
+        assert_eq!(eq.common_subexpressions(), vec![(&x + 1, 3)]);
+
+        let eq: PartEquation = (&x + 1).ln() * (&y - (&x + 1).ln()) + (&x + 1).ln().pow(&y);
+        let common: Vec<(PartEquation, usize)> = eq.common_subexpressions();
+        assert_eq!(common[0], (&x + 1, 3));
+        assert_eq!(common[1], ((&x + 1).ln(), 3));
+
+        assert!((&x + &y).common_subexpressions().is_empty());
     }
-}
 
-impl<'a> ops::Div<&'a PartEquation> for &'a PartEquation {
-    type Output = PartEquation;
+    #[test]
+    fn test_shared_subtrees_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
 
-    fn div(self, rhs: Self) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::DivNode {
-                numerator: Box::new(self.eq.clone()),
-                denominator: Box::new(rhs.eq.clone()),
-            },
+        let eq: PartEquation = (&x + 1).pow(&PartEquation::from(2)) / (&y - 3);
+        let copy: PartEquation = eq.clone();
+        match (&eq.eq, &copy.eq) {
+            (
+                EquationComponentType::DivNode { numerator, .. },
+                EquationComponentType::DivNode {
+                    numerator: copied, ..
+                },
+            ) => assert!(Arc::ptr_eq(numerator, copied)),
+            _ => unreachable!(),
         }
-        .simplify()
+
+        // rewriting the copy leaves the shared original as it was
+        let substituted: PartEquation = copy.substitute('y', &PartEquation::from(5));
+        assert_eq!(substituted, (&x + 1).pow(&PartEquation::from(2)) / 2);
+        assert_eq!(eq, copy);
+        assert_eq!(eq.to_string(), "(((x + 1) ^ 2) / (y + -3))");
+
+        // shared subtrees keep expressions usable across threads
+        fn send_sync<T: Send + Sync>() {}
+        send_sync::<PartEquation>();
+        send_sync::<Equation>();
+        let handle = std::thread::spawn(move || eq.substitute('x', &PartEquation::from(1)));
+        assert_eq!(handle.join().unwrap(), PartEquation::from(4) / (&y - 3));
     }
-}
 
-impl<'a> ops::Div<PartEquation> for &'a PartEquation {
-    type Output = PartEquation;
+    #[test]
+    fn test_default_1() {
+        assert_eq!(PartEquation::default(), PartEquation::from(0));
+        assert_eq!(PartEquation::default().to_string(), "0");
 
-    fn div(self, rhs: PartEquation) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::DivNode {
-                numerator: Box::new(self.eq.clone()),
-                denominator: Box::new(rhs.eq),
-            },
-        }
-        .simplify()
+        let x: PartEquation = PartEquation::from('x');
+        assert_eq!(PartEquation::default() + &x, x);
+
+        let eq: Equation = Equation::default();
+        assert_eq!(
+            eq,
+            Equation::new(&PartEquation::from(0), &PartEquation::from(0))
+        );
+        assert_eq!(eq.classify(), EquationClass::Identity);
     }
-}
 
-impl<'a> ops::Div<&'a PartEquation> for PartEquation {
-    type Output = PartEquation;
+    #[test]
+    fn test_leading_coefficient_1() {
+        let x: PartEquation = PartEquation::from('x');
+
+        let eq: PartEquation = 3 * x.pow(&PartEquation::from(2)) + 2 * &x + 7;
+        assert_eq!(eq.leading_coefficient('x'), Some(Number::from(3)));
+        assert_eq!(eq.constant_term('x'), Some(Number::from(7)));
 
-    fn div(self, rhs: &'a PartEquation) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::DivNode {
-                numerator: Box::new(self.eq),
-                denominator: Box::new(rhs.eq.clone()),
-            },
-        }
-        .simplify()
-    }
-}
+        let eq: PartEquation = (&x + 1) * (2 * &x - 3);
+        assert_eq!(eq.leading_coefficient('x'), Some(Number::from(2)));
+        assert_eq!(eq.constant_term('x'), Some(Number::from(-3)));
 
-impl ops::Div<i64> for PartEquation {
-    type Output = PartEquation;
+        let eq = PartEquation::from(0);
+        assert_eq!(eq.leading_coefficient('x'), Some(Number::from(0)));
+        assert_eq!(eq.constant_term('x'), Some(Number::from(0)));
 
-    fn div(self, rhs: i64) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::DivNode {
-                numerator: Box::new(self.eq),
-                denominator: Box::new(EquationComponentType::ConstantNode(Number::from(rhs))),
-            },
-        }
-        .simplify()
+        assert_eq!(x.ln().leading_coefficient('x'), None);
+        assert_eq!(x.sqrt().constant_term('x'), None);
     }
-}
 
-impl ops::Div<f64> for PartEquation {
-    type Output = PartEquation;
+    #[test]
+    fn test_numer_denom_degree_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
 
-    fn div(self, rhs: f64) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::DivNode {
-                numerator: Box::new(self.eq),
-                denominator: Box::new(EquationComponentType::ConstantNode(Number::from(rhs))),
-            },
-        }
-        .simplify()
+        let eq: PartEquation = (x.pow(&PartEquation::from(2)) + 1) / (&x - 1);
+        assert_eq!(eq.numer_denom_degree('x'), Some((2, 1)));
+        assert_eq!(
+            eq.as_fraction(),
+            (x.pow(&PartEquation::from(2)) + 1, &x - 1)
+        );
+
+        assert_eq!(
+            (&x + PartEquation::from(1) / &x).numer_denom_degree('x'),
+            Some((2, 1))
+        );
+        assert_eq!((&x * 3 + 2).numer_denom_degree('x'), Some((1, 0)));
+        assert_eq!((&y / &x).numer_denom_degree('y'), Some((1, 0)));
+        assert_eq!((x.ln() / &x).numer_denom_degree('x'), None);
+        assert_eq!((&x / x.sqrt()).numer_denom_degree('x'), None);
     }
-}
 
-impl ops::Div<PartEquation> for i64 {
-    type Output = PartEquation;
+    #[test]
+    fn test_equation_from_expr_1() {
+        let x: PartEquation = PartEquation::from('x');
 
-    fn div(self, rhs: PartEquation) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::DivNode {
-                numerator: Box::new(EquationComponentType::ConstantNode(Number::from(self))),
-                denominator: Box::new(rhs.eq),
-            },
-        }
-        .simplify()
+        let eq: Equation = Equation::from_expr(&(&x - 3));
+        assert_eq!(eq.solve('x').unwrap(), PartEquation::from(3));
     }
-}
 
-impl ops::Div<PartEquation> for f64 {
-    type Output = PartEquation;
+    #[test]
+    fn test_count_occurrences_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
 
-    fn div(self, rhs: PartEquation) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::DivNode {
-                numerator: Box::new(EquationComponentType::ConstantNode(Number::from(self))),
-                denominator: Box::new(rhs.eq),
-            },
-        }
-        .simplify()
+        let eq = x.log(&y) + x.pow(&PartEquation::from(2)) - (-&y) / 3;
+        assert_eq!(eq.eq.count_occurrences('x'), 2);
+        assert_eq!(eq.eq.count_occurrences('y'), 2);
+        assert_eq!(eq.eq.count_occurrences('z'), 0);
     }
-}
 
-impl<'a> ops::Div<i64> for &'a PartEquation {
-    type Output = PartEquation;
+    #[test]
+    fn test_count_occurrences_2() {
+        // a node kind defined outside of the crate, counted through its children
+        struct Hypot(PartEquation, PartEquation);
 
-    fn div(self, rhs: i64) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::DivNode {
-                numerator: Box::new(self.eq.clone()),
-                denominator: Box::new(EquationComponentType::ConstantNode(Number::from(rhs))),
-            },
+        impl CountOccurrences for Hypot {
+            fn count_occurrences(&self, variable: char) -> i64 {
+                self.0.count_occurrences(variable) + self.1.count_occurrences(variable)
+            }
         }
-        .simplify()
+
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
+
+        let node: Hypot = Hypot(x.abs() + &y, x.floordiv(&y) - x.rem(&PartEquation::from(3)));
+        assert_eq!(node.count_occurrences('x'), 3);
+        assert_eq!(node.count_occurrences('y'), 2);
+        assert_eq!(node.count_occurrences('z'), 0);
+
+        let eq: Equation = Equation::new(&x.exp(), &(&y * 2));
+        assert_eq!(eq.count_occurrences('x'), 1);
+        assert_eq!(eq.count_occurrences('y'), 1);
     }
-}
 
-impl<'a> ops::Div<f64> for &'a PartEquation {
-    type Output = PartEquation;
+    #[test]
+    fn test_solving_log_equation_1() {
+        // log_2(x) = 3
+        let x: PartEquation = PartEquation::from('x');
+        let eq: Equation = Equation::new(&x.log(&PartEquation::from(2)), &PartEquation::from(3));
 
-    fn div(self, rhs: f64) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::DivNode {
-                numerator: Box::new(self.eq.clone()),
-                denominator: Box::new(EquationComponentType::ConstantNode(Number::from(rhs))),
-            },
-        }
-        .simplify()
+        let result = eq.solve('x').unwrap();
+        assert_eq!(result.evaluate().unwrap(), Number::from(8));
     }
-}
 
-impl<'a> ops::Div<&'a PartEquation> for i64 {
-    type Output = PartEquation;
+    #[test]
+    fn test_solving_log_equation_2() {
+        // log_x(8) = 3
+        let x: PartEquation = PartEquation::from('x');
+        let eq: Equation = Equation::new(&PartEquation::from(8).log(&x), &PartEquation::from(3));
 
-    fn div(self, rhs: &PartEquation) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::DivNode {
-                numerator: Box::new(EquationComponentType::ConstantNode(Number::from(self))),
-                denominator: Box::new(rhs.eq.clone()),
-            },
-        }
-        .simplify()
+        let result = eq.solve('x').unwrap().evaluate().unwrap();
+        assert!(result > Number::from(1.999999) && result < Number::from(2.000001));
     }
-}
 
-impl<'a> ops::Div<&'a PartEquation> for f64 {
-    type Output = PartEquation;
+    #[test]
+    fn test_solving_minus_base_1() {
+        // (-x)^3 = 8
+        let x: PartEquation = PartEquation::from('x');
+        let eq: Equation =
+            Equation::new(&(-&x).pow(&PartEquation::from(3)), &PartEquation::from(8));
 
-    fn div(self, rhs: &PartEquation) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::DivNode {
-                numerator: Box::new(EquationComponentType::ConstantNode(Number::from(self))),
-                denominator: Box::new(rhs.eq.clone()),
-            },
-        }
-        .simplify()
+        let result = eq.solve('x').unwrap().evaluate().unwrap();
+        assert!(result > Number::from(-2.000001) && result < Number::from(-1.999999));
     }
-}
 
-impl ops::Neg for PartEquation {
-    type Output = PartEquation;
+    #[test]
+    fn test_simplify_idempotent_1() {
+        use crate::random::XorShift;
 
-    fn neg(self) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::MinusNode(Box::new(self.eq)),
+        let fixpoint: SimplifyOptions = SimplifyOptions::new().distribute(true).fixpoint(true);
+        let mut rng: XorShift = XorShift::new(3);
+        for _ in 0..2000 {
+            let eq: PartEquation = PartEquation::random(&['x', 'y', 'z'], 4, &mut rng);
+
+            let once: PartEquation = eq.simplify();
+            assert_eq!(once.simplify().eq, once.eq, "{}", eq);
+
+            let once: PartEquation = eq.simplify_with(&fixpoint);
+            assert_eq!(once.simplify_with(&fixpoint).eq, once.eq, "{}", eq);
         }
-        .simplify()
     }
-}
 
-impl<'a> ops::Neg for &'a PartEquation {
-    type Output = PartEquation;
+    #[test]
+    fn test_random_1() {
+        use crate::random::XorShift;
 
-    fn neg(self) -> Self::Output {
-        PartEquation {
-            eq: EquationComponentType::MinusNode(Box::new(self.eq.clone())),
+        let mut rng: XorShift = XorShift::new(7);
+        for _ in 0..200 {
+            let eq: PartEquation = PartEquation::random(&['x', 'y'], 4, &mut rng);
+            eq.simplify();
         }
-        .simplify()
-    }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let eq: PartEquation = PartEquation::random(&[], 3, &mut rng);
+        assert!(eq.free_of('x'));
+        assert_eq!(
+            PartEquation::random(&['x'], 5, &mut XorShift::new(1)).to_string(),
+            PartEquation::random(&['x'], 5, &mut XorShift::new(1)).to_string()
+        );
+    }
 
     #[test]
-    fn test_solving_equation_1() {
+    fn test_to_infix_1() {
         let x: PartEquation = PartEquation::from('x');
-        let eq: Equation = Equation::new(&x, &PartEquation::from(12));
+        let y: PartEquation = PartEquation::from('y');
 
-        if let EquationComponentType::ConstantNode(i) = eq.solve('x').unwrap().eq {
-            assert_eq!(i, Number::from(12));
-        } else {
-            assert!(false);
-        }
+        assert_eq!((x.pow(&PartEquation::from(2)) + 1).to_infix(), "x**2 + 1");
+        assert_eq!(((&x + 1) * 3).to_infix(), "3*(x + 1)");
+        assert_eq!(
+            (&x + &y).pow(&(PartEquation::from(1) / 3)).to_infix(),
+            "(y + x)**(1/3)"
+        );
+        assert_eq!((&x / (&y * 2)).to_infix(), "x/(2*y)");
+        assert_eq!((-x.pow(&y)).to_infix(), "-x**y");
+        assert_eq!(x.pow(&-&y).to_infix(), "x**-y");
+        assert_eq!((PartEquation::from(-2).pow(&x)).to_infix(), "(-2)**x");
+        assert_eq!(x.ln().to_infix(), "log(x)");
+        assert_eq!(x.log(&PartEquation::from(2)).to_infix(), "log(x, 2)");
+        assert_eq!((x.abs() + y.exp()).to_infix(), "exp(y) + Abs(x)");
+        assert_eq!((&x * PartEquation::pi()).to_infix(), "pi*x");
     }
 
     #[test]
-    fn test_solving_equation_2() {
+    fn test_to_pretty_1() {
         let x: PartEquation = PartEquation::from('x');
-        let eq: Equation = Equation::new(&PartEquation::from(3.14), &x);
 
-        if let EquationComponentType::ConstantNode(i) = eq.solve('x').unwrap().eq {
-            assert_eq!(i, Number::from(3.14));
-        } else {
-            assert!(false);
+        let mut eq: PartEquation = PartEquation::from(0);
+        for i in 1..=9 {
+            eq = eq + x.pow(&PartEquation::from(i)) * i;
         }
+        eq = eq - 7;
+
+        let pretty: String = eq.to_pretty(40);
+        let lines: Vec<&str> = pretty.lines().collect();
+        assert!(eq.to_string().len() > 40);
+        assert_eq!(
+            lines,
+            vec![
+                "  (9 * (x ^ 9)) + (8 * (x ^ 8))",
+                "+ (7 * (x ^ 7)) + (6 * (x ^ 6))",
+                "+ (5 * (x ^ 5)) + (4 * (x ^ 4))",
+                "+ (3 * (x ^ 3)) + (2 * (x ^ 2)) + x - 7",
+            ]
+        );
+        // every line is full: the first term of the next one would not have fit
+        for pair in lines.windows(2) {
+            let next: &str = pair[1][2..].split(" + ").next().unwrap();
+            assert!(pair[0].len() <= 40 && pair[0].len() + 3 + next.len() > 40);
+        }
+        assert_eq!(
+            pretty.replace('\n', " ").parse::<PartEquation>().unwrap(),
+            eq
+        );
+
+        assert_eq!((&x + 1).to_pretty(40), (&x + 1).to_string());
+        let product: PartEquation = (&x + 1) * (&x + 2) * (&x + 3) * (&x + 4);
+        assert_eq!(product.to_pretty(10), product.to_string());
+        let eq: PartEquation = -&x - x.pow(&PartEquation::from(2)) * 2 + 1;
+        assert_eq!(eq.to_pretty(5).lines().count(), 3);
+        assert!(eq.to_pretty(5).lines().any(|l| l == "- (2 * (x ^ 2))"));
     }
 
     #[test]
-    fn test_solving_equation_3() {
+    fn test_coefficient_first_1() {
         let x: PartEquation = PartEquation::from('x');
-        let eq: Equation = Equation::new(&PartEquation::from(3), &(x * 2));
+        let y: PartEquation = PartEquation::from('y');
 
-        if let EquationComponentType::ConstantNode(i) = eq.solve('x').unwrap().eq {
-            assert_eq!(i, Number::from(1.5));
-        } else {
-            assert!(false);
+        let forms: [PartEquation; 4] = [&x * 3, 3 * &x, (&x * 3).simplify(), &x + &x * 2];
+        for eq in forms.iter() {
+            assert_eq!(eq.to_string(), "(3 * x)");
         }
+
+        let xy: PartEquation = &x * &y;
+        let eq: PartEquation = &y * 3 * &x;
+        assert_eq!(eq.to_string(), (&xy * 3).to_string());
+        assert!(eq.to_string().starts_with("(3 * "));
+        assert!((&xy * 2.5).to_string().starts_with("(2.5"));
     }
 
     #[test]
-    fn test_solving_equation_4() {
+    fn test_minus_product_1() {
         let x: PartEquation = PartEquation::from('x');
-        let eq: Equation = Equation::new(&PartEquation::from(3), &(x + 2));
+        let y: PartEquation = PartEquation::from('y');
 
-        if let EquationComponentType::ConstantNode(i) = eq.solve('x').unwrap().eq {
-            assert_eq!(i, Number::from(1));
-        } else {
-            assert!(false);
-        }
+        let eq = -(&x * 3);
+        assert_eq!(eq, -3 * &x);
+        assert_eq!(eq.to_string(), "(-3 * x)");
+
+        let eq = -(&x * &y);
+        assert_eq!(eq, -1 * (&x * &y));
+        assert!(eq
+            .eq
+            .contains(&EquationComponentType::ConstantNode(Number::from(-1))));
+
+        let eq = -(-(&x * 3));
+        assert_eq!(eq, 3 * &x);
+
+        // the coefficient is found anywhere in a nested product
+        let eq = -(&x * (&y * 3));
+        assert_eq!(eq, -3 * (&x * &y));
+        assert!(eq
+            .eq
+            .contains(&EquationComponentType::ConstantNode(Number::from(-3))));
     }
 
     #[test]
-    fn test_solving_equation_5() {
+    fn test_div_minus_denominator_1() {
         let x: PartEquation = PartEquation::from('x');
-        let eq: Equation = Equation::new(&PartEquation::from(3), &(x / 2));
+        let y: PartEquation = PartEquation::from('y');
 
-        if let EquationComponentType::ConstantNode(i) = eq.solve('x').unwrap().eq {
-            assert_eq!(i, Number::from(6));
-        } else {
-            assert!(false);
-        }
+        assert_eq!(&x / -&y, -&x / &y);
+        assert_eq!(-&x / &y, -(&x / &y));
+        assert_eq!(&x / (&y * -3), -&x / (&y * 3));
+        assert_eq!(&x / -2, -&x / 2);
+        assert_eq!(-&x / -&y, &x / &y);
+        assert_eq!((&x / -&y).to_string(), (-&x / &y).to_string());
+
+        let eq: PartEquation = &x / -&y;
+        assert!(!eq.eq.contains(&(-&y).eq));
+        assert_eq!(
+            eq.substitute('x', &PartEquation::from(6))
+                .substitute('y', &PartEquation::from(3))
+                .evaluate()
+                .unwrap(),
+            Number::from(-2)
+        );
     }
 
     #[test]
-    fn test_solving_equation_6() {
+    fn test_div_self_1() {
         let x: PartEquation = PartEquation::from('x');
-        let eq: Equation = Equation::new(&PartEquation::from(9), &(&x.pow(&PartEquation::from(2))));
+        let y: PartEquation = PartEquation::from('y');
 
-        if let EquationComponentType::ConstantNode(i) = eq.solve('x').unwrap().eq {
-            assert_eq!(i, Number::from(3));
-        } else {
-            assert!(false);
-        }
+        assert_eq!(&x / &x, PartEquation::from(1));
+        assert_eq!(PartEquation::from(0) / &x, PartEquation::from(0));
+        assert_eq!(x.ln() / x.ln(), PartEquation::from(1));
+        assert_eq!((&x + &y) / (&y + &x), PartEquation::from(1));
+        assert_eq!((PartEquation::from(0) / (&x + &y)) + &x, x.clone());
     }
 
     #[test]
-    fn test_solving_equation_7() {
-        // TODO: evaluate log
+    fn test_order_many_terms_1() {
+        // 0, 7, 14, ... mod 500 visits every coefficient once, in a scrambled order
+        let terms: Vec<EquationComponentType> = (0..500)
+            .map(|i| EquationComponentType::MulNode {
+                lhs: Arc::new(EquationComponentType::ConstantNode(Number::from(
+                    (i * 7) % 500 + 1,
+                ))),
+                rhs: Arc::new(EquationComponentType::VariableNode('x')),
+            })
+            .collect();
+
+        let ordered: EquationComponentType =
+            EquationComponentType::construct_from_terms(terms).order();
+        let mut result: Vec<EquationComponentType> = Vec::new();
+        ordered.separate_terms(&mut result);
+
+        // largest coefficient first: (500 * x) + (499 * x) + ... + (1 * x)
+        let expected: Vec<EquationComponentType> = (1..=500)
+            .rev()
+            .map(|i| EquationComponentType::MulNode {
+                lhs: Arc::new(EquationComponentType::ConstantNode(Number::from(i))),
+                rhs: Arc::new(EquationComponentType::VariableNode('x')),
+            })
+            .collect();
+        assert!(result == expected);
+        assert_eq!(ordered.order(), ordered);
+    }
+
+    #[test]
+    fn test_hash_key_1() {
         let x: PartEquation = PartEquation::from('x');
-        let eq: Equation = Equation::new(&PartEquation::from(8), &(&PartEquation::from(2).pow(&x)));
+        let y: PartEquation = PartEquation::from('y');
 
-        if let EquationComponentType::LogNode { base, argument } = eq.solve('x').unwrap().eq {
-            if let EquationComponentType::ConstantNode(i) = *base {
-                assert_eq!(i, Number::from(2));
-            } else {
-                assert!(false);
-            }
+        assert_eq!((&x + &y).hash_key(), (&y + &x).hash_key());
+        assert_eq!((&x * 2 + &x).hash_key(), (&x * 3).hash_key());
+        assert_ne!((&x + 1).hash_key(), (&x + 2).hash_key());
 
-            if let EquationComponentType::ConstantNode(i) = *argument {
-                assert_eq!(i, Number::from(8));
-            } else {
-                assert!(false);
-            }
-        } else {
-            assert!(false);
-        }
+        let mut seen: HashMap<PartEquation, i32> = HashMap::new();
+        seen.insert(&x + &y, 1);
+        assert_eq!(seen.get(&(&y + &x)), Some(&1));
     }
 
+    #[cfg(feature = "cache")]
     #[test]
-    fn test_solving_equation_8() {
+    fn test_simplify_cache_1() {
         let x: PartEquation = PartEquation::from('x');
-        let eq: Equation = Equation::new(&(-x), &PartEquation::from(1));
+        let eq: EquationComponentType = (&x + 1).pow(&PartEquation::from(2)).eq;
+        let hits = || SIMPLIFY_CACHE_HITS.with(|hits| hits.get());
+
+        PartEquation::clear_simplify_cache();
+        let first: EquationComponentType = eq.simplify();
+        let before: usize = hits();
+        assert_eq!(eq.simplify(), first);
+        assert_eq!(hits(), before + 1);
+
+        // 5/2 and 2.5 are equal but must not share an entry
+        let exact: EquationComponentType = (&x + Number::from(5) / Number::from(2)).eq;
+        let float: EquationComponentType = (&x + 2.5).eq;
+        assert!(exact.simplify().to_string().contains("5/2"));
+        assert!(float.simplify().to_string().contains("2.5"));
+
+        PartEquation::clear_simplify_cache();
+        let before: usize = hits();
+        eq.simplify();
+        assert_eq!(hits(), before);
+    }
 
-        if let EquationComponentType::ConstantNode(i) = eq.solve('x').unwrap().eq {
-            assert_eq!(i, Number::from(-1));
-        } else {
-            assert!(false);
-        }
+    #[test]
+    fn test_from_monomials_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
+
+        let eq = PartEquation::from_monomials(&[
+            (Number::from(3), vec![('x', 2), ('y', 1)]),
+            (Number::from(5), vec![]),
+        ]);
+        assert_eq!(eq, 3 * x.pow(&PartEquation::from(2)) * &y + 5);
+
+        assert_eq!(PartEquation::from_monomials(&[]), PartEquation::from(0));
     }
 
     #[test]
-    fn test_solving_equation_9() {
+    fn test_combine_powers_1() {
         let x: PartEquation = PartEquation::from('x');
-        let eq: Equation = Equation::new(&(&x + 5), &(2 * &x));
+        let y: PartEquation = PartEquation::from('y');
+        let a: PartEquation = PartEquation::from('a');
+        let m: PartEquation = PartEquation::from('m');
+        let n: PartEquation = PartEquation::from('n');
+        let two: PartEquation = PartEquation::from(2);
+
+        assert_eq!(two.pow(&x) * two.pow(&y), two.pow(&(&x + &y)));
+        assert_eq!(a.pow(&m) * a.pow(&n), a.pow(&(&m + &n)));
+        assert_eq!(a.pow(&m) * &a * a.pow(&n), a.pow(&(&m + &n + 1)));
+        assert_eq!(
+            (&x + 1).pow(&m) * (&x + 1).pow(&n),
+            (&x + 1).pow(&(&m + &n))
+        );
+        assert_eq!(two.pow(&x) * two.pow(&-&x), PartEquation::from(1));
+        assert_eq!(
+            (a.pow(&m) * 3 * a.pow(&-&m)).simplify(),
+            PartEquation::from(3)
+        );
+
+        // only equal bases combine
+        let eq: PartEquation = two.pow(&x) * PartEquation::from(3).pow(&x);
+        assert!(matches!(
+            eq.simplify().eq,
+            EquationComponentType::MulNode { .. }
+        ));
+    }
 
-        if let EquationComponentType::ConstantNode(i) = eq.solve('x').unwrap().eq {
-            assert_eq!(i, Number::from(5));
-        } else {
-            assert!(false);
-        }
+    #[test]
+    fn test_cancel_powers_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
+        let a: PartEquation = PartEquation::from('a');
+        let b: PartEquation = PartEquation::from('b');
+        let m: PartEquation = PartEquation::from('m');
+        let n: PartEquation = PartEquation::from('n');
+        let two: PartEquation = PartEquation::from(2);
+        let power = |base: &PartEquation, exponent: i32| base.pow(&PartEquation::from(exponent));
+
+        assert_eq!(power(&x, 5) / power(&x, 2), power(&x, 3));
+        assert_eq!(
+            power(&a, 2) / power(&a, 5),
+            PartEquation::from(1) / power(&a, 3)
+        );
+        assert_eq!(power(&two, 5) / power(&two, 3), PartEquation::from(4));
+
+        assert_eq!(a.pow(&m) / a.pow(&n), a.pow(&(&m - &n)));
+        assert_eq!(two.pow(&x) / two.pow(&y), two.pow(&(&x - &y)));
+        assert_eq!(a.pow(&m) * &b / a.pow(&m), b.clone());
+        assert_eq!(
+            power(&(&x + &y), 2) / power(&(&x + &y), 5),
+            PartEquation::from(1) / power(&(&x + &y), 3)
+        );
+        assert_eq!(&b * power(&a, 2) / (power(&a, 3) * &x), &b / (&a * &x));
     }
 
     #[test]
-    fn test_solving_equation_10() {
+    fn test_monomials_1() {
         let x: PartEquation = PartEquation::from('x');
-        let eq: Equation = Equation::new(&(-&x + 5), &(2 * &x));
+        let y: PartEquation = PartEquation::from('y');
 
-        if let EquationComponentType::ConstantNode(i) = eq.solve('x').unwrap().eq {
-            assert_eq!(i, Number::from(5) / Number::from(3));
-        } else {
-            assert!(false);
-        }
+        let expected: BTreeMap<Vec<(char, i64)>, Number> = BTreeMap::from([
+            (vec![('x', 1)], Number::from(1)),
+            (vec![('x', 1), ('y', 1)], Number::from(1)),
+        ]);
+        assert_eq!((&x * (&y + 1)).monomials().unwrap(), expected);
+        assert_eq!((&x * &y + &x).monomials().unwrap(), expected);
+        assert_eq!((&y * &x + &x).monomials().unwrap(), expected);
+
+        let eq: PartEquation =
+            (&x + &y).pow(&PartEquation::from(2)) - (&x - &y).pow(&PartEquation::from(2));
+        assert_eq!(
+            eq.monomials().unwrap(),
+            BTreeMap::from([(vec![('x', 1), ('y', 1)], Number::from(4))])
+        );
+        assert!((&x - &x).monomials().unwrap().is_empty());
+
+        let eq: PartEquation = (3 * &x * &y + 6 * &x) / (3 * &x);
+        assert_eq!(eq.monomials().unwrap(), (&y + 2).monomials().unwrap());
+        assert_eq!(
+            (PartEquation::from(1) / x.pow(&PartEquation::from(2)))
+                .monomials()
+                .unwrap(),
+            BTreeMap::from([(vec![('x', -2)], Number::from(1))])
+        );
+
+        let terms: Vec<(Number, Vec<(char, i64)>)> = eq
+            .monomials()
+            .unwrap()
+            .into_iter()
+            .map(|(powers, coefficient)| (coefficient, powers))
+            .collect();
+        assert_eq!(PartEquation::from_monomials(&terms), &y + 2);
+
+        assert!(x.abs().monomials().is_none());
+        assert!((PartEquation::from(1) / (&x + 1)).monomials().is_none());
+        assert!(x.sqrt().monomials().is_none());
     }
 
     #[test]
-    fn test_equality_for_part_equation_1() {
+    fn test_substitute_equation_1() {
         let x: PartEquation = PartEquation::from('x');
         let y: PartEquation = PartEquation::from('y');
-        let z: PartEquation = PartEquation::from('z');
 
-        let eq1 = &x + &y + &z;
+        let eq: Equation = Equation::new(&(&x + &y), &PartEquation::from(5));
+        let eq: Equation = eq.substitute('y', &PartEquation::from(2));
 
-        assert_eq!(eq1, &x + &z + &y);
-        assert_eq!(eq1, &y + &x + &z);
-        assert_eq!(eq1, &y + &z + &x);
-        assert_eq!(eq1, &z + &y + &x);
-        assert_eq!(eq1, &z + &x + &y);
+        assert_eq!(eq.solve('x').unwrap(), PartEquation::from(3));
     }
 
     #[test]
-    fn test_equality_for_part_equation_2() {
+    fn test_zero_form_1() {
+        let x: PartEquation = PartEquation::from('x');
+
+        let eq: Equation = Equation::new(&(&x + 1), &(2 * &x));
+        assert_eq!(eq.to_zero_form(), 1 - &x);
+        assert_eq!(eq.to_zero_form().to_string(), "(1 + (-1 * x))");
+    }
+
+    #[test]
+    fn test_simplify_with_assumptions_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let eq: PartEquation = x.pow(&PartEquation::from(2)).sqrt();
+
+        assert_eq!(eq.to_string(), "|x|");
+        assert_eq!(
+            eq.simplify_with_assumptions(&Assumptions::new())
+                .to_string(),
+            "|x|"
+        );
+        assert_eq!(
+            eq.simplify_with_assumptions(&Assumptions::new().positive('x')),
+            x
+        );
+        assert_eq!(
+            eq.simplify_with_assumptions(&Assumptions::new().negative('x')),
+            -&x
+        );
+        assert_eq!(
+            eq.simplify_with_assumptions(&Assumptions::new().positive('y'))
+                .to_string(),
+            "|x|"
+        );
+    }
+
+    #[test]
+    fn test_simplify_with_assumptions_2() {
         let x: PartEquation = PartEquation::from('x');
         let y: PartEquation = PartEquation::from('y');
-        let z: PartEquation = PartEquation::from('z');
+        // e ^ ln(x) -> x needs no assumption
+        let eq: PartEquation = PartEquation::e().pow(&x.ln());
+        assert_eq!(eq, x);
+        assert_eq!(
+            eq.simplify_with_assumptions(&Assumptions::new().nonnegative('x')),
+            x
+        );
+
+        // the sign of a product follows from the signs of its factors
+        let eq: PartEquation = (&x * &y).pow(&PartEquation::from(2)).sqrt();
+        let assumptions = Assumptions::new().positive('x').negative('y');
+        assert_eq!(eq.simplify_with_assumptions(&assumptions), -(&x * &y));
+    }
 
-        let eq1 = &x * &y * &z;
+    #[test]
+    fn test_equation_eq_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
 
-        assert_eq!(eq1, &x * &z * &y);
-        assert_eq!(eq1, &y * &x * &z);
-        assert_eq!(eq1, &y * &z * &x);
-        assert_eq!(eq1, &z * &y * &x);
-        assert_eq!(eq1, &z * &x * &y);
+        let eq: Equation = Equation::new(&x, &PartEquation::from(5));
+        assert_eq!(eq, Equation::new(&(2 * &x), &PartEquation::from(10)));
+        assert_eq!(eq, Equation::new(&(&x - 5), &PartEquation::from(0)));
+        assert_eq!(eq, Equation::new(&PartEquation::from(5), &x));
+        assert_ne!(eq, Equation::new(&x, &PartEquation::from(6)));
+        assert_ne!(eq, Equation::new(&(2 * &x), &PartEquation::from(5)));
+
+        let eq: Equation = Equation::new(&(&x + &y), &PartEquation::from(1));
+        assert_eq!(eq, Equation::new(&(3 - 3 * &x), &(3 * &y)));
+        assert_ne!(eq, Equation::new(&(&x - &y), &PartEquation::from(1)));
+
+        let identity: Equation = Equation::new(&x, &x);
+        assert_eq!(
+            identity,
+            Equation::new(&PartEquation::from(0), &PartEquation::from(0))
+        );
+        assert_ne!(identity, eq);
     }
 
     #[test]
-    fn test_equality_for_part_equation_3() {
+    fn test_compile_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let eq: PartEquation = x.pow(&PartEquation::from(2)) + 1;
+        let f = eq.compile('x');
+
+        for i in 0..1000 {
+            let point: f64 = (i as f64 - 500.0) / 7.0;
+            let expected: f64 = eq
+                .substitute('x', &PartEquation::from(point))
+                .evaluate()
+                .unwrap()
+                .to_f64();
+            assert!((f(point).unwrap() - expected).abs() <= 1e-9 * expected.abs());
+        }
+
+        let points: Vec<f64> = vec![-1.0, 0.0, 2.5];
+        assert_eq!(eq.subs('x', &points).unwrap(), vec![2.0, 1.0, 7.25]);
+    }
+
+    #[test]
+    fn test_compile_2() {
         let x: PartEquation = PartEquation::from('x');
         let y: PartEquation = PartEquation::from('y');
-        let z: PartEquation = PartEquation::from('z');
 
-        let eq1 = &x * (&y + &z);
+        let f = (PartEquation::from(1) / &x).compile('x');
+        assert!(matches!(f(0.0), Err(MathError::ZeroDivisionError)));
+        assert_eq!(f(4.0).unwrap(), 0.25);
 
-        assert_eq!(eq1, &x * (&z + &y));
-        assert_eq!(eq1, (&y + &z) * (&x));
-        assert_eq!(eq1, (&z + &y) * (&x));
+        let f = x.ln().compile('x');
+        assert!(matches!(f(-1.0), Err(MathError::DomainError)));
+
+        let f = x
+            .pow(&(PartEquation::from(1) / PartEquation::from(3)))
+            .compile('x');
+        assert!((f(-8.0).unwrap() + 2.0).abs() < 1e-12);
+
+        let f = (&x + &y).compile('x');
+        assert!(matches!(f(1.0), Err(MathError::EquationMismatchError)));
     }
 }
@@ -0,0 +1,200 @@
+//! Stack machine for evaluating an expression many times at `f64` precision.
+//! `PartEquation::to_program` lowers the expression tree once, `Program::run` then only does arithmetic.
+
+use crate::math::MathError;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OpCode {
+    Push(f64),
+    Load(char),
+    Add,
+    Sub,
+    Mul,
+    Div,
+    FloorDiv,
+    Mod,
+    Pow,
+    /// Power with a rational exponent of odd denominator, defined for negative bases too.
+    /// The result for a negative base is negated if the numerator of the exponent is odd
+    OddRootPow(bool),
+    Log,
+    Neg,
+    Abs,
+    Exp,
+}
+
+/// Postfix sequence of operations, each consuming its operands from the stack
+#[derive(Debug, Clone, PartialEq)]
+pub struct Program {
+    code: Vec<OpCode>,
+}
+
+impl Program {
+    pub(crate) fn new(code: Vec<OpCode>) -> Self {
+        Program { code }
+    }
+
+    pub fn code(&self) -> &[OpCode] {
+        &self.code
+    }
+
+    /// Runs the program with the given variable values, a variable without a value fails with
+    /// `EquationMismatchError`. Results are real: where `PartEquation::evaluate` gives a complex
+    /// number, like `2i` for the square root of -4, this fails with `DomainError`
+    pub fn run(&self, variables: &[(char, f64)]) -> Result<f64, MathError> {
+        let mut stack: Vec<f64> = Vec::with_capacity(self.code.len());
+
+        for op in self.code.iter() {
+            let value: f64 = match *op {
+                OpCode::Push(value) => value,
+                OpCode::Load(variable) => match variables.iter().find(|(v, _)| *v == variable) {
+                    Some((_, value)) => *value,
+                    None => return Err(MathError::EquationMismatchError),
+                },
+                OpCode::Neg => -pop(&mut stack)?,
+                OpCode::Abs => pop(&mut stack)?.abs(),
+                OpCode::Exp => pop(&mut stack)?.exp(),
+                _ => {
+                    let rhs: f64 = pop(&mut stack)?;
+                    let lhs: f64 = pop(&mut stack)?;
+                    binary(op, lhs, rhs)?
+                }
+            };
+            stack.push(value);
+        }
+
+        let result: f64 = pop(&mut stack)?;
+        if !stack.is_empty() {
+            return Err(MathError::InternalError);
+        }
+        Ok(result)
+    }
+}
+
+fn pop(stack: &mut Vec<f64>) -> Result<f64, MathError> {
+    stack.pop().ok_or(MathError::InternalError)
+}
+
+fn binary(op: &OpCode, lhs: f64, rhs: f64) -> Result<f64, MathError> {
+    match *op {
+        OpCode::Add => Ok(lhs + rhs),
+        OpCode::Sub => Ok(lhs - rhs),
+        OpCode::Mul => Ok(lhs * rhs),
+        OpCode::Div => {
+            if rhs == 0.0 {
+                return Err(MathError::ZeroDivisionError);
+            }
+            Ok(lhs / rhs)
+        }
+        OpCode::FloorDiv => {
+            if rhs == 0.0 {
+                return Err(MathError::ZeroDivisionError);
+            }
+            Ok((lhs / rhs).floor())
+        }
+        OpCode::Mod => {
+            if rhs == 0.0 {
+                return Err(MathError::ZeroDivisionError);
+            }
+            Ok(lhs - rhs * (lhs / rhs).floor())
+        }
+        OpCode::Pow => {
+            if lhs < 0.0 && rhs.fract() != 0.0 {
+                return Err(MathError::DomainError);
+            }
+            Ok(lhs.powf(rhs))
+        }
+        OpCode::OddRootPow(negate) => {
+            if lhs >= 0.0 {
+                return Ok(lhs.powf(rhs));
+            }
+            let result: f64 = (-lhs).powf(rhs);
+            if negate {
+                return Ok(-result);
+            }
+            Ok(result)
+        }
+        // base below argument on the stack
+        OpCode::Log => {
+            if rhs <= 0.0 || lhs <= 0.0 || lhs == 1.0 {
+                return Err(MathError::DomainError);
+            }
+            Ok(rhs.ln() / lhs.ln())
+        }
+        _ => Err(MathError::InternalError),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::equation::PartEquation;
+
+    fn assert_matches_evaluate(eq: &PartEquation, x: f64, y: f64) {
+        let expected: f64 = eq
+            .substitute('x', &PartEquation::from(x))
+            .substitute('y', &PartEquation::from(y))
+            .evaluate()
+            .unwrap()
+            .to_f64();
+        let result: f64 = eq.to_program().run(&[('x', x), ('y', y)]).unwrap();
+
+        assert!(
+            (result - expected).abs() <= 1e-9 * expected.abs().max(1.0),
+            "{} at x = {}, y = {}: {} != {}",
+            eq,
+            x,
+            y,
+            result,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_run_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
+
+        let expressions: Vec<PartEquation> = vec![
+            x.pow(&PartEquation::from(2)) + 1,
+            3 * &x - &y / 2,
+            (&x + &y).pow(&PartEquation::from(3)) / (&y + 10),
+            x.pow(&(PartEquation::from(1) / PartEquation::from(3))) * &y,
+            (&x * &x + 1).log(&PartEquation::from(2)) - (&y * &y + 1).ln(),
+            -(&x - &y),
+        ];
+
+        for eq in expressions.iter() {
+            for (x, y) in [(1.5, 2.0), (-3.0, 0.25), (0.0, 7.0), (10.0, -4.0)] {
+                assert_matches_evaluate(eq, x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn test_run_errors_1() {
+        let x: PartEquation = PartEquation::from('x');
+
+        let program: Program = (PartEquation::from(1) / &x).to_program();
+        assert!(matches!(
+            program.run(&[('x', 0.0)]),
+            Err(MathError::ZeroDivisionError)
+        ));
+        assert!(matches!(
+            program.run(&[]),
+            Err(MathError::EquationMismatchError)
+        ));
+
+        let program: Program = x.sqrt().to_program();
+        assert!(matches!(
+            program.run(&[('x', -4.0)]),
+            Err(MathError::DomainError)
+        ));
+        assert_eq!(program.run(&[('x', 4.0)]).unwrap(), 2.0);
+
+        assert!(matches!(
+            Program::new(vec![OpCode::Add]).run(&[]),
+            Err(MathError::InternalError)
+        ));
+    }
+}
@@ -1,7 +1,8 @@
 use super::{lexer::Token, parser::Nodes};
-use math_engine::math::MathError;
+use crate::math::MathError;
 use std::fmt;
 use std::fmt::Display;
+use std::ops::Range;
 
 #[derive(Debug, Clone)]
 pub enum Error {
@@ -13,12 +14,20 @@ pub enum Error {
     ParserError {
         token: Token,
         message: &'static str,
+        // byte range of the token in the statement
+        span: Range<usize>,
     },
     EvalError {
         node: Nodes,
         message: &'static str,
+        // byte range of the node in the statement, empty for a node built by hand
+        span: Range<usize>,
     },
     MathError(MathError),
+    StatementError {
+        statement: usize,
+        error: Box<Error>,
+    },
 }
 
 impl From<MathError> for Error {
@@ -43,18 +52,33 @@ impl Display for Error {
                     message
                 )
             }
-            Error::ParserError { token, message } => {
-                write!(f, "\n Parser Error: {},\n  at token {:?}\n", message, token)
+            Error::ParserError {
+                token,
+                message,
+                span,
+            } => {
+                write!(
+                    f,
+                    "\n Parser Error: {},\n  at token {:?} ({}..{})\n",
+                    message, token, span.start, span.end
+                )
             }
-            Error::EvalError { node, message } => {
+            Error::EvalError {
+                node,
+                message,
+                span,
+            } => {
                 write!(
                     f,
-                    "\n Interpreter Error: {},\n  at node {}\n",
-                    message, node
+                    "\n Interpreter Error: {},\n  at node {} ({}..{})\n",
+                    message, node, span.start, span.end
                 )
             }
             Error::MathError(e) => {
-                write!(f, "\n Math Error: {:?}\n", e)
+                write!(f, "\n Math Error: {}\n", e)
+            }
+            Error::StatementError { statement, error } => {
+                write!(f, "\n Error in statement {}:{}", statement, error)
             }
         }
     }
@@ -1,8 +1,17 @@
 use super::error::Error;
-use super::parser::{Nodes, Parser};
-use math_engine::equation::{Equation, PartEquation};
+use super::lexer::{Constant, Token};
+use super::parser::{Nodes, Parser, Spanned};
+use crate::equation::{Equation, PartEquation};
+use crate::math::MathError;
+use crate::number::Number;
+use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Display;
+use std::ops::Range;
+use std::str::FromStr;
+
+/// Values bound to variables by earlier statements of a program
+type Environment = HashMap<char, PartEquation>;
 
 #[derive(Debug)]
 pub enum EvalResult {
@@ -14,34 +23,54 @@ impl Display for EvalResult {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             EvalResult::Equation(e) => write!(f, "{}", e),
-            EvalResult::PartEquation(e) => write!(f, "{}", e),
+            // a float result is shown rounded, the expression itself prints exactly
+            EvalResult::PartEquation(e) => match e.as_number() {
+                Some(value @ Number::Float(_)) => write!(f, "{}", value.display()),
+                _ => write!(f, "{}", e),
+            },
         }
     }
 }
 
-fn eval(node: Nodes) -> Result<EvalResult, Error> {
-    match node {
+fn eval(node: Spanned<Nodes>, env: &Environment) -> Result<EvalResult, Error> {
+    let span: Range<usize> = node.span;
+
+    match node.node {
         Nodes::IntegerNode(i) => Ok(EvalResult::PartEquation(PartEquation::from(i))),
-        Nodes::DecimalNode(i) => Ok(EvalResult::PartEquation(PartEquation::from(i))),
-        Nodes::VariableNode(i) => Ok(EvalResult::PartEquation(PartEquation::from(i))),
+        Nodes::BigIntegerNode(ref i) | Nodes::DecimalNode(ref i) => match i.parse::<Number>() {
+            Ok(n) => Ok(EvalResult::PartEquation(PartEquation::from(n))),
+            Err(_) => Err(Error::EvalError {
+                node: node.node,
+                message: "Could not parse number literal",
+                span,
+            }),
+        },
+        Nodes::VariableNode(i) => match env.get(&i) {
+            Some(value) => Ok(EvalResult::PartEquation(value.clone())),
+            None => Ok(EvalResult::PartEquation(PartEquation::from(i))),
+        },
+        Nodes::ConstantNode(Constant::Pi) => Ok(EvalResult::PartEquation(PartEquation::pi())),
+        Nodes::ConstantNode(Constant::E) => Ok(EvalResult::PartEquation(PartEquation::e())),
         Nodes::AddNode { lhs, rhs } => {
             let lhs: PartEquation = {
-                match eval(*lhs.clone())? {
+                match eval(*lhs.clone(), env)? {
                     EvalResult::Equation(_) => {
                         return Err(Error::EvalError {
-                            node: *lhs,
+                            node: lhs.node,
                             message: "Got Equation where PartEquation was expected",
+                            span: lhs.span,
                         });
                     }
                     EvalResult::PartEquation(e) => e,
                 }
             };
             let rhs: PartEquation = {
-                match eval(*rhs.clone())? {
+                match eval(*rhs.clone(), env)? {
                     EvalResult::Equation(_) => {
                         return Err(Error::EvalError {
-                            node: *rhs,
+                            node: rhs.node,
                             message: "Got Equation where PartEquation was expected",
+                            span: rhs.span,
                         });
                     }
                     EvalResult::PartEquation(e) => e,
@@ -52,22 +81,24 @@ fn eval(node: Nodes) -> Result<EvalResult, Error> {
         }
         Nodes::SubNode { lhs, rhs } => {
             let lhs: PartEquation = {
-                match eval(*lhs.clone())? {
+                match eval(*lhs.clone(), env)? {
                     EvalResult::Equation(_) => {
                         return Err(Error::EvalError {
-                            node: *lhs,
+                            node: lhs.node,
                             message: "Got Equation where PartEquation was expected",
+                            span: lhs.span,
                         });
                     }
                     EvalResult::PartEquation(e) => e,
                 }
             };
             let rhs: PartEquation = {
-                match eval(*rhs.clone())? {
+                match eval(*rhs.clone(), env)? {
                     EvalResult::Equation(_) => {
                         return Err(Error::EvalError {
-                            node: *rhs,
+                            node: rhs.node,
                             message: "Got Equation where PartEquation was expected",
+                            span: rhs.span,
                         });
                     }
                     EvalResult::PartEquation(e) => e,
@@ -78,22 +109,24 @@ fn eval(node: Nodes) -> Result<EvalResult, Error> {
         }
         Nodes::MulNode { lhs, rhs } => {
             let lhs: PartEquation = {
-                match eval(*lhs.clone())? {
+                match eval(*lhs.clone(), env)? {
                     EvalResult::Equation(_) => {
                         return Err(Error::EvalError {
-                            node: *lhs,
+                            node: lhs.node,
                             message: "Got Equation where PartEquation was expected",
+                            span: lhs.span,
                         });
                     }
                     EvalResult::PartEquation(e) => e,
                 }
             };
             let rhs: PartEquation = {
-                match eval(*rhs.clone())? {
+                match eval(*rhs.clone(), env)? {
                     EvalResult::Equation(_) => {
                         return Err(Error::EvalError {
-                            node: *rhs,
+                            node: rhs.node,
                             message: "Got Equation where PartEquation was expected",
+                            span: rhs.span,
                         });
                     }
                     EvalResult::PartEquation(e) => e,
@@ -107,22 +140,24 @@ fn eval(node: Nodes) -> Result<EvalResult, Error> {
             denominator,
         } => {
             let numerator: PartEquation = {
-                match eval(*numerator.clone())? {
+                match eval(*numerator.clone(), env)? {
                     EvalResult::Equation(_) => {
                         return Err(Error::EvalError {
-                            node: *numerator,
+                            node: numerator.node,
                             message: "Got Equation where PartEquation was expected",
+                            span: numerator.span,
                         });
                     }
                     EvalResult::PartEquation(e) => e,
                 }
             };
             let denominator: PartEquation = {
-                match eval(*denominator.clone())? {
+                match eval(*denominator.clone(), env)? {
                     EvalResult::Equation(_) => {
                         return Err(Error::EvalError {
-                            node: *denominator,
+                            node: denominator.node,
                             message: "Got Equation where PartEquation was expected",
+                            span: denominator.span,
                         });
                     }
                     EvalResult::PartEquation(e) => e,
@@ -133,22 +168,24 @@ fn eval(node: Nodes) -> Result<EvalResult, Error> {
         }
         Nodes::PowNode { base, exponent } => {
             let base: PartEquation = {
-                match eval(*base.clone())? {
+                match eval(*base.clone(), env)? {
                     EvalResult::Equation(_) => {
                         return Err(Error::EvalError {
-                            node: *base,
+                            node: base.node,
                             message: "Got Equation where PartEquation was expected",
+                            span: base.span,
                         });
                     }
                     EvalResult::PartEquation(e) => e,
                 }
             };
             let exponent: PartEquation = {
-                match eval(*exponent.clone())? {
+                match eval(*exponent.clone(), env)? {
                     EvalResult::Equation(_) => {
                         return Err(Error::EvalError {
-                            node: *exponent,
+                            node: exponent.node,
                             message: "Got Equation where PartEquation was expected",
+                            span: exponent.span,
                         });
                     }
                     EvalResult::PartEquation(e) => e,
@@ -157,13 +194,42 @@ fn eval(node: Nodes) -> Result<EvalResult, Error> {
 
             Ok(EvalResult::PartEquation(base.pow(&exponent)))
         }
+        Nodes::LogNode { base, argument } => {
+            let base: PartEquation = {
+                match eval(*base.clone(), env)? {
+                    EvalResult::Equation(_) => {
+                        return Err(Error::EvalError {
+                            node: base.node,
+                            message: "Got Equation where PartEquation was expected",
+                            span: base.span,
+                        });
+                    }
+                    EvalResult::PartEquation(e) => e,
+                }
+            };
+            let argument: PartEquation = {
+                match eval(*argument.clone(), env)? {
+                    EvalResult::Equation(_) => {
+                        return Err(Error::EvalError {
+                            node: argument.node,
+                            message: "Got Equation where PartEquation was expected",
+                            span: argument.span,
+                        });
+                    }
+                    EvalResult::PartEquation(e) => e,
+                }
+            };
+
+            Ok(EvalResult::PartEquation(argument.log(&base)))
+        }
         Nodes::MinusNode(i) => {
             let v: PartEquation = {
-                match eval(*i.clone())? {
+                match eval(*i.clone(), env)? {
                     EvalResult::Equation(_) => {
                         return Err(Error::EvalError {
-                            node: *i,
+                            node: i.node,
                             message: "Got Equation where PartEquation was expected",
+                            span: i.span,
                         });
                     }
                     EvalResult::PartEquation(e) => e,
@@ -172,24 +238,42 @@ fn eval(node: Nodes) -> Result<EvalResult, Error> {
 
             Ok(EvalResult::PartEquation(-v))
         }
+        Nodes::AbsNode(i) => {
+            let v: PartEquation = {
+                match eval(*i.clone(), env)? {
+                    EvalResult::Equation(_) => {
+                        return Err(Error::EvalError {
+                            node: i.node,
+                            message: "Got Equation where PartEquation was expected",
+                            span: i.span,
+                        });
+                    }
+                    EvalResult::PartEquation(e) => e,
+                }
+            };
+
+            Ok(EvalResult::PartEquation(v.abs()))
+        }
         Nodes::EquationNode { lhs, rhs } => {
             let lhs: PartEquation = {
-                match eval(*lhs.clone())? {
+                match eval(*lhs.clone(), env)? {
                     EvalResult::Equation(_) => {
                         return Err(Error::EvalError {
-                            node: *lhs,
+                            node: lhs.node,
                             message: "Got Equation where PartEquation was expected",
+                            span: lhs.span,
                         });
                     }
                     EvalResult::PartEquation(e) => e,
                 }
             };
             let rhs: PartEquation = {
-                match eval(*rhs.clone())? {
+                match eval(*rhs.clone(), env)? {
                     EvalResult::Equation(_) => {
                         return Err(Error::EvalError {
-                            node: *rhs,
+                            node: rhs.node,
                             message: "Got Equation where PartEquation was expected",
+                            span: rhs.span,
                         });
                     }
                     EvalResult::PartEquation(e) => e,
@@ -199,41 +283,49 @@ fn eval(node: Nodes) -> Result<EvalResult, Error> {
             Ok(EvalResult::Equation(Equation::new(&lhs, &rhs)))
         }
         Nodes::SolutionNode { eq, at } => {
-            let eq = eval(*eq.clone())?;
+            let eq = eval(*eq.clone(), env)?;
 
-            if let Nodes::SubstituteNode(variable, value) = *at {
+            if let Nodes::SubstituteNode(variable, value) = at.node {
                 match value {
                     Some(v) => match eq {
-                        EvalResult::PartEquation(e) => match *v {
-                            Nodes::IntegerNode(i) => {
-                                Ok(EvalResult::PartEquation(e.substitute(variable, &PartEquation::from(i))))
-                            }
-                            Nodes::DecimalNode(i) => {
-                                Ok(EvalResult::PartEquation(e.substitute(variable, &PartEquation::from(i))))
+                        EvalResult::PartEquation(e) => match v.node {
+                            Nodes::IntegerNode(i) => Ok(EvalResult::PartEquation(
+                                e.substitute(variable, &PartEquation::from(i)),
+                            )),
+                            Nodes::BigIntegerNode(ref i) | Nodes::DecimalNode(ref i) => {
+                                match i.parse::<Number>() {
+                                    Ok(n) => Ok(EvalResult::PartEquation(
+                                        e.substitute(variable, &PartEquation::from(n)),
+                                    )),
+                                    Err(_) => Err(Error::EvalError {
+                                        node: v.node,
+                                        message: "Could not parse number literal",
+                                        span: v.span,
+                                    }),
+                                }
                             }
-                            _ => Err(Error::EvalError {
-                                node: *v,
-                                message: "Substitution of value other then integer and decimal is not yet implemented",
-                            }),
+                            _ => Err(Error::MathError(MathError::UnsupportedOperation)),
                         },
                         EvalResult::Equation(_) => {
                             return Err(Error::EvalError {
-                                node: *v,
+                                node: v.node,
                                 message: "Got PartEquation where Equation was expected",
+                                span: v.span,
                             });
                         }
                     },
                     None => match eq {
                         EvalResult::Equation(e) => Ok(EvalResult::PartEquation(e.solve(variable)?)),
                         EvalResult::PartEquation(e) => Ok(EvalResult::PartEquation(
-                            Equation::new(&e, &PartEquation::from(0)).solve(variable)?,
+                            Equation::from_expr(&e).solve(variable)?,
                         )),
                     },
                 }
             } else {
                 return Err(Error::EvalError {
-                    node: *at,
+                    node: at.node,
                     message: "Expected a SubstituteNode got something else",
+                    span: at.span,
                 });
             }
         }
@@ -241,12 +333,499 @@ fn eval(node: Nodes) -> Result<EvalResult, Error> {
             return Err(Error::EvalError {
                 node: n,
                 message: "Got SubstituteNode when expecting anything else",
+                span,
             });
         }
+        n @ Nodes::AssignNode { .. } => Err(Error::EvalError {
+            node: n,
+            message: "Assignments with ':=' are only allowed as statements of a program",
+            span,
+        }),
     }
 }
 
+/// Interprets a single statement, see `interpret_program` for several statements
 pub fn interpret(statement: String) -> Result<EvalResult, Error> {
-    let node: Nodes = Parser::new(statement).parse()?;
-    eval(node)
+    let mut statements: Vec<Spanned<Nodes>> =
+        Parser::new(statement.clone()).parse_program_spanned()?;
+
+    if statements.len() != 1 {
+        return Err(Error::ParserError {
+            token: Token::NoneToken,
+            message: "Expected a single statement",
+            span: 0..statement.len(),
+        });
+    }
+    eval(statements.remove(0), &Environment::new())
+}
+
+/// Interprets `;` separated statements in order.
+/// A statement of the form `a := <expression>` binds `a` to the expression
+/// for all the statements that follow it, `a = <expression>` stays an equation.
+pub fn interpret_program(program: String) -> Result<Vec<EvalResult>, Error> {
+    let statements: Vec<Spanned<Nodes>> = Parser::new(program).parse_program_spanned()?;
+    let mut env: Environment = Environment::new();
+    let mut results: Vec<EvalResult> = Vec::new();
+
+    for (i, node) in statements.into_iter().enumerate() {
+        let result = match node.node {
+            Nodes::AssignNode { variable, value } => bind(variable, *value, &mut env),
+            n => eval(
+                Spanned {
+                    node: n,
+                    span: node.span,
+                },
+                &env,
+            ),
+        };
+
+        match result {
+            Ok(r) => results.push(r),
+            Err(e) => {
+                return Err(Error::StatementError {
+                    statement: i + 1,
+                    error: Box::new(e),
+                })
+            }
+        }
+    }
+
+    Ok(results)
+}
+
+impl FromStr for PartEquation {
+    type Err = Error;
+
+    /// Parses a single expression, in the syntax that `Display` produces
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut statements: Vec<Spanned<Nodes>> =
+            Parser::new(String::from(s)).parse_program_spanned()?;
+
+        if statements.len() != 1 {
+            return Err(Error::ParserError {
+                token: Token::NoneToken,
+                message: "Expected a single expression",
+                span: 0..s.len(),
+            });
+        }
+
+        PartEquation::try_from(statements.remove(0))
+    }
+}
+
+impl TryFrom<Spanned<Nodes>> for PartEquation {
+    type Error = Error;
+
+    /// Evaluates a parsed expression the way `interpret_program` does, with no variables bound.
+    /// Fails with `EvalError` for an equation
+    fn try_from(node: Spanned<Nodes>) -> Result<Self, Self::Error> {
+        match eval(node.clone(), &Environment::new())? {
+            EvalResult::PartEquation(e) => Ok(e),
+            EvalResult::Equation(_) => Err(Error::EvalError {
+                node: node.node,
+                message: "Got Equation where PartEquation was expected",
+                span: node.span,
+            }),
+        }
+    }
+}
+
+impl TryFrom<Nodes> for PartEquation {
+    type Error = Error;
+
+    /// Same as for `Spanned<Nodes>`, errors carry an empty span
+    fn try_from(node: Nodes) -> Result<Self, Self::Error> {
+        PartEquation::try_from(Spanned::from(node))
+    }
+}
+
+impl TryFrom<Spanned<Nodes>> for Equation {
+    type Error = Error;
+
+    /// Evaluates a parsed equation the way `interpret_program` does, with no variables bound.
+    /// Fails with `EvalError` for an expression that is not an equation
+    fn try_from(node: Spanned<Nodes>) -> Result<Self, Self::Error> {
+        match eval(node.clone(), &Environment::new())? {
+            EvalResult::Equation(e) => Ok(e),
+            EvalResult::PartEquation(_) => Err(Error::EvalError {
+                node: node.node,
+                message: "Got PartEquation where Equation was expected",
+                span: node.span,
+            }),
+        }
+    }
+}
+
+impl TryFrom<Nodes> for Equation {
+    type Error = Error;
+
+    /// Same as for `Spanned<Nodes>`, errors carry an empty span
+    fn try_from(node: Nodes) -> Result<Self, Self::Error> {
+        Equation::try_from(Spanned::from(node))
+    }
+}
+
+fn bind(variable: char, value: Spanned<Nodes>, env: &mut Environment) -> Result<EvalResult, Error> {
+    let value: PartEquation = match eval(value.clone(), env)? {
+        EvalResult::Equation(_) => {
+            return Err(Error::EvalError {
+                node: value.node,
+                message: "Got Equation where PartEquation was expected",
+                span: value.span,
+            });
+        }
+        EvalResult::PartEquation(e) => e,
+    };
+
+    env.insert(variable, value.clone());
+    Ok(EvalResult::Equation(Equation::new(
+        &PartEquation::from(variable),
+        &value,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::TAU;
+
+    #[test]
+    fn test_interpret_program_1() {
+        let results = interpret_program(String::from("a := x + 1; a * 2 @ x, 3")).unwrap();
+        assert_eq!(results.len(), 2);
+
+        match &results[1] {
+            EvalResult::PartEquation(e) => assert_eq!(*e, PartEquation::from(8)),
+            r => panic!("expected a PartEquation, got {}", r),
+        }
+    }
+
+    #[test]
+    fn test_interpret_assign_1() {
+        // `=` with a variable on the left stays an equation and binds nothing
+        let results = interpret_program(String::from("x = 2 * y + 1; x @ y, 3")).unwrap();
+        match &results[0] {
+            EvalResult::Equation(e) => {
+                assert_eq!(e.solve('y').unwrap(), (PartEquation::from('x') - 1) / 2)
+            }
+            r => panic!("expected an Equation, got {}", r),
+        }
+        match &results[1] {
+            EvalResult::PartEquation(e) => assert_eq!(*e, PartEquation::from('x')),
+            r => panic!("expected a PartEquation, got {}", r),
+        }
+
+        assert!(matches!(
+            interpret_program(String::from("2 * a := 3")),
+            Err(Error::StatementError { statement: 1, .. })
+        ));
+
+        match interpret(String::from("2 * x = 4 @ x")).unwrap() {
+            EvalResult::PartEquation(e) => assert_eq!(e, PartEquation::from(2)),
+            r => panic!("expected a PartEquation, got {}", r),
+        }
+        assert!(interpret(String::from("x; y")).is_err());
+        assert!(interpret(String::from("a := 2")).is_err());
+    }
+
+    #[test]
+    fn test_interpret_constants_1() {
+        let results = interpret_program(String::from("2 * pi")).unwrap();
+
+        match &results[0] {
+            EvalResult::PartEquation(e) => {
+                let value = e.evaluate().unwrap();
+                assert!(value > Number::from(TAU - 1e-9) && value < Number::from(TAU + 1e-9));
+            }
+            r => panic!("expected a PartEquation, got {}", r),
+        }
+
+        let results = interpret_program(String::from("e ^ 2")).unwrap();
+
+        match &results[0] {
+            EvalResult::PartEquation(e) => {
+                let value = e.evaluate().unwrap();
+                assert!(value > Number::from(7.389) && value < Number::from(7.390));
+            }
+            r => panic!("expected a PartEquation, got {}", r),
+        }
+
+        assert!(interpret_program(String::from("2 * pie")).is_err());
+    }
+
+    #[test]
+    fn test_interpret_big_integer_1() {
+        let results =
+            interpret_program(String::from("1234567890123456789012345678901234567890 + 1"))
+                .unwrap();
+
+        match &results[0] {
+            EvalResult::PartEquation(e) => assert_eq!(
+                *e,
+                PartEquation::from(
+                    "1234567890123456789012345678901234567891"
+                        .parse::<Number>()
+                        .unwrap()
+                )
+            ),
+            r => panic!("expected a PartEquation, got {}", r),
+        }
+
+        let results = interpret_program(String::from("x * 2 @ x, 99999999999999999999")).unwrap();
+
+        match &results[0] {
+            EvalResult::PartEquation(e) => assert_eq!(
+                *e,
+                PartEquation::from("199999999999999999998".parse::<Number>().unwrap())
+            ),
+            r => panic!("expected a PartEquation, got {}", r),
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rug")]
+    fn test_interpret_decimal_precision_1() {
+        let results = interpret_program(String::from("0.1 + 0.2")).unwrap();
+
+        let value: Number = match &results[0] {
+            EvalResult::PartEquation(e) => e.evaluate().unwrap(),
+            r => panic!("expected a PartEquation, got {}", r),
+        };
+        let error: Number = value - "0.3".parse::<Number>().unwrap();
+        let f64_error: Number = Number::from(0.1_f64 + 0.2_f64 - 0.3_f64);
+
+        assert!(error < Number::from(1e-25) && error > Number::from(-1e-25));
+        assert!(f64_error > Number::from(1e-25));
+
+        assert_eq!(results[0].to_string(), "0.3");
+
+        let results = interpret_program(String::from("x + .5 @ x, 1.")).unwrap();
+
+        match &results[0] {
+            EvalResult::PartEquation(e) => assert_eq!(*e, PartEquation::from(1.5)),
+            r => panic!("expected a PartEquation, got {}", r),
+        }
+    }
+
+    #[test]
+    fn test_interpret_unsupported_substitution_1() {
+        match interpret_program(String::from("x + 1 @ x, y")) {
+            Err(Error::StatementError {
+                statement: 1,
+                error,
+            }) => assert!(matches!(
+                *error,
+                Error::MathError(MathError::UnsupportedOperation)
+            )),
+            r => panic!("expected an UnsupportedOperation error, got {:?}", r),
+        }
+    }
+
+    #[test]
+    fn test_interpret_error_span_1() {
+        // the error points at the value substituted into an equation, not the whole statement
+        match interpret(String::from("x = 1 @ x, 2")) {
+            Err(Error::EvalError { node, span, .. }) => {
+                assert_eq!(node, Nodes::IntegerNode(2));
+                assert_eq!(span, 11..12);
+            }
+            r => panic!("expected an EvalError, got {:?}", r),
+        }
+    }
+
+    #[test]
+    fn test_interpret_program_2() {
+        assert!(matches!(
+            interpret_program(String::from("a := 2; a * 2 = 4 @ x")),
+            Err(Error::StatementError { statement: 2, .. })
+        ));
+    }
+
+    // every expression built from the atoms with one or two operations
+    fn generate_expressions() -> Vec<PartEquation> {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
+        let atoms: Vec<PartEquation> = vec![
+            x.clone(),
+            y.clone(),
+            PartEquation::from(3),
+            PartEquation::from(-2),
+            PartEquation::from(1) / PartEquation::from(2),
+            PartEquation::from(1.5),
+            -&x,
+        ];
+        let exponents: Vec<PartEquation> =
+            vec![PartEquation::from(2), PartEquation::from(-1), y.clone()];
+
+        let combine = |a: &PartEquation, b: &PartEquation| -> Vec<PartEquation> {
+            let mut results = vec![a + b, a - b, a * b];
+            if *b != PartEquation::from(0) {
+                results.push(a / b);
+            }
+            results
+        };
+
+        let mut level: Vec<PartEquation> = Vec::new();
+        for a in atoms.iter() {
+            for b in atoms.iter() {
+                level.extend(combine(a, b));
+            }
+            for b in exponents.iter() {
+                level.push(a.pow(b));
+            }
+            level.push(a.pow(&(PartEquation::from(1) / PartEquation::from(3))));
+            level.push(-a);
+            level.push(a.ln());
+            level.push(a.log(&PartEquation::from(2)));
+            level.push(a.exp());
+            level.push(a.abs());
+        }
+
+        let mut expressions: Vec<PartEquation> = level.clone();
+        for a in level.iter() {
+            for b in atoms.iter() {
+                expressions.extend(combine(a, b));
+                expressions.extend(combine(b, a));
+            }
+            for b in exponents.iter() {
+                expressions.push(a.pow(b));
+                expressions.push(b.pow(a));
+            }
+            expressions.push(-a);
+        }
+
+        // (-1) ^ (1/2) folds to i, which has no syntax
+        expressions.retain(|e| !matches!(e.evaluate(), Ok(Number::Complex(_))));
+        expressions
+    }
+
+    #[test]
+    fn test_display_round_trip_1() {
+        for expr in generate_expressions() {
+            let string: String = expr.to_string();
+            let parsed: PartEquation = match string.parse() {
+                Ok(e) => e,
+                Err(e) => panic!("could not parse {}: {}", string, e),
+            };
+            assert_eq!(parsed, expr, "round trip of {}", string);
+        }
+
+        // bars inside bars print parenthesized, in expressions and in the sides of an equation
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
+        for expr in [
+            (x.abs() - 4).abs(),
+            (x.abs() + 1 + &y).abs(),
+            (x.abs() * y.abs()).abs(),
+            (x.abs() - 4).abs().log(&PartEquation::from(2)).abs(),
+        ] {
+            let string: String = expr.to_string();
+            assert_eq!(string.parse::<PartEquation>().unwrap(), expr, "{}", string);
+
+            let eq: Equation = Equation::new(&expr, &PartEquation::from(1));
+            let string: String = eq.to_string();
+            match interpret(string.clone()) {
+                Ok(EvalResult::Equation(parsed)) => assert_eq!(parsed, eq, "{}", string),
+                r => panic!("could not parse {}: {:?}", string, r),
+            }
+        }
+        assert_eq!(
+            Equation::new(&(x.abs() - 4).abs(), &PartEquation::from(1)).to_string(),
+            "|(|x| + -4)| = 1"
+        );
+    }
+
+    #[test]
+    fn test_parse_abs_1() {
+        let x: PartEquation = PartEquation::from('x');
+
+        assert_eq!("|x|".parse::<PartEquation>().unwrap(), x.abs());
+        assert_eq!("|x - 3|".parse::<PartEquation>().unwrap(), (&x - 3).abs());
+        assert_eq!(
+            "|x| * |y| + 1".parse::<PartEquation>().unwrap(),
+            x.abs() * PartEquation::from('y').abs() + 1
+        );
+        assert_eq!(
+            "|(|x| - 4)|".parse::<PartEquation>().unwrap(),
+            (x.abs() - 4).abs()
+        );
+        assert_eq!(
+            "|log(|x|, 2)|".parse::<PartEquation>().unwrap(),
+            PartEquation::from(2).log(&x.abs()).abs()
+        );
+        assert_eq!(
+            "|-5| + |2 - 7|".parse::<PartEquation>().unwrap(),
+            PartEquation::from(10)
+        );
+
+        assert!(matches!(
+            "||x||".parse::<PartEquation>(),
+            Err(Error::StatementError { error, .. })
+                if matches!(*error, Error::ParserError { token: Token::BarToken, .. })
+        ));
+        assert!("|x".parse::<PartEquation>().is_err());
+    }
+
+    #[test]
+    fn test_parse_part_equation_1() {
+        let x: PartEquation = PartEquation::from('x');
+
+        assert_eq!(
+            "2 ^ 3 ^ 2".parse::<PartEquation>().unwrap(),
+            PartEquation::from(512)
+        );
+        assert_eq!(
+            "2 ** 3 ** 2".parse::<PartEquation>().unwrap(),
+            PartEquation::from(512)
+        );
+        assert_eq!(
+            "log(2, 8)"
+                .parse::<PartEquation>()
+                .unwrap()
+                .evaluate()
+                .unwrap(),
+            Number::from(3)
+        );
+        assert_eq!(
+            "2.5e-1 * x".parse::<PartEquation>().unwrap(),
+            PartEquation::from(0.25) * &x
+        );
+        assert_eq!(
+            "e*2".parse::<PartEquation>().unwrap(),
+            PartEquation::e() * 2
+        );
+
+        assert!("x = 2".parse::<PartEquation>().is_err());
+        assert!("x; y".parse::<PartEquation>().is_err());
+        assert!("".parse::<PartEquation>().is_err());
+    }
+
+    #[test]
+    fn test_try_from_nodes_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let mut statements: Vec<Nodes> = Parser::new(String::from("2 * x + |x - 1|; 3 * x = 6"))
+            .parse_program()
+            .unwrap();
+        let equation: Nodes = statements.pop().unwrap();
+        let expression: Nodes = statements.pop().unwrap();
+
+        assert_eq!(
+            PartEquation::try_from(expression.clone()).unwrap(),
+            2 * &x + (&x - 1).abs()
+        );
+        assert!(Equation::try_from(expression).is_err());
+
+        let eq: Equation = Equation::try_from(equation.clone()).unwrap();
+        assert_eq!(eq.solve('x').unwrap(), PartEquation::from(2));
+        assert!(PartEquation::try_from(equation).is_err());
+
+        let tree: Nodes = Nodes::PowNode {
+            base: Box::new(Nodes::VariableNode('y').into()),
+            exponent: Box::new(Nodes::IntegerNode(2).into()),
+        };
+        assert_eq!(
+            PartEquation::try_from(tree).unwrap(),
+            PartEquation::from('y').pow(&PartEquation::from(2))
+        );
+    }
 }
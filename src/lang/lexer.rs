@@ -1,6 +1,13 @@
 use super::error::Error;
+use std::ops::Range;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Constant {
+    Pi,
+    E,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Token {
     NoneToken,
     PlusToken,
@@ -9,17 +16,25 @@ pub enum Token {
     DivToken,
     PowToken,
     EqualToken,
-    ForToken,   // @
-    CommaToken, // ,
+    AssignToken,    // :=
+    ForToken,       // @
+    CommaToken,     // ,
+    SemicolonToken, // ;
     LeftParenToken,
     RightParenToken,
+    BarToken, // |
     IntegerToken(i64),
-    DecimalToken(f64),
+    BigIntegerToken, // integers that do not fit in i64, the digits are the token's text
+    DecimalToken,    // the digits are the token's text
     VariableToken(char),
+    ConstantToken(Constant), // pi, e
+    LogToken,                // log
 }
 
 struct Statement {
     string: String,
+    // byte offset and character at every character index, so lookups are O(1)
+    chars: Vec<(usize, char)>,
     position: usize,
 }
 
@@ -27,8 +42,8 @@ impl Iterator for Statement {
     type Item = (usize, char);
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.string.chars().nth(self.position) {
-            Some(x) => {
+        match self.chars.get(self.position) {
+            Some(&(_, x)) => {
                 self.position += 1;
                 Some((self.position - 1, x))
             }
@@ -42,16 +57,25 @@ impl Iterator for Statement {
 
 impl Statement {
     fn present(&self) -> Option<(usize, char)> {
-        match self.string.chars().nth(self.position - 1) {
-            Some(x) => Some((self.position, x)),
-            None => None,
-        }
+        self.chars
+            .get(self.position - 1)
+            .map(|&(_, x)| (self.position, x))
+    }
+
+    // character `offset` places after the present one
+    fn peek(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.position - 1 + offset).map(|&(_, x)| x)
     }
 }
 
 pub struct Lexer {
     statement: Statement,
     present_token: Token,
+    // byte offsets of the present token and where the one before it ended
+    present_span: Range<usize>,
+    previous_end: usize,
+    // character index the token being lexed starts at
+    token_start: usize,
     err: Error,
     err_occurred: bool,
 }
@@ -60,11 +84,41 @@ impl Iterator for Lexer {
     type Item = Result<Token, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        self.previous_end = self.present_span.end;
+        let token: Option<Self::Item> = self.lex();
+
+        self.present_span = match token {
+            Some(Ok(_)) => {
+                self.byte_offset(self.token_start)..self.byte_offset(self.statement.position - 1)
+            }
+            _ => {
+                let end: usize = self.statement.string.len();
+                end..end
+            }
+        };
+        token
+    }
+}
+
+impl Lexer {
+    fn lex(&mut self) -> Option<Result<Token, Error>> {
         while let Some((p, c)) = self.statement.present() {
             if c.is_whitespace() {
                 self.statement.next();
                 continue;
-            } else if c.is_numeric() || c == '.' {
+            } else if c == '#' {
+                // comment, up to the end of the line
+                while let Some((_, c)) = self.statement.present() {
+                    if c == '\n' {
+                        break;
+                    }
+                    self.statement.next();
+                }
+                continue;
+            }
+
+            self.token_start = p - 1;
+            if c.is_numeric() || c == '.' {
                 match self.generate_number() {
                     Ok(x) => {
                         self.present_token = x;
@@ -78,9 +132,18 @@ impl Iterator for Lexer {
                     }
                 }
             } else if c.is_alphabetic() {
-                self.present_token = Token::VariableToken(c);
-                self.statement.next();
-                return Some(Ok(self.present_token));
+                match self.generate_identifier() {
+                    Ok(x) => {
+                        self.present_token = x;
+                        return Some(Ok(self.present_token));
+                    }
+                    Err(x) => {
+                        self.present_token = Token::NoneToken;
+                        self.err_occurred = true;
+                        self.err = x.clone();
+                        return Some(Err(x));
+                    }
+                }
             } else if c == '+' {
                 self.present_token = Token::PlusToken;
                 self.statement.next();
@@ -89,6 +152,21 @@ impl Iterator for Lexer {
                 self.present_token = Token::MinusToken;
                 self.statement.next();
                 return Some(Ok(self.present_token));
+            } else if c == '*' && self.statement.peek(1) == Some('*') {
+                // `**` is an alternative spelling of `^`
+                if self.statement.peek(2) == Some('*') {
+                    self.err_occurred = true;
+                    self.err = Error::LexerError {
+                        position: p + 2,
+                        statement: self.statement.string.clone(),
+                        message: "Unexpected `*` after `**`",
+                    };
+                    return Some(Err(self.err.clone()));
+                }
+                self.present_token = Token::PowToken;
+                self.statement.next();
+                self.statement.next();
+                return Some(Ok(self.present_token));
             } else if c == '*' {
                 self.present_token = Token::MulToken;
                 self.statement.next();
@@ -105,6 +183,11 @@ impl Iterator for Lexer {
                 self.present_token = Token::EqualToken;
                 self.statement.next();
                 return Some(Ok(self.present_token));
+            } else if c == ':' && self.statement.peek(1) == Some('=') {
+                self.present_token = Token::AssignToken;
+                self.statement.next();
+                self.statement.next();
+                return Some(Ok(self.present_token));
             } else if c == '(' {
                 self.present_token = Token::LeftParenToken;
                 self.statement.next();
@@ -113,6 +196,10 @@ impl Iterator for Lexer {
                 self.present_token = Token::RightParenToken;
                 self.statement.next();
                 return Some(Ok(self.present_token));
+            } else if c == '|' {
+                self.present_token = Token::BarToken;
+                self.statement.next();
+                return Some(Ok(self.present_token));
             } else if c == '@' {
                 self.present_token = Token::ForToken;
                 self.statement.next();
@@ -121,6 +208,10 @@ impl Iterator for Lexer {
                 self.present_token = Token::CommaToken;
                 self.statement.next();
                 return Some(Ok(self.present_token));
+            } else if c == ';' {
+                self.present_token = Token::SemicolonToken;
+                self.statement.next();
+                return Some(Ok(self.present_token));
             } else {
                 self.err_occurred = true;
                 self.err = Error::LexerError {
@@ -140,16 +231,18 @@ impl Iterator for Lexer {
         self.present_token = Token::NoneToken;
         return None;
     }
-}
 
-impl Lexer {
     pub fn new(string: String) -> Self {
         let mut r = Lexer {
             statement: Statement {
+                chars: string.char_indices().collect(),
                 string: string,
                 position: 0,
             },
             present_token: Token::NoneToken,
+            present_span: 0..0,
+            previous_end: 0,
+            token_start: 0,
             err: Error::LexerError {
                 position: 0,
                 statement: String::new(),
@@ -169,6 +262,28 @@ impl Lexer {
         return Ok(self.present_token);
     }
 
+    /// Byte range of the present token in the statement, empty at the end of the statement
+    pub fn span(&self) -> Range<usize> {
+        self.present_span.clone()
+    }
+
+    /// Text of the present token in the statement
+    pub fn text(&self) -> &str {
+        &self.statement.string[self.present_span.clone()]
+    }
+
+    /// Byte offset where the token before the present one ended
+    pub fn previous_end(&self) -> usize {
+        self.previous_end
+    }
+
+    fn byte_offset(&self, index: usize) -> usize {
+        match self.statement.chars.get(index) {
+            Some(&(offset, _)) => offset,
+            None => self.statement.string.len(),
+        }
+    }
+
     fn generate_number(&mut self) -> Result<Token, Error> {
         let mut num_string: String = String::new();
         let mut decimal: bool = false;
@@ -177,6 +292,23 @@ impl Lexer {
             if c.is_numeric() {
                 num_string.push(c);
                 self.statement.next();
+            } else if c == 'e' && self.exponent_follows() {
+                // scientific notation, as floats are displayed: 1.5e-7
+                decimal = true;
+                num_string.push(c);
+                self.statement.next();
+                if let Some((_, sign @ ('+' | '-'))) = self.statement.present() {
+                    num_string.push(sign);
+                    self.statement.next();
+                }
+                while let Some((_, c)) = self.statement.present() {
+                    if !c.is_numeric() {
+                        break;
+                    }
+                    num_string.push(c);
+                    self.statement.next();
+                }
+                break;
             } else if c == '.' {
                 if decimal == true {
                     return Err(Error::LexerError {
@@ -194,8 +326,215 @@ impl Lexer {
         }
 
         if decimal {
-            return Ok(Token::DecimalToken(num_string.parse().unwrap()));
+            return Ok(Token::DecimalToken);
+        }
+        match num_string.parse() {
+            Ok(i) => Ok(Token::IntegerToken(i)),
+            Err(_) => Ok(Token::BigIntegerToken),
+        }
+    }
+
+    // checks if the present `e` starts the exponent of a number rather than naming the constant
+    fn exponent_follows(&self) -> bool {
+        match self.statement.peek(1) {
+            Some('+' | '-') => self.statement.peek(2).is_some_and(|c| c.is_numeric()),
+            Some(c) => c.is_numeric(),
+            None => false,
+        }
+    }
+
+    fn generate_identifier(&mut self) -> Result<Token, Error> {
+        let mut identifier: String = String::new();
+        let start: usize = match self.statement.present() {
+            Some((p, _)) => p,
+            None => 0,
+        };
+
+        while let Some((_, c)) = self.statement.present() {
+            if c.is_alphabetic() {
+                identifier.push(c);
+                self.statement.next();
+            } else {
+                break;
+            }
+        }
+
+        match identifier.as_str() {
+            "pi" => return Ok(Token::ConstantToken(Constant::Pi)),
+            "e" => return Ok(Token::ConstantToken(Constant::E)),
+            "log" => return Ok(Token::LogToken),
+            _ => {}
+        }
+
+        let mut chars = identifier.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Ok(Token::VariableToken(c));
         }
-        return Ok(Token::IntegerToken(num_string.parse().unwrap()));
+
+        Err(Error::LexerError {
+            position: start,
+            statement: self.statement.string.clone(),
+            message: "Unknown identifier, variables are single letters",
+        })
+    }
+}
+
+/// Splits a statement into tokens
+pub fn tokenize(statement: &str) -> Result<Vec<Token>, Error> {
+    Lexer::new(String::from(statement)).collect()
+}
+
+/// Splits a statement into tokens, each with its byte range in the statement
+pub fn tokenize_spanned(statement: &str) -> Result<Vec<(Token, Range<usize>)>, Error> {
+    let mut lexer: Lexer = Lexer::new(String::from(statement));
+    let mut tokens: Vec<(Token, Range<usize>)> = Vec::new();
+
+    while let Some(token) = lexer.next() {
+        tokens.push((token?, lexer.span()));
+    }
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(statement: &str) -> Result<Vec<Token>, Error> {
+        tokenize(statement)
+    }
+
+    #[test]
+    fn test_lex_comment_1() {
+        let expected = tokens("2 + 3").unwrap();
+
+        assert_eq!(tokens("2 + 3 # this is five").unwrap(), expected);
+        assert_eq!(tokens("2 + 3 # five; * 4\n").unwrap(), expected);
+        assert_eq!(tokens("# sum\n2 +# first\n 3").unwrap(), expected);
+
+        assert!(tokens("# only a comment").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_lex_power_1() {
+        assert_eq!(
+            tokens("2 ** 3").unwrap(),
+            vec![
+                Token::IntegerToken(2),
+                Token::PowToken,
+                Token::IntegerToken(3)
+            ]
+        );
+        assert_eq!(
+            tokens("2 * 3").unwrap(),
+            vec![
+                Token::IntegerToken(2),
+                Token::MulToken,
+                Token::IntegerToken(3)
+            ]
+        );
+        assert_eq!(
+            tokens("x**2*y").unwrap(),
+            vec![
+                Token::VariableToken('x'),
+                Token::PowToken,
+                Token::IntegerToken(2),
+                Token::MulToken,
+                Token::VariableToken('y')
+            ]
+        );
+        assert_eq!(
+            tokens("2 ** *3").unwrap(),
+            vec![
+                Token::IntegerToken(2),
+                Token::PowToken,
+                Token::MulToken,
+                Token::IntegerToken(3)
+            ]
+        );
+
+        assert!(matches!(
+            tokens("2 *** 3"),
+            Err(Error::LexerError { position: 5, .. })
+        ));
+    }
+
+    #[test]
+    fn test_lex_1() {
+        assert_eq!(
+            tokens("log(2, x) = -1.5 @ x; |pi - e|").unwrap(),
+            vec![
+                Token::LogToken,
+                Token::LeftParenToken,
+                Token::IntegerToken(2),
+                Token::CommaToken,
+                Token::VariableToken('x'),
+                Token::RightParenToken,
+                Token::EqualToken,
+                Token::MinusToken,
+                Token::DecimalToken,
+                Token::ForToken,
+                Token::VariableToken('x'),
+                Token::SemicolonToken,
+                Token::BarToken,
+                Token::ConstantToken(Constant::Pi),
+                Token::MinusToken,
+                Token::ConstantToken(Constant::E),
+                Token::BarToken,
+            ]
+        );
+        assert_eq!(
+            tokenize_spanned("99999999999999999999").unwrap(),
+            vec![(Token::BigIntegerToken, 0..20)]
+        );
+    }
+
+    #[test]
+    fn test_lex_constants_1() {
+        // `e` names the constant, `E` is still a variable
+        assert_eq!(
+            tokenize("pi * e * E").unwrap(),
+            vec![
+                Token::ConstantToken(Constant::Pi),
+                Token::MulToken,
+                Token::ConstantToken(Constant::E),
+                Token::MulToken,
+                Token::VariableToken('E'),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lex_spans_1() {
+        assert_eq!(
+            tokenize_spanned("2 + x").unwrap(),
+            vec![
+                (Token::IntegerToken(2), 0..1),
+                (Token::PlusToken, 2..3),
+                (Token::VariableToken('x'), 4..5),
+            ]
+        );
+        assert_eq!(
+            tokenize_spanned("  log(12.5, pi) ** y # note").unwrap(),
+            vec![
+                (Token::LogToken, 2..5),
+                (Token::LeftParenToken, 5..6),
+                (Token::DecimalToken, 6..10),
+                (Token::CommaToken, 10..11),
+                (Token::ConstantToken(Constant::Pi), 12..14),
+                (Token::RightParenToken, 14..15),
+                (Token::PowToken, 16..18),
+                (Token::VariableToken('y'), 19..20),
+            ]
+        );
+
+        // byte offsets, `θ` takes two bytes
+        assert_eq!(
+            tokenize_spanned("θ + x").unwrap(),
+            vec![
+                (Token::VariableToken('θ'), 0..2),
+                (Token::PlusToken, 3..4),
+                (Token::VariableToken('x'), 5..6),
+            ]
+        );
     }
 }
@@ -3,4 +3,7 @@ mod interpreter;
 mod lexer;
 mod parser;
 
-pub use interpreter::interpret;
+pub use error::Error;
+pub use interpreter::{interpret, interpret_program, EvalResult};
+pub use lexer::{tokenize, tokenize_spanned, Constant, Token};
+pub use parser::{Nodes, Parser, Spanned};
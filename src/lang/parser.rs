@@ -1,51 +1,94 @@
 use super::error::Error;
-use super::lexer::{Lexer, Token};
+use super::lexer::{Constant, Lexer, Token};
 use std::fmt;
 use std::fmt::Display;
+use std::ops::Range;
 
+/// A parsed node with the byte range it was parsed from
 #[derive(Debug, Clone)]
+pub struct Spanned<T> {
+    pub node: T,
+    pub span: Range<usize>,
+}
+
+// the span is where the node came from, not part of its value
+impl<T: PartialEq> PartialEq for Spanned<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.node == other.node
+    }
+}
+
+/// A node built by hand, with an empty span
+impl<T> From<T> for Spanned<T> {
+    fn from(node: T) -> Self {
+        Spanned { node, span: 0..0 }
+    }
+}
+
+impl<T: Display> Display for Spanned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.node.fmt(f)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum Nodes {
     IntegerNode(i64),
-    DecimalNode(f64),
+    BigIntegerNode(String),
+    DecimalNode(String),
     VariableNode(char),
+    ConstantNode(Constant),
     AddNode {
-        lhs: Box<Nodes>,
-        rhs: Box<Nodes>,
+        lhs: Box<Spanned<Nodes>>,
+        rhs: Box<Spanned<Nodes>>,
     },
     SubNode {
-        lhs: Box<Nodes>,
-        rhs: Box<Nodes>,
+        lhs: Box<Spanned<Nodes>>,
+        rhs: Box<Spanned<Nodes>>,
     },
     MulNode {
-        lhs: Box<Nodes>,
-        rhs: Box<Nodes>,
+        lhs: Box<Spanned<Nodes>>,
+        rhs: Box<Spanned<Nodes>>,
     },
     DivNode {
-        numerator: Box<Nodes>,
-        denominator: Box<Nodes>,
+        numerator: Box<Spanned<Nodes>>,
+        denominator: Box<Spanned<Nodes>>,
     },
     PowNode {
-        base: Box<Nodes>,
-        exponent: Box<Nodes>,
+        base: Box<Spanned<Nodes>>,
+        exponent: Box<Spanned<Nodes>>,
+    },
+    LogNode {
+        base: Box<Spanned<Nodes>>,
+        argument: Box<Spanned<Nodes>>,
     },
-    MinusNode(Box<Nodes>),
+    MinusNode(Box<Spanned<Nodes>>),
+    AbsNode(Box<Spanned<Nodes>>),
     EquationNode {
-        lhs: Box<Nodes>,
-        rhs: Box<Nodes>,
+        lhs: Box<Spanned<Nodes>>,
+        rhs: Box<Spanned<Nodes>>,
     },
     SolutionNode {
-        eq: Box<Nodes>,
-        at: Box<Nodes>,
+        eq: Box<Spanned<Nodes>>,
+        at: Box<Spanned<Nodes>>,
+    },
+    SubstituteNode(char, Option<Box<Spanned<Nodes>>>), // substitute a variable to integer or decimal
+    /// `a := <expression>`, binds `a` for the statements that follow
+    AssignNode {
+        variable: char,
+        value: Box<Spanned<Nodes>>,
     },
-    SubstituteNode(char, Option<Box<Nodes>>), // substitute a variable to integer or decimal
 }
 
 impl Display for Nodes {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Nodes::IntegerNode(i) => write!(f, "{}", i),
+            Nodes::BigIntegerNode(i) => write!(f, "{}", i),
             Nodes::DecimalNode(i) => write!(f, "{}", i),
             Nodes::VariableNode(i) => write!(f, "{}", i),
+            Nodes::ConstantNode(Constant::Pi) => write!(f, "pi"),
+            Nodes::ConstantNode(Constant::E) => write!(f, "e"),
             Nodes::AddNode { lhs, rhs } => write!(f, "({} + {})", lhs, rhs),
             Nodes::SubNode { lhs, rhs } => write!(f, "({} - {})", lhs, rhs),
             Nodes::MulNode { lhs, rhs } => write!(f, "({} * {})", lhs, rhs),
@@ -56,13 +99,16 @@ impl Display for Nodes {
             Nodes::PowNode { base, exponent } => {
                 write!(f, "({} ^ {})", base, exponent)
             }
+            Nodes::LogNode { base, argument } => write!(f, "log({}, {})", base, argument),
             Nodes::EquationNode { lhs, rhs } => write!(f, "({} = {})", lhs, rhs),
             Nodes::MinusNode(value) => write!(f, "-({})", value),
+            Nodes::AbsNode(value) => write!(f, "|{}|", value),
             Nodes::SubstituteNode(c, v) => match v {
                 Some(v) => write!(f, "  substitute {} with {}", c, v),
                 None => write!(f, "solve for {}", c),
             },
             Nodes::SolutionNode { eq, at } => write!(f, "{} @ {}", eq, at),
+            Nodes::AssignNode { variable, value } => write!(f, "{} := {}", variable, value),
         }
     }
 }
@@ -70,6 +116,8 @@ impl Display for Nodes {
 pub struct Parser {
     // statement: String,
     tokenizer: Lexer,
+    // between `|` bars, a `|` can only close them unless it is inside parentheses
+    in_abs: bool,
 }
 
 impl Parser {
@@ -77,65 +125,157 @@ impl Parser {
         Parser {
             // statement: statement.clone(),
             tokenizer: Lexer::new(statement),
+            in_abs: false,
         }
     }
 }
 
 impl Parser {
-    pub fn parse(&mut self) -> Result<Nodes, Error> {
+    pub fn parse_program(&mut self) -> Result<Vec<Nodes>, Error> {
+        Ok(self
+            .parse_program_spanned()?
+            .into_iter()
+            .map(|statement| statement.node)
+            .collect())
+    }
+
+    /// Same as `parse_program`, keeping the byte range of every statement and every node in it
+    pub fn parse_program_spanned(&mut self) -> Result<Vec<Spanned<Nodes>>, Error> {
+        let mut statements: Vec<Spanned<Nodes>> = Vec::new();
         self.tokenizer.next();
-        let ast =  self.solution();
-        if let Token::NoneToken = self.tokenizer.present()? {
-            ast
-        } else {
-            Err(Error::ParserError { token: self.tokenizer.present()?, message: "Expected end of line, but got a token" })
+
+        loop {
+            match self.tokenizer.present()? {
+                Token::NoneToken => break,
+                Token::SemicolonToken => {
+                    // empty statement
+                    self.tokenizer.next();
+                    continue;
+                }
+                _ => {}
+            }
+
+            let statement: Spanned<Nodes> =
+                self.statement().map_err(|e| Error::StatementError {
+                    statement: statements.len() + 1,
+                    error: Box::new(e),
+                })?;
+            statements.push(statement);
+
+            match self.tokenizer.present()? {
+                Token::NoneToken => break,
+                Token::SemicolonToken => {
+                    self.tokenizer.next();
+                }
+                n => {
+                    return Err(Error::StatementError {
+                        statement: statements.len(),
+                        error: Box::new(Error::ParserError {
+                            token: n,
+                            message: "Expected ';' or end of line, but got a token",
+                            span: self.tokenizer.span(),
+                        }),
+                    });
+                }
+            }
+        }
+
+        Ok(statements)
+    }
+
+    // `a := <solution>` or a solution
+    fn statement(&mut self) -> Result<Spanned<Nodes>, Error> {
+        let span: Range<usize> = self.tokenizer.span();
+        let eq: Spanned<Nodes> = self.solution()?;
+
+        if let Token::AssignToken = self.tokenizer.present()? {
+            let variable: char = match eq.node {
+                Nodes::VariableNode(i) => i,
+                _ => {
+                    return Err(Error::ParserError {
+                        token: Token::AssignToken,
+                        message: "Expected a single variable before ':='",
+                        span: span.start..self.tokenizer.span().end,
+                    });
+                }
+            };
+            self.tokenizer.next();
+            let value: Spanned<Nodes> = self.solution()?;
+            return Ok(self.spanned(
+                Nodes::AssignNode {
+                    variable,
+                    value: Box::new(value),
+                },
+                span.start,
+            ));
         }
+
+        Ok(eq)
     }
 
-    fn solution(&mut self) -> Result<Nodes, Error> {
-        let eq: Nodes = self.equation()?;
+    fn solution(&mut self) -> Result<Spanned<Nodes>, Error> {
+        let eq: Spanned<Nodes> = self.equation()?;
 
         if let Token::ForToken = self.tokenizer.present()? {
             self.tokenizer.next();
-            return Ok(Nodes::SolutionNode {
-                eq: Box::new(eq),
-                at: Box::new(self.substitute()?),
-            });
+            let start: usize = eq.span.start;
+            let at: Spanned<Nodes> = self.substitute()?;
+            return Ok(self.spanned(
+                Nodes::SolutionNode {
+                    eq: Box::new(eq),
+                    at: Box::new(at),
+                },
+                start,
+            ));
         }
 
         return Ok(eq);
     }
 
-    fn equation(&mut self) -> Result<Nodes, Error> {
-        let eq: Nodes = self.expression()?;
+    fn equation(&mut self) -> Result<Spanned<Nodes>, Error> {
+        let eq: Spanned<Nodes> = self.expression()?;
 
         if let Token::EqualToken = self.tokenizer.present()? {
             self.tokenizer.next();
-            return Ok(Nodes::EquationNode {
-                lhs: Box::new(eq),
-                rhs: Box::new(self.expression()?),
-            });
+            let start: usize = eq.span.start;
+            let rhs: Spanned<Nodes> = self.expression()?;
+            return Ok(self.spanned(
+                Nodes::EquationNode {
+                    lhs: Box::new(eq),
+                    rhs: Box::new(rhs),
+                },
+                start,
+            ));
         }
 
         return Ok(eq);
     }
 
-    fn expression(&mut self) -> Result<Nodes, Error> {
-        let mut eq: Nodes = self.term()?;
+    fn expression(&mut self) -> Result<Spanned<Nodes>, Error> {
+        let mut eq: Spanned<Nodes> = self.term()?;
+        let start: usize = eq.span.start;
 
         loop {
             if let Token::PlusToken = self.tokenizer.present()? {
                 self.tokenizer.next();
-                eq = Nodes::AddNode {
-                    lhs: Box::new(eq),
-                    rhs: Box::new(self.term()?),
-                };
+                let rhs: Spanned<Nodes> = self.term()?;
+                eq = self.spanned(
+                    Nodes::AddNode {
+                        lhs: Box::new(eq),
+                        rhs: Box::new(rhs),
+                    },
+                    start,
+                );
             } else if let Token::MinusToken = self.tokenizer.present()? {
                 self.tokenizer.next();
-                eq = Nodes::SubNode {
-                    lhs: Box::new(eq),
-                    rhs: Box::new(self.term()?),
-                };
+                let rhs: Spanned<Nodes> = self.term()?;
+                eq = self.spanned(
+                    Nodes::SubNode {
+                        lhs: Box::new(eq),
+                        rhs: Box::new(rhs),
+                    },
+                    start,
+                );
             } else {
                 break;
             }
@@ -144,22 +284,31 @@ impl Parser {
         return Ok(eq);
     }
 
-    fn term(&mut self) -> Result<Nodes, Error> {
-        let mut eq: Nodes = self.exponent()?;
+    fn term(&mut self) -> Result<Spanned<Nodes>, Error> {
+        let mut eq: Spanned<Nodes> = self.exponent()?;
+        let start: usize = eq.span.start;
 
         loop {
             if let Token::MulToken = self.tokenizer.present()? {
                 self.tokenizer.next();
-                eq = Nodes::MulNode {
-                    lhs: Box::new(eq),
-                    rhs: Box::new(self.exponent()?),
-                };
+                let rhs: Spanned<Nodes> = self.exponent()?;
+                eq = self.spanned(
+                    Nodes::MulNode {
+                        lhs: Box::new(eq),
+                        rhs: Box::new(rhs),
+                    },
+                    start,
+                );
             } else if let Token::DivToken = self.tokenizer.present()? {
                 self.tokenizer.next();
-                eq = Nodes::DivNode {
-                    numerator: Box::new(eq),
-                    denominator: Box::new(self.exponent()?),
-                };
+                let denominator: Spanned<Nodes> = self.exponent()?;
+                eq = self.spanned(
+                    Nodes::DivNode {
+                        numerator: Box::new(eq),
+                        denominator: Box::new(denominator),
+                    },
+                    start,
+                );
             } else {
                 break;
             }
@@ -168,37 +317,55 @@ impl Parser {
         return Ok(eq);
     }
 
-    fn exponent(&mut self) -> Result<Nodes, Error> {
-        let mut eq: Nodes = self.factor()?;
+    fn exponent(&mut self) -> Result<Spanned<Nodes>, Error> {
+        let eq: Spanned<Nodes> = self.factor()?;
 
-        loop {
-            if let Token::PowToken = self.tokenizer.present()? {
-                self.tokenizer.next();
-                eq = Nodes::PowNode {
+        // right associative: 2 ^ 3 ^ 2 -> 2 ^ (3 ^ 2)
+        if let Token::PowToken = self.tokenizer.present()? {
+            self.tokenizer.next();
+            let start: usize = eq.span.start;
+            let exponent: Spanned<Nodes> = self.exponent()?;
+            return Ok(self.spanned(
+                Nodes::PowNode {
                     base: Box::new(eq),
-                    exponent: Box::new(self.factor()?),
-                };
-            } else {
-                break;
-            }
+                    exponent: Box::new(exponent),
+                },
+                start,
+            ));
         }
 
         return Ok(eq);
     }
 
-    fn factor(&mut self) -> Result<Nodes, Error> {
+    fn factor(&mut self) -> Result<Spanned<Nodes>, Error> {
+        let start: usize = self.tokenizer.span().start;
+
         match self.tokenizer.present()? {
             Token::IntegerToken(i) => {
                 self.tokenizer.next();
-                return Ok(Nodes::IntegerNode(i));
+                return Ok(self.spanned(Nodes::IntegerNode(i), start));
             }
-            Token::DecimalToken(i) => {
+            Token::BigIntegerToken => {
+                let i: String = String::from(self.tokenizer.text());
                 self.tokenizer.next();
-                return Ok(Nodes::DecimalNode(i));
+                return Ok(self.spanned(Nodes::BigIntegerNode(i), start));
+            }
+            Token::DecimalToken => {
+                let i: String = String::from(self.tokenizer.text());
+                self.tokenizer.next();
+                return Ok(self.spanned(Nodes::DecimalNode(i), start));
             }
             Token::VariableToken(i) => {
                 self.tokenizer.next();
-                return Ok(Nodes::VariableNode(i));
+                return Ok(self.spanned(Nodes::VariableNode(i), start));
+            }
+            Token::ConstantToken(i) => {
+                self.tokenizer.next();
+                return Ok(self.spanned(Nodes::ConstantNode(i), start));
+            }
+            Token::LogToken => {
+                self.tokenizer.next();
+                return self.log(start);
             }
             Token::PlusToken => {
                 self.tokenizer.next();
@@ -206,24 +373,49 @@ impl Parser {
             }
             Token::MinusToken => {
                 self.tokenizer.next();
-                return Ok(Nodes::MinusNode(Box::new(self.factor()?)));
+                let value: Spanned<Nodes> = self.factor()?;
+                return Ok(self.spanned(Nodes::MinusNode(Box::new(value)), start));
             }
             Token::LeftParenToken => {
                 self.tokenizer.next();
 
-                let eq: Nodes = self.expression()?;
-
-                match self.tokenizer.present()? {
-                    Token::RightParenToken => {
-                        self.tokenizer.next();
-                        return Ok(eq);
-                    }
+                let in_abs: bool = self.in_abs;
+                self.in_abs = false;
+                let eq: Spanned<Nodes> = self.expression()?;
+                self.in_abs = in_abs;
 
-                    _ => {}
+                if let Token::RightParenToken = self.tokenizer.present()? {
+                    self.tokenizer.next();
+                    return Ok(eq);
                 }
                 return Err(Error::ParserError {
                     token: self.tokenizer.present()?,
                     message: "Expected ')'",
+                    span: self.tokenizer.span(),
+                });
+            }
+            Token::BarToken => {
+                if self.in_abs {
+                    return Err(Error::ParserError {
+                        token: Token::BarToken,
+                        message: "Nested '|' must be inside parentheses, like |(|x| - 1)|",
+                        span: self.tokenizer.span(),
+                    });
+                }
+                self.tokenizer.next();
+
+                self.in_abs = true;
+                let eq: Spanned<Nodes> = self.expression()?;
+                self.in_abs = false;
+
+                if let Token::BarToken = self.tokenizer.present()? {
+                    self.tokenizer.next();
+                    return Ok(self.spanned(Nodes::AbsNode(Box::new(eq)), start));
+                }
+                return Err(Error::ParserError {
+                    token: self.tokenizer.present()?,
+                    message: "Expected '|'",
+                    span: self.tokenizer.span(),
                 });
             }
             _ => {}
@@ -232,10 +424,70 @@ impl Parser {
         return Err(Error::ParserError {
             token: self.tokenizer.present()?,
             message: "Expected variable or integer or decimal token but got some thing else.",
+            span: self.tokenizer.span(),
         });
     }
 
-    fn substitute(&mut self) -> Result<Nodes, Error> {
+    // log(base, argument), `start` is where the `log` token began
+    fn log(&mut self, start: usize) -> Result<Spanned<Nodes>, Error> {
+        match self.tokenizer.present()? {
+            Token::LeftParenToken => {
+                self.tokenizer.next();
+            }
+            n => {
+                return Err(Error::ParserError {
+                    token: n,
+                    message: "Expected '(' after log",
+                    span: self.tokenizer.span(),
+                });
+            }
+        }
+
+        // the arguments are bracketed like a parenthesized expression, so |log(|x|, 2)| parses
+        let in_abs: bool = self.in_abs;
+        self.in_abs = false;
+        let base: Spanned<Nodes> = self.expression()?;
+
+        match self.tokenizer.present()? {
+            Token::CommaToken => {
+                self.tokenizer.next();
+            }
+            n => {
+                return Err(Error::ParserError {
+                    token: n,
+                    message: "Expected ',' between the base and the argument of log",
+                    span: self.tokenizer.span(),
+                });
+            }
+        }
+
+        let argument: Spanned<Nodes> = self.expression()?;
+        self.in_abs = in_abs;
+
+        match self.tokenizer.present()? {
+            Token::RightParenToken => {
+                self.tokenizer.next();
+            }
+            n => {
+                return Err(Error::ParserError {
+                    token: n,
+                    message: "Expected ')'",
+                    span: self.tokenizer.span(),
+                });
+            }
+        }
+
+        Ok(self.spanned(
+            Nodes::LogNode {
+                base: Box::new(base),
+                argument: Box::new(argument),
+            },
+            start,
+        ))
+    }
+
+    fn substitute(&mut self) -> Result<Spanned<Nodes>, Error> {
+        let start: usize = self.tokenizer.span().start;
         let variable: char = match self.tokenizer.present()? {
             Token::VariableToken(i) => i,
             n => {
@@ -243,6 +495,7 @@ impl Parser {
                     token: n,
                     message:
                         "Expected variable token after @ to solve for, but found something else",
+                    span: self.tokenizer.span(),
                 });
             }
         };
@@ -252,16 +505,20 @@ impl Parser {
                 let x: Token = x?;
 
                 if let Token::CommaToken = x {
+                } else if let Token::SemicolonToken = x {
+                    // the statement ends here, leave the separator for the program parser
+                    return Ok(self.spanned(Nodes::SubstituteNode(variable, None), start));
                 } else {
                     return Err(Error::ParserError {
                         token: x,
                         message: "Expected end of line or comma, but found something else",
+                        span: self.tokenizer.span(),
                     });
                 }
             }
             _ => {
                 self.tokenizer.next();
-                return Ok(Nodes::SubstituteNode(variable, None));
+                return Ok(self.spanned(Nodes::SubstituteNode(variable, None), start));
             }
         };
 
@@ -273,13 +530,16 @@ impl Parser {
                     Nodes::VariableNode(i)
                 } else if let Token::IntegerToken(i) = x {
                     Nodes::IntegerNode(i)
-                } else if let Token::DecimalToken(i) = x {
-                    Nodes::DecimalNode(i)
+                } else if let Token::BigIntegerToken = x {
+                    Nodes::BigIntegerNode(String::from(self.tokenizer.text()))
+                } else if let Token::DecimalToken = x {
+                    Nodes::DecimalNode(String::from(self.tokenizer.text()))
                 } else {
                     return Err(Error::ParserError {
                         token: x,
                         message:
                             "Expected variable token after @ to solve for, but found something else",
+                        span: self.tokenizer.span(),
                     });
                 }
             }
@@ -287,14 +547,150 @@ impl Parser {
                 return Err(Error::ParserError {
                     token: Token::NoneToken,
                     message: "Expected variable token after @ to solve for, but found nothing",
+                    span: self.tokenizer.span(),
                 });
             }
         };
 
+        let substitute_value: Spanned<Nodes> = Spanned {
+            node: substitute_value,
+            span: self.tokenizer.span(),
+        };
         self.tokenizer.next();
-        return Ok(Nodes::SubstituteNode(
-            variable,
-            Some(Box::new(substitute_value)),
+        return Ok(self.spanned(
+            Nodes::SubstituteNode(variable, Some(Box::new(substitute_value))),
+            start,
+        ));
+    }
+
+    // `node` spanning from `start` to the end of the last token it consumed
+    fn spanned(&self, node: Nodes, start: usize) -> Spanned<Nodes> {
+        Spanned {
+            node,
+            span: start..self.tokenizer.previous_end(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(statement: &str) -> Result<Vec<Nodes>, Error> {
+        Parser::new(String::from(statement)).parse_program()
+    }
+
+    fn node(n: Nodes) -> Box<Spanned<Nodes>> {
+        Box::new(Spanned::from(n))
+    }
+
+    fn variable(c: char) -> Box<Spanned<Nodes>> {
+        node(Nodes::VariableNode(c))
+    }
+
+    fn integer(i: i64) -> Box<Spanned<Nodes>> {
+        node(Nodes::IntegerNode(i))
+    }
+
+    #[test]
+    fn test_parse_precedence_1() {
+        // 2 * x + 3 ^ 2 ^ y
+        assert_eq!(
+            parse("2 * x + 3 ^ 2 ^ y").unwrap(),
+            vec![Nodes::AddNode {
+                lhs: node(Nodes::MulNode {
+                    lhs: integer(2),
+                    rhs: variable('x'),
+                }),
+                rhs: node(Nodes::PowNode {
+                    base: integer(3),
+                    exponent: node(Nodes::PowNode {
+                        base: integer(2),
+                        exponent: variable('y'),
+                    }),
+                }),
+            }]
+        );
+
+        assert_eq!(
+            parse("-(x - 1) / |y|").unwrap(),
+            vec![Nodes::DivNode {
+                numerator: node(Nodes::MinusNode(node(Nodes::SubNode {
+                    lhs: variable('x'),
+                    rhs: integer(1),
+                }))),
+                denominator: node(Nodes::AbsNode(variable('y'))),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_statements_1() {
+        assert_eq!(
+            parse("a = x; x + 1 = 3 @ x; ; x @ x, 2").unwrap(),
+            vec![
+                Nodes::EquationNode {
+                    lhs: variable('a'),
+                    rhs: variable('x'),
+                },
+                Nodes::SolutionNode {
+                    eq: node(Nodes::EquationNode {
+                        lhs: node(Nodes::AddNode {
+                            lhs: variable('x'),
+                            rhs: integer(1),
+                        }),
+                        rhs: integer(3),
+                    }),
+                    at: node(Nodes::SubstituteNode('x', None)),
+                },
+                Nodes::SolutionNode {
+                    eq: variable('x'),
+                    at: node(Nodes::SubstituteNode('x', Some(integer(2)))),
+                },
+            ]
+        );
+
+        assert!(matches!(
+            parse("x +"),
+            Err(Error::StatementError { statement: 1, .. })
         ));
     }
+
+    #[test]
+    fn test_parse_spans_1() {
+        let statements = Parser::new(String::from("2 + x; y = 3 @ y"))
+            .parse_program_spanned()
+            .unwrap();
+        let spans: Vec<Range<usize>> = statements.into_iter().map(|s| s.span).collect();
+        assert_eq!(spans, vec![0..5, 7..16]);
+
+        let statements = Parser::new(String::from("-(x - 1) / |y|"))
+            .parse_program_spanned()
+            .unwrap();
+        match &statements[0].node {
+            Nodes::DivNode {
+                numerator,
+                denominator,
+            } => {
+                assert_eq!(numerator.span, 0..8);
+                assert_eq!(denominator.span, 11..14);
+                match &numerator.node {
+                    Nodes::MinusNode(value) => assert_eq!(value.span, 2..7),
+                    n => panic!("expected a MinusNode, got {}", n),
+                }
+            }
+            n => panic!("expected a DivNode, got {}", n),
+        }
+
+        match parse("2 + x )") {
+            Err(Error::StatementError { error, .. }) => match *error {
+                Error::ParserError { token, span, .. } => {
+                    assert_eq!(token, Token::RightParenToken);
+                    assert_eq!(span, 6..7);
+                }
+                e => panic!("expected a parser error, got {}", e),
+            },
+            r => panic!("expected a parser error, got {:?}", r),
+        }
+    }
 }
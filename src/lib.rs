@@ -1,6 +1,17 @@
 pub mod equation;
+pub mod eval;
+pub mod lang;
+#[cfg(not(feature = "rug"))]
+mod light;
 pub mod math;
 pub mod number;
+pub mod polynomial;
+#[cfg(any(test, feature = "test-util"))]
+pub mod random;
+// without `test-util` only `PartEquation::numerically_equal` uses it
+#[cfg(not(any(test, feature = "test-util")))]
+#[allow(dead_code)]
+mod random;
 
 pub fn get_version() -> &'static str {
     "0.0.1"
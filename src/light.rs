@@ -0,0 +1,878 @@
+//! Stand-ins for the parts of `rug` that `Number` uses, selected when the `rug` feature is
+//! disabled. Integers and rationals are backed by `i128` and floats by `f64`, so results are
+//! only exact for small inputs.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::num::{ParseFloatError, ParseIntError, TryFromIntError};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, ShrAssign, Sub, SubAssign};
+use std::str::FromStr;
+
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Integer(i128);
+
+/// Always kept in lowest terms with a positive denominator
+#[derive(Clone, PartialEq, Eq)]
+pub struct Rational {
+    numer: Integer,
+    denom: Integer,
+}
+
+#[derive(Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct Float(f64);
+
+#[derive(Clone, Default, PartialEq)]
+pub struct Complex {
+    re: Float,
+    im: Float,
+}
+
+pub enum Constant {
+    Pi,
+}
+
+pub trait Pow<Rhs> {
+    type Output;
+
+    fn pow(self, rhs: Rhs) -> Self::Output;
+}
+
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs()
+}
+
+impl Integer {
+    pub fn is_odd(&self) -> bool {
+        self.0 % 2 != 0
+    }
+
+    pub fn square_mut(&mut self) {
+        self.0 *= self.0;
+    }
+
+    pub fn to_u32(&self) -> Option<u32> {
+        u32::try_from(self.0).ok()
+    }
+
+    pub fn to_i64(&self) -> Option<i64> {
+        i64::try_from(self.0).ok()
+    }
+
+    pub fn checked_add(&self, rhs: &Integer) -> Option<Integer> {
+        self.0.checked_add(rhs.0).map(Integer)
+    }
+
+    pub fn checked_sub(&self, rhs: &Integer) -> Option<Integer> {
+        self.0.checked_sub(rhs.0).map(Integer)
+    }
+
+    pub fn checked_mul(&self, rhs: &Integer) -> Option<Integer> {
+        self.0.checked_mul(rhs.0).map(Integer)
+    }
+
+    pub fn checked_neg(&self) -> Option<Integer> {
+        self.0.checked_neg().map(Integer)
+    }
+
+    /// `self ^ exponent`, `None` if the exponent is negative or the power does not fit
+    pub fn checked_pow(&self, exponent: &Integer) -> Option<Integer> {
+        match self.0 {
+            0 | 1 if exponent.0 > 0 => Some(self.clone()),
+            -1 if exponent.0 > 0 => Some(Integer(if exponent.is_odd() { -1 } else { 1 })),
+            _ => self
+                .0
+                .checked_pow(u32::try_from(exponent.0).ok()?)
+                .map(Integer),
+        }
+    }
+
+    /// `self ^ exponent` modulo `modulo` in `0..|modulo|`, fails if the modulo is zero or the
+    /// exponent is negative and `self` has no inverse
+    pub fn pow_mod(self, exponent: &Self, modulo: &Self) -> Result<Self, Self> {
+        if modulo.0 == 0 {
+            return Err(self);
+        }
+        let m: u128 = modulo.0.unsigned_abs();
+        let mut base: u128 = self.0.rem_euclid(m as i128) as u128;
+        if exponent.0 < 0 {
+            base = match inverse_mod(base, m) {
+                Some(inverse) => inverse,
+                None => return Err(self),
+            };
+        }
+
+        let mut e: u128 = exponent.0.unsigned_abs();
+        let mut result: u128 = 1 % m;
+        while e > 0 {
+            if e & 1 == 1 {
+                result = mul_mod(result, base, m);
+            }
+            base = mul_mod(base, base, m);
+            e >>= 1;
+        }
+        Ok(Integer(result as i128))
+    }
+}
+
+// a * b mod m by doubling, the product itself may not fit
+fn mul_mod(mut a: u128, mut b: u128, m: u128) -> u128 {
+    let mut result: u128 = 0;
+    a %= m;
+    while b > 0 {
+        if b & 1 == 1 {
+            result = (result + a) % m;
+        }
+        a = (a + a) % m;
+        b >>= 1;
+    }
+    result
+}
+
+fn inverse_mod(a: u128, m: u128) -> Option<u128> {
+    // extended euclid on (a, m), tracking the coefficient of a modulo m
+    let (mut r0, mut r1): (u128, u128) = (m, a);
+    let (mut t0, mut t1): (u128, u128) = (0, 1);
+    while r1 != 0 {
+        let q: u128 = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (t0, t1) = (t1, (t0 + m - mul_mod(q, t1, m)) % m);
+    }
+    if r0 != 1 {
+        return None;
+    }
+    Some(t0 % m)
+}
+
+impl Rational {
+    fn new(numer: i128, denom: i128) -> Self {
+        if denom == 0 {
+            panic!("division by zero");
+        }
+
+        let divisor: i128 = gcd(numer, denom) * denom.signum();
+        Rational {
+            numer: Integer(numer / divisor),
+            denom: Integer(denom / divisor),
+        }
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denom.0 == 1
+    }
+
+    pub fn numer(&self) -> &Integer {
+        &self.numer
+    }
+
+    pub fn denom(&self) -> &Integer {
+        &self.denom
+    }
+
+    pub fn into_numer_denom(self) -> (Integer, Integer) {
+        (self.numer, self.denom)
+    }
+
+    pub fn square_mut(&mut self) {
+        self.numer.square_mut();
+        self.denom.square_mut();
+    }
+
+    pub fn floor(self) -> Self {
+        Rational::new(self.numer.0.div_euclid(self.denom.0), 1)
+    }
+}
+
+impl Float {
+    pub fn with_val<T: Into<Float>>(_precision: u32, value: T) -> Self {
+        value.into()
+    }
+
+    pub fn parse(src: &str) -> Result<Float, ParseFloatError> {
+        Ok(Float(src.parse()?))
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.0
+    }
+
+    pub fn exp(self) -> Self {
+        Float(self.0.exp())
+    }
+
+    pub fn ln(self) -> Self {
+        Float(self.0.ln())
+    }
+
+    pub fn round(self) -> Self {
+        Float(self.0.round())
+    }
+
+    pub fn floor(self) -> Self {
+        Float(self.0.floor())
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0 == 0.0
+    }
+
+    pub fn is_nan(&self) -> bool {
+        self.0.is_nan()
+    }
+
+    pub fn is_finite(&self) -> bool {
+        self.0.is_finite()
+    }
+
+    pub fn is_sign_negative(&self) -> bool {
+        self.0.is_sign_negative()
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.0.is_finite() && self.0.fract() == 0.0
+    }
+
+    pub fn to_integer(self) -> Option<Integer> {
+        if !self.0.is_finite() {
+            return None;
+        }
+        Some(Integer(self.0.trunc() as i128))
+    }
+}
+
+impl Complex {
+    pub fn with_val<T: Into<Complex>>(_precision: u32, value: T) -> Self {
+        value.into()
+    }
+
+    pub fn real(&self) -> &Float {
+        &self.re
+    }
+
+    pub fn imag(&self) -> &Float {
+        &self.im
+    }
+
+    pub fn square_mut(&mut self) {
+        *self = self.clone() * self.clone();
+    }
+}
+
+// principal value, through the polar form
+impl Pow<&Complex> for Complex {
+    type Output = Complex;
+
+    fn pow(self, rhs: &Complex) -> Self::Output {
+        if self.re.0 == 0.0 && self.im.0 == 0.0 {
+            return self;
+        }
+
+        let (ln_r, theta) = (self.re.0.hypot(self.im.0).ln(), self.im.0.atan2(self.re.0));
+        let re: f64 = rhs.re.0 * ln_r - rhs.im.0 * theta;
+        let im: f64 = rhs.re.0 * theta + rhs.im.0 * ln_r;
+        Complex {
+            re: Float(re.exp() * im.cos()),
+            im: Float(re.exp() * im.sin()),
+        }
+    }
+}
+
+impl Pow<Float> for &Float {
+    type Output = Float;
+
+    fn pow(self, rhs: Float) -> Self::Output {
+        Float(self.0.powf(rhs.0))
+    }
+}
+
+impl Default for Rational {
+    fn default() -> Self {
+        Rational::new(0, 1)
+    }
+}
+
+// conversions
+
+/// Conversion used to evaluate operations that mix in a float
+trait ToF64 {
+    fn float(&self) -> f64;
+}
+
+impl ToF64 for Integer {
+    fn float(&self) -> f64 {
+        self.0 as f64
+    }
+}
+
+impl ToF64 for Rational {
+    fn float(&self) -> f64 {
+        self.numer.0 as f64 / self.denom.0 as f64
+    }
+}
+
+impl ToF64 for &Integer {
+    fn float(&self) -> f64 {
+        (*self).float()
+    }
+}
+
+impl ToF64 for &Rational {
+    fn float(&self) -> f64 {
+        (*self).float()
+    }
+}
+
+impl ToF64 for f32 {
+    fn float(&self) -> f64 {
+        f64::from(*self)
+    }
+}
+
+macro_rules! integer_from {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Integer {
+                fn from(value: $t) -> Self {
+                    Integer(i128::from(value))
+                }
+            }
+
+            impl From<$t> for Rational {
+                fn from(value: $t) -> Self {
+                    Rational::new(i128::from(value), 1)
+                }
+            }
+
+            impl From<($t, $t)> for Rational {
+                fn from(value: ($t, $t)) -> Self {
+                    Rational::new(i128::from(value.0), i128::from(value.1))
+                }
+            }
+
+            impl From<$t> for Float {
+                fn from(value: $t) -> Self {
+                    Float(value as f64)
+                }
+            }
+
+            impl ToF64 for $t {
+                fn float(&self) -> f64 {
+                    *self as f64
+                }
+            }
+        )*
+    };
+}
+
+integer_from!(i8, i16, i32, i64, i128, u8, u16, u32, u64);
+
+impl From<isize> for Integer {
+    fn from(value: isize) -> Self {
+        Integer(value as i128)
+    }
+}
+
+impl From<usize> for Integer {
+    fn from(value: usize) -> Self {
+        Integer(value as i128)
+    }
+}
+
+impl TryFrom<u128> for Integer {
+    type Error = TryFromIntError;
+
+    fn try_from(value: u128) -> Result<Self, Self::Error> {
+        Ok(Integer(i128::try_from(value)?))
+    }
+}
+
+impl From<&Integer> for Integer {
+    fn from(value: &Integer) -> Self {
+        value.clone()
+    }
+}
+
+impl From<Integer> for Rational {
+    fn from(value: Integer) -> Self {
+        Rational::new(value.0, 1)
+    }
+}
+
+impl From<&Rational> for Rational {
+    fn from(value: &Rational) -> Self {
+        value.clone()
+    }
+}
+
+impl From<(Integer, Integer)> for Rational {
+    fn from(value: (Integer, Integer)) -> Self {
+        Rational::new(value.0 .0, value.1 .0)
+    }
+}
+
+macro_rules! float_from {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Float {
+                fn from(value: $t) -> Self {
+                    Float(value.float())
+                }
+            }
+        )*
+    };
+}
+
+float_from!(Integer, &Integer, Rational, &Rational);
+
+impl From<f32> for Float {
+    fn from(value: f32) -> Self {
+        Float(f64::from(value))
+    }
+}
+
+impl From<f64> for Float {
+    fn from(value: f64) -> Self {
+        Float(value)
+    }
+}
+
+impl From<Constant> for Float {
+    fn from(value: Constant) -> Self {
+        match value {
+            Constant::Pi => Float(std::f64::consts::PI),
+        }
+    }
+}
+
+macro_rules! complex_from {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Complex {
+                fn from(value: $t) -> Self {
+                    Complex {
+                        re: Float::from(value),
+                        im: Float(0.0),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+complex_from!(Integer, &Integer, Rational, &Rational, i32, i64, f32, f64);
+
+impl From<Float> for Complex {
+    fn from(value: Float) -> Self {
+        Complex {
+            re: value,
+            im: Float(0.0),
+        }
+    }
+}
+
+impl From<&Float> for Complex {
+    fn from(value: &Float) -> Self {
+        Complex::from(*value)
+    }
+}
+
+impl From<(Float, Float)> for Complex {
+    fn from(value: (Float, Float)) -> Self {
+        Complex {
+            re: value.0,
+            im: value.1,
+        }
+    }
+}
+
+impl FromStr for Integer {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Integer(s.parse()?))
+    }
+}
+
+// arithmetic
+
+impl Add<Rational> for Rational {
+    type Output = Rational;
+
+    fn add(self, rhs: Rational) -> Self::Output {
+        Rational::new(
+            self.numer.0 * rhs.denom.0 + rhs.numer.0 * self.denom.0,
+            self.denom.0 * rhs.denom.0,
+        )
+    }
+}
+
+impl Sub<Rational> for Rational {
+    type Output = Rational;
+
+    fn sub(self, rhs: Rational) -> Self::Output {
+        Rational::new(
+            self.numer.0 * rhs.denom.0 - rhs.numer.0 * self.denom.0,
+            self.denom.0 * rhs.denom.0,
+        )
+    }
+}
+
+impl Mul<Rational> for Rational {
+    type Output = Rational;
+
+    fn mul(self, rhs: Rational) -> Self::Output {
+        Rational::new(self.numer.0 * rhs.numer.0, self.denom.0 * rhs.denom.0)
+    }
+}
+
+impl Div<Rational> for Rational {
+    type Output = Rational;
+
+    fn div(self, rhs: Rational) -> Self::Output {
+        Rational::new(self.numer.0 * rhs.denom.0, self.denom.0 * rhs.numer.0)
+    }
+}
+
+macro_rules! binary {
+    ($trait:ident, $method:ident, $lhs:ty, $rhs:ty, $out:ty, |$a:ident, $b:ident| $body:expr) => {
+        impl $trait<$rhs> for $lhs {
+            type Output = $out;
+
+            fn $method(self, rhs: $rhs) -> Self::Output {
+                let ($a, $b) = (self, rhs);
+                $body
+            }
+        }
+    };
+}
+
+macro_rules! assign {
+    ($trait:ident, $method:ident, $op:tt, $lhs:ty, $($rhs:ty),*) => {
+        $(
+            impl $trait<$rhs> for $lhs {
+                fn $method(&mut self, rhs: $rhs) {
+                    *self = self.clone() $op rhs.clone();
+                }
+            }
+        )*
+    };
+}
+
+// integers stay integers (`/` truncates, like rug), mixing in a rational gives a rational and
+// mixing in a float gives a float
+macro_rules! arithmetic {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $op:tt) => {
+        binary!($trait, $method, Integer, Integer, Integer, |a, b| Integer(a.0 $op b.0));
+        binary!($trait, $method, Integer, i32, Integer, |a, b| a $op Integer::from(b));
+        binary!($trait, $method, Integer, i64, Integer, |a, b| a $op Integer::from(b));
+        binary!($trait, $method, Integer, Rational, Rational, |a, b| Rational::from(a) $op b);
+        binary!($trait, $method, Integer, Float, Float, |a, b| Float(a.float() $op b.0));
+
+        binary!($trait, $method, Rational, Integer, Rational, |a, b| a $op Rational::from(b));
+        binary!($trait, $method, Rational, i32, Rational, |a, b| a $op Rational::from(b));
+        binary!($trait, $method, Rational, i64, Rational, |a, b| a $op Rational::from(b));
+        binary!($trait, $method, Rational, Float, Float, |a, b| Float(a.float() $op b.0));
+
+        binary!($trait, $method, Float, Integer, Float, |a, b| Float(a.0 $op b.float()));
+        binary!($trait, $method, Float, Rational, Float, |a, b| Float(a.0 $op b.float()));
+        binary!($trait, $method, Float, Float, Float, |a, b| Float(a.0 $op b.0));
+        binary!($trait, $method, Float, i32, Float, |a, b| Float(a.0 $op b.float()));
+        binary!($trait, $method, Float, i64, Float, |a, b| Float(a.0 $op b.float()));
+        binary!($trait, $method, Float, f32, Float, |a, b| Float(a.0 $op b.float()));
+        binary!($trait, $method, Float, f64, Float, |a, b| Float(a.0 $op b));
+
+        assign!($assign_trait, $assign_method, $op, Integer, &Integer, i32);
+        assign!($assign_trait, $assign_method, $op, Rational, &Integer, &Rational);
+        assign!($assign_trait, $assign_method, $op, Float, &Integer, &Rational, &Float);
+    };
+}
+
+arithmetic!(Add, add, AddAssign, add_assign, +);
+arithmetic!(Sub, sub, SubAssign, sub_assign, -);
+arithmetic!(Mul, mul, MulAssign, mul_assign, *);
+arithmetic!(Div, div, DivAssign, div_assign, /);
+
+impl Add<Complex> for Complex {
+    type Output = Complex;
+
+    fn add(self, rhs: Complex) -> Self::Output {
+        Complex {
+            re: Float(self.re.0 + rhs.re.0),
+            im: Float(self.im.0 + rhs.im.0),
+        }
+    }
+}
+
+impl Sub<Complex> for Complex {
+    type Output = Complex;
+
+    fn sub(self, rhs: Complex) -> Self::Output {
+        Complex {
+            re: Float(self.re.0 - rhs.re.0),
+            im: Float(self.im.0 - rhs.im.0),
+        }
+    }
+}
+
+impl Mul<Complex> for Complex {
+    type Output = Complex;
+
+    fn mul(self, rhs: Complex) -> Self::Output {
+        Complex {
+            re: Float(self.re.0 * rhs.re.0 - self.im.0 * rhs.im.0),
+            im: Float(self.re.0 * rhs.im.0 + self.im.0 * rhs.re.0),
+        }
+    }
+}
+
+impl Div<Complex> for Complex {
+    type Output = Complex;
+
+    fn div(self, rhs: Complex) -> Self::Output {
+        let denominator: f64 = rhs.re.0 * rhs.re.0 + rhs.im.0 * rhs.im.0;
+        Complex {
+            re: Float((self.re.0 * rhs.re.0 + self.im.0 * rhs.im.0) / denominator),
+            im: Float((self.im.0 * rhs.re.0 - self.re.0 * rhs.im.0) / denominator),
+        }
+    }
+}
+
+assign!(AddAssign, add_assign, +, Complex, Complex);
+assign!(SubAssign, sub_assign, -, Complex, Complex);
+assign!(MulAssign, mul_assign, *, Complex, Complex, &Complex);
+assign!(DivAssign, div_assign, /, Complex, Complex);
+
+impl ShrAssign<u32> for Integer {
+    fn shr_assign(&mut self, rhs: u32) {
+        self.0 >>= rhs;
+    }
+}
+
+impl Neg for Integer {
+    type Output = Integer;
+
+    fn neg(self) -> Self::Output {
+        Integer(-self.0)
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Self::Output {
+        Rational::new(-self.numer.0, self.denom.0)
+    }
+}
+
+impl Neg for Complex {
+    type Output = Complex;
+
+    fn neg(self) -> Self::Output {
+        Complex {
+            re: -self.re,
+            im: -self.im,
+        }
+    }
+}
+
+impl Neg for Float {
+    type Output = Float;
+
+    fn neg(self) -> Self::Output {
+        Float(-self.0)
+    }
+}
+
+// comparisons
+
+impl PartialEq<i32> for Integer {
+    fn eq(&self, other: &i32) -> bool {
+        self.0 == i128::from(*other)
+    }
+}
+
+impl PartialOrd<i32> for Integer {
+    fn partial_cmp(&self, other: &i32) -> Option<Ordering> {
+        self.0.partial_cmp(&i128::from(*other))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        // denominators are positive, so cross multiplying keeps the order
+        match (
+            self.numer.0.checked_mul(other.denom.0),
+            other.numer.0.checked_mul(self.denom.0),
+        ) {
+            (Some(lhs), Some(rhs)) => lhs.partial_cmp(&rhs),
+            _ => self.float().partial_cmp(&other.float()),
+        }
+    }
+}
+
+macro_rules! compare {
+    ($lhs:ty, $rhs:ty, |$a:ident, $b:ident| ($x:expr, $y:expr)) => {
+        impl PartialEq<$rhs> for $lhs {
+            fn eq(&self, other: &$rhs) -> bool {
+                let ($a, $b) = (self, other);
+                $x == $y
+            }
+        }
+
+        impl PartialOrd<$rhs> for $lhs {
+            fn partial_cmp(&self, other: &$rhs) -> Option<Ordering> {
+                let ($a, $b) = (self, other);
+                $x.partial_cmp(&$y)
+            }
+        }
+    };
+}
+
+compare!(Integer, Rational, |a, b| (Rational::from(a.clone()), *b));
+compare!(Rational, Integer, |a, b| (*a, Rational::from(b.clone())));
+compare!(Integer, Float, |a, b| (a.float(), b.0));
+compare!(Float, Integer, |a, b| (a.0, b.float()));
+compare!(Rational, Float, |a, b| (a.float(), b.0));
+compare!(Float, Rational, |a, b| (a.0, b.float()));
+
+// formatting
+
+impl fmt::Display for Integer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Debug for Integer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_integer() {
+            return write!(f, "{}", self.numer);
+        }
+        write!(f, "{}/{}", self.numer, self.denom)
+    }
+}
+
+impl fmt::Debug for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl fmt::Display for Float {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+// the precision counts significant digits as in rug, f64 counts the digits after the point
+impl fmt::LowerExp for Float {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match f.precision() {
+            Some(precision) => write!(f, "{:.*e}", precision.saturating_sub(1), self.0),
+            None => write!(f, "{:e}", self.0),
+        }
+    }
+}
+
+impl fmt::Debug for Complex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({} {})", self.re, self.im)
+    }
+}
+
+impl fmt::Debug for Float {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::equation::PartEquation;
+    use crate::number::Number;
+
+    #[test]
+    fn test_rational_lowest_terms_1() {
+        let r = Rational::from((Integer::from(4), Integer::from(-6)));
+        assert_eq!(r.numer(), &Integer::from(-2));
+        assert_eq!(r.denom(), &Integer::from(3));
+        assert_eq!(r.to_string(), "-2/3");
+    }
+
+    #[test]
+    fn test_light_simplify_1() {
+        let x: PartEquation = PartEquation::from('x');
+
+        assert_eq!(
+            (PartEquation::from(4) / PartEquation::from(2)).to_string(),
+            "2"
+        );
+        assert_eq!((&x + 1 + 1).to_string(), "(x + 2)");
+        assert_eq!((&x * 2 + 2).to_string(), "((2 * x) + 2)");
+        assert_eq!((x.pow(&PartEquation::from(2)) - 1) / (&x - 1), &x + 1);
+    }
+
+    #[test]
+    fn test_light_arithmetic_1() {
+        let sum = Number::from(1) / Number::from(3) + Number::from(1) / Number::from(6);
+        assert_eq!(sum, Number::from(1) / Number::from(2));
+        assert_eq!(Number::from(3).pow(&Number::from(4)), Number::from(81));
+        assert_eq!(
+            (Number::from(2) / Number::from(3)).pow(&Number::from(2)),
+            Number::from(4) / Number::from(9)
+        );
+    }
+
+    #[test]
+    fn test_light_integer_overflow_1() {
+        let max: Number = Number::from(i128::MAX);
+        let min: Number = Number::from(i128::MIN);
+
+        // results at the bounds stay exact
+        assert_eq!(&(&max - &Number::from(1)) + &Number::from(1), max);
+        assert!(matches!(-(&min + &Number::from(1)), Number::Integer(_)));
+        assert!(matches!(
+            Number::from(-2).pow(&Number::from(127)),
+            Number::Integer(_)
+        ));
+        assert!(matches!(
+            Number::from(2).pow(&Number::from(126)),
+            Number::Integer(_)
+        ));
+
+        // one past them falls back to a float
+        let sum: Number = &max + &Number::from(1);
+        assert!(matches!(sum, Number::Float(_)));
+        assert_eq!(sum, Number::from(2f64.powi(127)));
+        assert!(matches!(&min - &Number::from(1), Number::Float(_)));
+        assert!(matches!(max.clone() * 2, Number::Float(_)));
+        assert_eq!(-min.clone(), Number::from(2f64.powi(127)));
+        assert_eq!(
+            Number::from(2).pow(&Number::from(127)),
+            Number::from(2f64.powi(127))
+        );
+
+        let mut product: Number = max.clone();
+        product *= &Number::from(-2);
+        assert_eq!(product, Number::from(-(2f64.powi(128))));
+    }
+
+    #[test]
+    fn test_light_from_u128_1() {
+        assert_eq!(Number::from(i128::MAX as u128), Number::from(i128::MAX));
+        assert!(matches!(
+            Number::from(i128::MAX as u128 + 1),
+            Number::Float(_)
+        ));
+        assert_eq!(Number::from(u128::MAX), Number::from(2f64.powi(128)));
+    }
+}
@@ -1,5 +1,4 @@
-mod lang;
-use lang::interpret;
+use math_engine::lang::interpret_program;
 use std::{io::Write, process::ExitCode};
 
 fn main() -> ExitCode {
@@ -15,8 +14,8 @@ fn main() -> ExitCode {
             return ExitCode::SUCCESS;
         }
 
-        match interpret(line) {
-            Ok(r) => println!(" |> {}", r),
+        match interpret_program(line) {
+            Ok(results) => results.iter().for_each(|r| println!(" |> {}", r)),
             Err(e) => println!("{}", e),
         };
     }
@@ -1,7 +1,91 @@
+use std::fmt;
+
 #[derive(Debug, Clone, Copy)]
 pub enum MathError {
     ZeroDivisionError,
     EquationMismatchError,
     InternalError,
     NotYetImplemented,
+    DomainError,
+    NoSolution,
+    InfiniteSolutions,
+    UnsupportedOperation,
+}
+
+impl MathError {
+    /// Stable number of the error, never reused once assigned, usable as an exit code
+    pub fn code(&self) -> u32 {
+        match self {
+            MathError::ZeroDivisionError => 1,
+            MathError::EquationMismatchError => 2,
+            MathError::InternalError => 3,
+            MathError::NotYetImplemented => 4,
+            MathError::DomainError => 5,
+            MathError::NoSolution => 6,
+            MathError::InfiniteSolutions => 7,
+            MathError::UnsupportedOperation => 8,
+        }
+    }
+}
+
+impl fmt::Display for MathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message: &str = match self {
+            MathError::ZeroDivisionError => "Division by zero",
+            MathError::EquationMismatchError => {
+                "The expression does not have the form the operation expects"
+            }
+            MathError::InternalError => "Internal error, this is a bug",
+            MathError::NotYetImplemented => "This operation is not yet implemented",
+            MathError::DomainError => "The value is outside the domain of the function",
+            MathError::NoSolution => "The equation has no solution",
+            MathError::InfiniteSolutions => "The equation has infinitely many solutions",
+            MathError::UnsupportedOperation => "The operation is not supported for these values",
+        };
+        write!(f, "{}", message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_and_message_1() {
+        let errors: [(MathError, u32, &str); 8] = [
+            (MathError::ZeroDivisionError, 1, "Division by zero"),
+            (
+                MathError::EquationMismatchError,
+                2,
+                "The expression does not have the form the operation expects",
+            ),
+            (MathError::InternalError, 3, "Internal error, this is a bug"),
+            (
+                MathError::NotYetImplemented,
+                4,
+                "This operation is not yet implemented",
+            ),
+            (
+                MathError::DomainError,
+                5,
+                "The value is outside the domain of the function",
+            ),
+            (MathError::NoSolution, 6, "The equation has no solution"),
+            (
+                MathError::InfiniteSolutions,
+                7,
+                "The equation has infinitely many solutions",
+            ),
+            (
+                MathError::UnsupportedOperation,
+                8,
+                "The operation is not supported for these values",
+            ),
+        ];
+
+        for (error, code, message) in errors.iter() {
+            assert_eq!(error.code(), *code);
+            assert_eq!(error.to_string(), *message);
+        }
+    }
 }
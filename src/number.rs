@@ -1,19 +1,383 @@
+#[cfg(not(feature = "rug"))]
+use crate::light::{Complex, Constant, Float, Integer, Pow, Rational};
+#[cfg(feature = "rug")]
+use rug::float::Constant;
+#[cfg(feature = "rug")]
 use rug::ops::Pow;
-use rug::{Float, Integer, Rational};
+#[cfg(feature = "rug")]
+use rug::{Complex, Float, Integer, Rational};
 use std::cmp::Ordering;
 use std::fmt;
 use std::fmt::{Debug, Display};
-use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+use crate::math::MathError;
 
 #[derive(Clone)]
 pub enum Number {
     Integer(Integer),
     Rational(Rational),
     Float(Float),
+    Complex(Complex),
 }
 
 impl Number {
+    pub fn e() -> Number {
+        Number::Float(Float::with_val(100, 1).exp())
+    }
+
+    pub fn pi() -> Number {
+        Number::Float(Float::with_val(100, Constant::Pi))
+    }
+
+    pub fn is_integer(&self) -> bool {
+        match self {
+            Number::Integer(_) => true,
+            Number::Rational(r) => r.is_integer(),
+            Number::Float(f) => f.is_integer(),
+            Number::Complex(_) => false,
+        }
+    }
+
+    /// The integer -1, 0 or 1 by the sign of the number, NaN for NaN.
+    /// A complex number takes the sign of its real part, or of its imaginary part if the real part is 0
+    pub fn signum(&self) -> Number {
+        if self.is_nan() {
+            return self.clone();
+        }
+        match self.cmp(&Number::from(0)) {
+            Ordering::Less => Number::from(-1),
+            Ordering::Equal => Number::from(0),
+            Ordering::Greater => Number::from(1),
+        }
+    }
+
+    pub fn is_nan(&self) -> bool {
+        match self {
+            Number::Float(f) => f.is_nan(),
+            Number::Complex(c) => c.real().is_nan() || c.imag().is_nan(),
+            _ => false,
+        }
+    }
+
+    /// The value as an `i64`, if it is an integer that fits
+    pub fn to_i64(&self) -> Option<i64> {
+        match self.clone().normalize() {
+            Number::Integer(i) => i.to_i64(),
+            _ => None,
+        }
+    }
+
+    /// Numerator in lowest terms, `3` for `3/4`. Integers, floats and complex numbers are
+    /// their own numerator
+    pub fn numerator(&self) -> Number {
+        match self {
+            Number::Rational(r) => Number::Integer(r.numer().clone()),
+            n => n.clone(),
+        }
+    }
+
+    /// Denominator in lowest terms, always positive: `4` for `3/4` and `-3/4`, `1` for
+    /// integers, floats and complex numbers
+    pub fn denominator(&self) -> Number {
+        match self {
+            Number::Rational(r) => Number::Integer(r.denom().clone()),
+            _ => Number::from(1),
+        }
+    }
+
+    /// Least common multiple of the denominators of two numbers, the smallest common
+    /// denominator of their sum: `12` for `1/4` and `5/6`
+    pub fn lcm_denominators(&self, other: &Number) -> Number {
+        let lhs: Number = self.denominator();
+        let rhs: Number = other.denominator();
+        // lhs / rhs in lowest terms has the denominator rhs / gcd(lhs, rhs)
+        (&lhs * &(&lhs / &rhs).denominator()).normalize()
+    }
+
+    /// `self / rhs`, failing with `ZeroDivisionError` for a zero `rhs` of any kind where `/`
+    /// gives infinity or NaN. Integers divide exactly: `7 / 2` is the rational `7/2`
+    pub fn checked_div(&self, rhs: &Number) -> Result<Number, MathError> {
+        if *rhs == Number::from(0) {
+            return Err(MathError::ZeroDivisionError);
+        }
+        Ok(self / rhs)
+    }
+
+    /// Largest integer not greater than the value, `3` for `7/2` and `-4` for `-3.5`.
+    /// Infinities and NaN are kept, fails with `DomainError` for complex numbers
+    pub fn floor(&self) -> Result<Number, MathError> {
+        match self {
+            Number::Integer(_) => Ok(self.clone()),
+            Number::Rational(r) => Ok(Number::Rational(r.clone().floor()).normalize()),
+            Number::Float(f) => Ok(Number::Float(f.clone().floor()).normalize()),
+            Number::Complex(_) => Err(MathError::DomainError),
+        }
+    }
+
+    /// `floor(self / rhs)`, exact for integers and rationals. Fails with `ZeroDivisionError`
+    /// for a zero `rhs` and with `DomainError` for complex numbers
+    pub fn floor_div(&self, rhs: &Number) -> Result<Number, MathError> {
+        self.checked_div(rhs)?.floor()
+    }
+
+    /// Remainder of `floor_div`, `self - rhs * floor(self / rhs)`, which takes the sign
+    /// of `rhs`: `-7 mod 3` is `2` and `7 mod -3` is `-2`
+    pub fn rem(&self, rhs: &Number) -> Result<Number, MathError> {
+        let quotient: Number = self.floor_div(rhs)?;
+        Ok((self - &(rhs * &quotient)).normalize())
+    }
+
+    /// `self ^ exponent` modulo `modulus` for integers, in `0..|modulus|`. A negative exponent
+    /// takes powers of the inverse of `self`, failing with `DomainError` if there is none.
+    /// Fails with `DomainError` for non-integers and with `ZeroDivisionError` for a zero modulus
+    pub fn powmod(&self, exponent: &Number, modulus: &Number) -> Result<Number, MathError> {
+        let base: Integer = Integer::try_from(self.clone())?;
+        let exponent: Integer = Integer::try_from(exponent.clone())?;
+        let modulus: Integer = Integer::try_from(modulus.clone())?;
+        if modulus == 0 {
+            return Err(MathError::ZeroDivisionError);
+        }
+
+        base.pow_mod(&exponent, &modulus)
+            .map(Number::Integer)
+            .map_err(|_| MathError::DomainError)
+    }
+
+    /// Closest fraction with a denominator of at most `max_denominator`, found from the continued
+    /// fraction of the value at `f64` precision, so `0.3333333` gives `1/3` for a bound of 100.
+    /// Exact values that already fit are kept, complex and non-finite values are returned unchanged
+    pub fn to_rational_approx(&self, max_denominator: u64) -> Number {
+        let max_denominator: i64 = i64::try_from(max_denominator).unwrap_or(i64::MAX).max(1);
+
+        match self.clone().normalize() {
+            Number::Integer(i) => return Number::Rational(Rational::from(i)),
+            Number::Rational(r) if r.denom().to_i64().is_some_and(|d| d <= max_denominator) => {
+                return Number::Rational(r)
+            }
+            Number::Complex(_) => return self.clone(),
+            _ => {}
+        }
+        let value: f64 = self.to_f64();
+        if !value.is_finite() {
+            return self.clone();
+        }
+
+        // the last two convergents p / q, stopping before a denominator exceeds the bound
+        let (mut p0, mut q0, mut p1, mut q1): (i64, i64, i64, i64) = (0, 1, 1, 0);
+        let mut x: f64 = value;
+        loop {
+            let a: i64 = x.floor() as i64;
+            let next = a
+                .checked_mul(p1)
+                .and_then(|p| p.checked_add(p0))
+                .zip(a.checked_mul(q1).and_then(|q| q.checked_add(q0)));
+            match next {
+                Some((p2, q2)) if q2 <= max_denominator => (p0, q0, p1, q1) = (p1, q1, p2, q2),
+                _ => break,
+            }
+
+            let fraction: f64 = x - x.floor();
+            if fraction == 0.0 {
+                break;
+            }
+            x = 1.0 / fraction;
+        }
+
+        // the semiconvergent with the largest allowed denominator may be closer than p1 / q1
+        let k: i64 = (max_denominator - q0) / q1;
+        let (mut p, mut q): (i64, i64) = (p1, q1);
+        if let Some(p2) = k.checked_mul(p1).and_then(|p| p.checked_add(p0)) {
+            let q2: i64 = q0 + k * q1;
+            if (p2 as f64 / q2 as f64 - value).abs() < (p1 as f64 / q1 as f64 - value).abs() {
+                (p, q) = (p2, q2);
+            }
+        }
+        Number::Rational(Rational::from((p, q)))
+    }
+
+    /// Compact form for showing results, see `DisplayNumber`
+    pub fn display(&self) -> DisplayNumber<'_> {
+        DisplayNumber { number: self }
+    }
+
+    /// Base-10 exponent of the value, `floor(log10(|self|))`: `3` for `1234` and `-3` for
+    /// `0.001`. Exact for integers and rationals, floats are first rounded to the digits shown
+    /// by `DisplayNumber`. A complex number takes the larger magnitude of its parts, zero,
+    /// infinities and NaN have magnitude 0
+    pub fn magnitude(&self) -> i64 {
+        match self {
+            Number::Integer(i) => Number::decimal_digits(i) - 1,
+            Number::Rational(r) => {
+                // a rational zero that was not normalized to an integer
+                if *r.numer() == 0 {
+                    return 0;
+                }
+                let exponent: i64 =
+                    Number::decimal_digits(r.numer()) - Number::decimal_digits(r.denom());
+                // |n / d| is in [10 ^ (exponent - 1), 10 ^ (exponent + 1))
+                let bound: Number = Number::from(10).pow(&Number::from(exponent));
+                let value: Number = if *self < Number::from(0) {
+                    -self
+                } else {
+                    self.clone()
+                };
+                if value < bound {
+                    exponent - 1
+                } else {
+                    exponent
+                }
+            }
+            Number::Float(f) => {
+                if f.is_zero() || !f.is_finite() {
+                    return 0;
+                }
+                let scientific: String = format!("{:.*e}", DISPLAY_DIGITS, f);
+                scientific
+                    .split_once('e')
+                    .and_then(|(_, exponent)| exponent.parse().ok())
+                    .unwrap_or(0)
+            }
+            Number::Complex(c) => [c.real(), c.imag()]
+                .into_iter()
+                .filter(|part| !part.is_zero())
+                .map(|part| Number::Float(part.clone()).magnitude())
+                .max()
+                .unwrap_or(0),
+        }
+    }
+
+    /// Nearest `f64` to the value, NaN for a complex value
+    pub fn to_f64(&self) -> f64 {
+        if let Number::Complex(_) = self {
+            return f64::NAN;
+        }
+        self.to_float().to_f64()
+    }
+
+    /// Power restricted to real results: odd roots of negative numbers are negative,
+    /// even roots of negative numbers fail with `DomainError`
+    pub fn real_pow(&self, exponent: &Number) -> Result<Number, MathError> {
+        if let (Number::Complex(_), _) | (_, Number::Complex(_)) = (self, exponent) {
+            return Err(MathError::DomainError);
+        }
+        if *self == Number::from(0) && *exponent < Number::from(0) {
+            return Err(MathError::ZeroDivisionError);
+        }
+        if *self >= Number::from(0) || exponent.is_integer() {
+            return Ok(self.pow(exponent));
+        }
+
+        // (-8) ^ (1/3) = -2
+        if let Number::Rational(e) = exponent {
+            if e.denom().is_odd() {
+                let result: Number = (-self).pow(exponent);
+                if e.numer().is_odd() {
+                    return Ok(-result);
+                }
+                return Ok(result);
+            }
+        }
+
+        Err(MathError::DomainError)
+    }
+
+    /// Natural logarithm, fails with `DomainError` for non-positive and complex values
+    pub fn ln(&self) -> Result<Number, MathError> {
+        if *self <= Number::from(0) || matches!(self, Number::Complex(_)) {
+            return Err(MathError::DomainError);
+        }
+        Ok(Number::Float(self.to_float().ln()))
+    }
+
+    /// e raised to the value, exact for 0
+    pub fn exp(&self) -> Number {
+        if let Number::Complex(_) = self {
+            return Number::pow_complex(&Number::e().to_complex(), self);
+        }
+        if *self == Number::from(0) {
+            return Number::from(1);
+        }
+        Number::Float(self.to_float().exp())
+    }
+
+    /// Logarithm to the given base, exact when the result is an integer power of an exact base.
+    /// The float result is rounded to the nearest integer `n` and `base ^ n` is computed exactly,
+    /// `n` is returned as an `Integer` only if that gives back `self`, so `log(8, 2) = 3` but
+    /// `log(10, 2)` stays a `Float`
+    pub fn log(&self, base: &Number) -> Result<Number, MathError> {
+        if *self <= Number::from(0) || *base <= Number::from(0) || *base == Number::from(1) {
+            return Err(MathError::DomainError);
+        }
+        if let (Number::Complex(_), _) | (_, Number::Complex(_)) = (self, base) {
+            return Err(MathError::DomainError);
+        }
+
+        let result: Float = self.to_float().ln() / base.to_float().ln();
+
+        if !matches!(self, Number::Float(_)) && !matches!(base, Number::Float(_)) {
+            if let Some(estimate) = result.clone().round().to_integer() {
+                let estimate = Number::Integer(estimate);
+                if base.pow(&estimate) == *self {
+                    return Ok(estimate);
+                }
+            }
+        }
+
+        Ok(Number::Float(result))
+    }
+
+    /// Demotes integer-valued `Rational`s and `Float`s to `Integer`,
+    /// and `Complex` numbers without an imaginary part to real numbers
+    pub fn normalize(self) -> Number {
+        match self {
+            Number::Rational(r) => {
+                if r.is_integer() {
+                    let (n, _) = r.into_numer_denom();
+                    Number::Integer(n)
+                } else {
+                    Number::Rational(r)
+                }
+            }
+            Number::Float(f) => match f.to_integer() {
+                Some(i) if f.is_integer() => Number::Integer(i),
+                _ => Number::Float(f),
+            },
+            Number::Complex(c) if c.imag().is_zero() => Number::Float(c.real().clone()).normalize(),
+            n => n,
+        }
+    }
+
+    /// Power, the principal complex value when the base is negative and the exponent is not an integer.
+    /// Square roots of negative numbers are exact: (-4) ^ (1/2) = 2i
     pub fn pow(&self, exponent: &Number) -> Number {        
+        // b ^ -n = 1 / b ^ n, exact for integer and rational bases
+        if let Number::Integer(_) | Number::Rational(_) = self {
+            if exponent.is_integer() && *exponent < Number::from(0) {
+                if *self == Number::from(0) {
+                    return Number::from(f64::INFINITY);
+                }
+                return Number::from(1) / self.pow(&-exponent);
+            }
+        }
+
+        let real: bool =
+            !matches!(self, Number::Complex(_)) && !matches!(exponent, Number::Complex(_));
+        if real && *self < Number::from(0) && !exponent.is_integer() {
+            // (-b) ^ (n/2) = b ^ (n/2) * i ^ n
+            if let Number::Rational(e) = exponent {
+                if *e.denom() == 2 {
+                    let i: Complex =
+                        Complex::with_val(100, (Float::with_val(100, 0), Float::with_val(100, 1)));
+                    return (-self).pow(exponent)
+                        * Number::pow_complex(&i, &Number::Integer(e.numer().clone()));
+                }
+            }
+            return Number::pow_complex(&self.to_complex(), exponent);
+        }
+
         match self {
             Number::Integer(b) => match exponent {
                 Number::Integer(e) => Number::pow_integer(b, e),
@@ -21,11 +385,14 @@ impl Number {
                     if e.is_integer() {
                         let (e, _) = e.clone().into_numer_denom();
                         Number::pow_integer(b, &e)
+                    } else if let Some(root) = self.exact_root(e.denom()) {
+                        root.pow(&Number::Integer(e.numer().clone()))
                     } else {
                         Number::pow_float(&Float::with_val(100, b), &Float::with_val(100, e))
                     }
                 }
                 Number::Float(e) => Number::pow_float(&Float::with_val(100, b), e),
+                Number::Complex(_) => Number::pow_complex(&self.to_complex(), exponent),
             },
             Number::Rational(b) => match exponent {
                 Number::Integer(e) => {
@@ -41,37 +408,75 @@ impl Number {
                         let (b, _) = b.clone().into_numer_denom();
                         let (e, _) = e.clone().into_numer_denom();
                         Number::pow_integer(&b, &e)
+                    } else if let Some(root) = self.exact_root(e.denom()) {
+                        root.pow(&Number::Integer(e.numer().clone()))
                     } else {
                         Number::pow_float(&Float::with_val(100, b), &Float::with_val(100, e))
                     }
                 }
                 Number::Float(e) => Number::pow_float(&Float::with_val(100, b), e),
+                Number::Complex(_) => Number::pow_complex(&self.to_complex(), exponent),
             },
             Number::Float(b) => match exponent {
                 // ???: Check if Float is a integer and type cast it
                 Number::Integer(e) => Number::pow_float(b, &Float::with_val(100, e)),
                 Number::Rational(e) => Number::pow_float(b, &Float::with_val(100, e)),
                 Number::Float(e) => Number::pow_float(b, e),
+                Number::Complex(_) => Number::pow_complex(&self.to_complex(), exponent),
             },
+            Number::Complex(b) => Number::pow_complex(b, exponent),
         }
     }
 
-    fn pow_integer(base: &Integer, exponent: &Integer) -> Number {
-        let mut result = Integer::from(base);
-        let mut count = Integer::from(1);
-        while count < *exponent {
-            result *= base;
-            count += 1;
+    // n-th root of a non-negative integer or rational if it is exact: 8 ^ (1/3) = 2,
+    // (4/9) ^ (1/2) = 2/3, so that rational powers of perfect powers stay exact
+    fn exact_root(&self, n: &Integer) -> Option<Number> {
+        match self {
+            Number::Integer(b) => Number::integer_root(b, n).map(Number::Integer),
+            Number::Rational(b) => Some(Number::Rational(Rational::from((
+                Number::integer_root(b.numer(), n)?,
+                Number::integer_root(b.denom(), n)?,
+            )))),
+            _ => None,
         }
-        return Number::Integer(result);
     }
-    
+
+    // rounds a float estimate of the root and checks its neighbours exactly
+    fn integer_root(value: &Integer, n: &Integer) -> Option<Integer> {
+        if *value < 0 || *n < 1 {
+            return None;
+        }
+        if *value < 2 {
+            return Some(Integer::from(value));
+        }
+
+        let estimate: Float = Float::with_val(100, value)
+            .pow(Float::with_val(100, 1) / Float::with_val(100, n))
+            .round();
+        let estimate: Integer = estimate.to_integer()?;
+        // a root below 2 would be 0 or 1, which are only roots of themselves
+        if estimate < 2 {
+            return None;
+        }
+
+        let target: Number = Number::Integer(Integer::from(value));
+        [estimate.clone() - 1, estimate.clone(), estimate + 1]
+            .into_iter()
+            .find(|root| *root >= 2 && Number::pow_integer(root, n) == target)
+    }
+
     fn pow_rational(base: &Rational, exponent: &Integer) -> Number {
-        let mut result = Rational::from(base);
-        let mut count = Integer::from(1);
-        while count < *exponent {
-            result *= base;
-            count += 1;
+        let mut result = Rational::from(1);
+        let mut square = Rational::from(base);
+        let mut exponent = Integer::from(exponent);
+        while exponent > 0 {
+            if exponent.is_odd() {
+                result *= &square;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                square.square_mut();
+            }
         }
         return Number::Rational(result);
     }
@@ -79,6 +484,88 @@ impl Number {
     fn pow_float(base: &Float, exponent: &Float) -> Number {
         Number::Float(base.pow(exponent.clone()))
     }
+
+    // integer exponents by squaring, so that i ^ 2 is exactly -1
+    fn pow_complex(base: &Complex, exponent: &Number) -> Number {
+        let exponent: Integer = match exponent.clone().normalize() {
+            Number::Integer(e) => e,
+            e => return Number::from_complex(base.clone().pow(&e.to_complex())),
+        };
+
+        let mut result = Complex::with_val(100, 1);
+        let mut square = base.clone();
+        let mut remaining = if exponent < 0 {
+            -exponent.clone()
+        } else {
+            exponent.clone()
+        };
+        while remaining > 0 {
+            if remaining.is_odd() {
+                result *= &square;
+            }
+            remaining >>= 1;
+            if remaining > 0 {
+                square.square_mut();
+            }
+        }
+
+        if exponent < 0 {
+            result = Complex::with_val(100, 1) / result;
+        }
+        Number::from_complex(result)
+    }
+
+    fn from_complex(value: Complex) -> Number {
+        Number::Complex(value).normalize()
+    }
+
+    fn to_float(&self) -> Float {
+        match self {
+            Number::Integer(i) => Float::with_val(100, i),
+            Number::Rational(r) => Float::with_val(100, r),
+            Number::Float(f) => f.clone(),
+            Number::Complex(c) => c.real().clone(),
+        }
+    }
+
+    fn to_complex(&self) -> Complex {
+        match self {
+            Number::Integer(i) => Complex::with_val(100, i),
+            Number::Rational(r) => Complex::with_val(100, r),
+            Number::Float(f) => Complex::with_val(100, f),
+            Number::Complex(c) => c.clone(),
+        }
+    }
+
+    // number of decimal digits, ignoring the sign
+    fn decimal_digits(value: &Integer) -> i64 {
+        value.to_string().trim_start_matches('-').len() as i64
+    }
+
+    // integer or rational zero, which rug cannot divide by
+    fn is_exact_zero(&self) -> bool {
+        match self {
+            Number::Integer(i) => *i == 0,
+            Number::Rational(r) => *r.numer() == 0,
+            _ => false,
+        }
+    }
+
+    // x / 0 as for floats: infinity with the sign of x, NaN for 0 / 0
+    fn divide_by_zero(&self) -> Number {
+        match self {
+            Number::Complex(c) => Number::from_complex(c.clone() / Complex::with_val(100, 0)),
+            _ => Number::Float(self.to_float() / Float::with_val(100, 0)),
+        }
+    }
+
+    // lexicographic, real part first
+    fn cmp_complex(lhs: &Complex, rhs: &Complex) -> Option<Ordering> {
+        match lhs.real().partial_cmp(rhs.real()) {
+            Some(Ordering::Equal) => lhs.imag().partial_cmp(rhs.imag()),
+            ordering => ordering,
+        }
+    }
 }
 
 impl Debug for Number {
@@ -87,6 +574,7 @@ impl Debug for Number {
             Number::Integer(i) => write!(f, "{:?}", i),
             Number::Rational(i) => write!(f, "{:?}", i),
             Number::Float(i) => write!(f, "{:?}", i),
+            Number::Complex(i) => write!(f, "{:?}", i),
         }
     }
 }
@@ -97,6 +585,125 @@ impl Display for Number {
             Number::Integer(i) => write!(f, "{}", i),
             Number::Rational(i) => write!(f, "{}", i),
             Number::Float(i) => write!(f, "{}", i),
+            Number::Complex(i) => {
+                let real: Number = Number::Float(i.real().clone()).normalize();
+                let imag: Number = Number::Float(i.imag().clone()).normalize();
+                if real == Number::from(0) {
+                    write!(f, "{}i", imag)
+                } else if imag < Number::from(0) {
+                    write!(f, "{} - {}i", real, -imag)
+                } else {
+                    write!(f, "{} + {}i", real, imag)
+                }
+            }
+        }
+    }
+}
+
+// significant digits shown by `DisplayNumber`, enough to hide the error of a float
+// converted from f64 or accumulated over a few operations
+const DISPLAY_DIGITS: usize = 15;
+// decimal exponents outside [DISPLAY_MIN_EXPONENT, DISPLAY_MAX_EXPONENT) are shown in scientific notation
+const DISPLAY_MIN_EXPONENT: i64 = -5;
+const DISPLAY_MAX_EXPONENT: i64 = 15;
+
+/// Compact form of a `Number` for showing results, returned by `Number::display`.
+/// Floats are rounded to 15 significant digits, integer valued floats lose their fraction
+/// and very large or small ones are written as `1.5e30`. Unlike `Display` of `Number` it
+/// does not round trip, integers and rationals are printed the same by both
+///
+/// ```
+/// use math_engine::number::Number;
+///
+/// assert_eq!(Number::from(5.0).display().to_string(), "5");
+/// assert_eq!(Number::from(1e30).display().to_string(), "1e30");
+/// ```
+pub struct DisplayNumber<'a> {
+    number: &'a Number,
+}
+
+impl DisplayNumber<'_> {
+    fn fmt_float(f: &mut fmt::Formatter, value: &Float) -> fmt::Result {
+        if !value.is_finite() {
+            return write!(f, "{}", value);
+        }
+
+        let exponent: i64 = Number::Float(value.clone()).magnitude();
+        let negative: bool = value.is_sign_negative();
+        // significant digits of d.dddddddddddddde<exponent>, rounded as `magnitude` rounds
+        let digits: String = format!("{:.*e}", DISPLAY_DIGITS, value)
+            .chars()
+            .take_while(|c| *c != 'e')
+            .filter(char::is_ascii_digit)
+            .collect();
+        let digits: &str = digits.trim_end_matches('0');
+
+        if digits.is_empty() {
+            return write!(f, "0");
+        }
+        if negative {
+            write!(f, "-")?;
+        }
+
+        if !(DISPLAY_MIN_EXPONENT..DISPLAY_MAX_EXPONENT).contains(&exponent) {
+            let (first, rest) = digits.split_at(1);
+            if rest.is_empty() {
+                return write!(f, "{}e{}", first, exponent);
+            }
+            return write!(f, "{}.{}e{}", first, rest, exponent);
+        }
+
+        if exponent < 0 {
+            let zeros: String = "0".repeat((-exponent - 1) as usize);
+            return write!(f, "0.{}{}", zeros, digits);
+        }
+
+        let integer_digits: usize = exponent as usize + 1;
+        if digits.len() <= integer_digits {
+            let zeros: String = "0".repeat(integer_digits - digits.len());
+            write!(f, "{}{}", digits, zeros)
+        } else {
+            let (integer, fraction) = digits.split_at(integer_digits);
+            write!(f, "{}.{}", integer, fraction)
+        }
+    }
+}
+
+impl Display for DisplayNumber<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.number {
+            Number::Integer(_) | Number::Rational(_) => write!(f, "{}", self.number),
+            Number::Float(i) => DisplayNumber::fmt_float(f, i),
+            Number::Complex(i) => {
+                let real: Number = Number::Float(i.real().clone()).normalize();
+                let imag: Number = Number::Float(i.imag().clone()).normalize();
+                if real == Number::from(0) {
+                    write!(f, "{}i", imag.display())
+                } else if imag < Number::from(0) {
+                    write!(f, "{} - {}i", real.display(), (-imag).display())
+                } else {
+                    write!(f, "{} + {}i", real.display(), imag.display())
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseNumberError;
+
+impl FromStr for Number {
+    type Err = ParseNumberError;
+
+    /// Parses integers of any size, falls back to a float for anything else
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(i) = s.parse::<Integer>() {
+            return Ok(Number::Integer(i));
+        }
+
+        match Float::parse(s) {
+            Ok(f) => Ok(Number::Float(Float::with_val(100, f))),
+            Err(_) => Err(ParseNumberError),
         }
     }
 }
@@ -131,6 +738,12 @@ impl From<i128> for Number {
     }
 }
 
+impl From<isize> for Number {
+    fn from(value: isize) -> Self {
+        Number::Integer(Integer::from(value))
+    }
+}
+
 impl From<u8> for Number {
     fn from(value: u8) -> Self {
         Number::Integer(Integer::from(value))
@@ -157,6 +770,12 @@ impl From<u64> for Number {
 
 impl From<u128> for Number {
     fn from(value: u128) -> Self {
+        Number::from_u128(value)
+    }
+}
+
+impl From<usize> for Number {
+    fn from(value: usize) -> Self {
         Number::Integer(Integer::from(value))
     }
 }
@@ -173,24 +792,58 @@ impl From<f64> for Number {
     }
 }
 
+impl TryFrom<Number> for Integer {
+    type Error = MathError;
+
+    /// Succeeds for integer-valued numbers, fails with `DomainError` otherwise
+    fn try_from(value: Number) -> Result<Self, Self::Error> {
+        match value.normalize() {
+            Number::Integer(i) => Ok(i),
+            _ => Err(MathError::DomainError),
+        }
+    }
+}
+
+impl TryFrom<Number> for Rational {
+    type Error = MathError;
+
+    /// Succeeds for integers and rationals, fails with `DomainError` for floats and complex numbers
+    fn try_from(value: Number) -> Result<Self, Self::Error> {
+        match value {
+            Number::Integer(i) => Ok(Rational::from(i)),
+            Number::Rational(r) => Ok(r),
+            _ => Err(MathError::DomainError),
+        }
+    }
+}
+
 impl PartialEq for Number {
+    /// NaN equals NaN, so that `Eq` and `Ord` hold for every number
     fn eq(&self, other: &Self) -> bool {
+        if self.is_nan() || other.is_nan() {
+            return self.is_nan() && other.is_nan();
+        }
+
         match self {
             Number::Integer(lhs) => match other {
                 Number::Integer(rhs) => lhs == rhs,
                 Number::Rational(rhs) => lhs == rhs,
                 Number::Float(rhs) => lhs == rhs,
+                Number::Complex(rhs) => Complex::with_val(100, lhs) == *rhs,
             },
             Number::Rational(lhs) => match other {
                 Number::Integer(rhs) => lhs == rhs,
                 Number::Rational(rhs) => lhs == rhs,
                 Number::Float(rhs) => lhs == rhs,
+                Number::Complex(rhs) => Complex::with_val(100, lhs) == *rhs,
             },
             Number::Float(lhs) => match other {
                 Number::Integer(rhs) => lhs == rhs,
                 Number::Rational(rhs) => lhs == rhs,
                 Number::Float(rhs) => lhs == rhs,
+                Number::Complex(rhs) => Complex::with_val(100, lhs) == *rhs,
             },
+            Number::Complex(lhs) => *lhs == other.to_complex(),
         }
     }
 }
@@ -199,29 +852,150 @@ impl Eq for Number {}
 
 impl PartialOrd for Number {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        match self {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Number {
+    /// Total order, NaN sorts after every other number.
+    /// Complex numbers compare by their real parts, then by their imaginary parts
+    fn cmp(&self, other: &Self) -> Ordering {
+        // only NaN is unordered
+        let ordering: Option<Ordering> = match self {
             Number::Integer(lhs) => match other {
                 Number::Integer(rhs) => lhs.partial_cmp(rhs),
                 Number::Rational(rhs) => lhs.partial_cmp(rhs),
                 Number::Float(rhs) => lhs.partial_cmp(rhs),
+                Number::Complex(rhs) => Number::cmp_complex(&Complex::with_val(100, lhs), rhs),
             },
             Number::Rational(lhs) => match other {
                 Number::Integer(rhs) => lhs.partial_cmp(rhs),
                 Number::Rational(rhs) => lhs.partial_cmp(rhs),
                 Number::Float(rhs) => lhs.partial_cmp(rhs),
+                Number::Complex(rhs) => Number::cmp_complex(&Complex::with_val(100, lhs), rhs),
             },
             Number::Float(lhs) => match other {
                 Number::Integer(rhs) => lhs.partial_cmp(rhs),
                 Number::Rational(rhs) => lhs.partial_cmp(rhs),
                 Number::Float(rhs) => lhs.partial_cmp(rhs),
+                Number::Complex(rhs) => Number::cmp_complex(&Complex::with_val(100, lhs), rhs),
             },
+            Number::Complex(lhs) => Number::cmp_complex(lhs, &other.to_complex()),
+        };
+
+        match ordering {
+            Some(ordering) => ordering,
+            None => self.is_nan().cmp(&other.is_nan()),
         }
     }
 }
 
-impl Ord for Number {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap()
+impl Hash for Number {
+    /// Hashes the nearest `f64` values of the real and imaginary parts,
+    /// so equal numbers of different kinds such as `5/2` and `2.5` hash alike
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let (real, imag): (f64, f64) = match self {
+            Number::Complex(c) => (
+                Number::Float(c.real().clone()).to_f64(),
+                Number::Float(c.imag().clone()).to_f64(),
+            ),
+            n => (n.to_f64(), 0.0),
+        };
+
+        for part in [real, imag] {
+            if part.is_nan() {
+                f64::NAN.to_bits().hash(state);
+            } else {
+                // -0.0 == 0.0
+                (part + 0.0).to_bits().hash(state);
+            }
+        }
+    }
+}
+
+// Conversion and arithmetic on integers. `rug` integers grow as needed, the light backend's
+// are `i128` and there a result that does not fit is computed as a `Float` instead
+#[cfg(feature = "rug")]
+impl Number {
+    fn from_u128(value: u128) -> Number {
+        Number::Integer(Integer::from(value))
+    }
+
+    fn add_integers(lhs: Integer, rhs: &Integer) -> Number {
+        Number::Integer(lhs + rhs)
+    }
+
+    fn sub_integers(lhs: Integer, rhs: &Integer) -> Number {
+        Number::Integer(lhs - rhs)
+    }
+
+    fn mul_integers(lhs: Integer, rhs: &Integer) -> Number {
+        Number::Integer(lhs * rhs)
+    }
+
+    fn neg_integer(value: Integer) -> Number {
+        Number::Integer(-value)
+    }
+
+    fn pow_integer(base: &Integer, exponent: &Integer) -> Number {
+        let mut result = Integer::from(1);
+        let mut square = Integer::from(base);
+        let mut exponent = Integer::from(exponent);
+        while exponent > 0 {
+            if exponent.is_odd() {
+                result *= &square;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                square.square_mut();
+            }
+        }
+        Number::Integer(result)
+    }
+}
+
+#[cfg(not(feature = "rug"))]
+impl Number {
+    fn from_u128(value: u128) -> Number {
+        match Integer::try_from(value) {
+            Ok(i) => Number::Integer(i),
+            Err(_) => Number::Float(Float::from(value as f64)),
+        }
+    }
+
+    fn add_integers(lhs: Integer, rhs: &Integer) -> Number {
+        match lhs.checked_add(rhs) {
+            Some(sum) => Number::Integer(sum),
+            None => Number::Float(Float::from(lhs) + Float::from(rhs)),
+        }
+    }
+
+    fn sub_integers(lhs: Integer, rhs: &Integer) -> Number {
+        match lhs.checked_sub(rhs) {
+            Some(difference) => Number::Integer(difference),
+            None => Number::Float(Float::from(lhs) - Float::from(rhs)),
+        }
+    }
+
+    fn mul_integers(lhs: Integer, rhs: &Integer) -> Number {
+        match lhs.checked_mul(rhs) {
+            Some(product) => Number::Integer(product),
+            None => Number::Float(Float::from(lhs) * Float::from(rhs)),
+        }
+    }
+
+    fn neg_integer(value: Integer) -> Number {
+        match value.checked_neg() {
+            Some(negated) => Number::Integer(negated),
+            None => Number::Float(-Float::from(value)),
+        }
+    }
+
+    fn pow_integer(base: &Integer, exponent: &Integer) -> Number {
+        match base.checked_pow(exponent) {
+            Some(power) => Number::Integer(power),
+            None => Number::Float((&Float::from(base)).pow(Float::from(exponent))),
+        }
     }
 }
 
@@ -231,20 +1005,30 @@ impl Add<&Number> for &Number {
     fn add(self, rhs: &Number) -> Self::Output {
         match self {
             Number::Integer(lhs) => match rhs {
-                Number::Integer(rhs) => Number::Integer(lhs.clone() + rhs.clone()),
+                Number::Integer(rhs) => Number::add_integers(lhs.clone(), rhs),
                 Number::Rational(rhs) => Number::Rational(lhs.clone() + rhs.clone()),
                 Number::Float(rhs) => Number::Float(lhs.clone() + rhs.clone()),
+                Number::Complex(rhs) => {
+                    Number::from_complex(Complex::with_val(100, lhs) + rhs.clone())
+                }
             },
             Number::Rational(lhs) => match rhs {
                 Number::Float(rhs) => Number::Float(lhs.clone() + rhs.clone()),
                 Number::Integer(rhs) => Number::Rational(lhs.clone() + rhs.clone()),
                 Number::Rational(rhs) => Number::Rational(lhs.clone() + rhs.clone()),
+                Number::Complex(rhs) => {
+                    Number::from_complex(Complex::with_val(100, lhs) + rhs.clone())
+                }
             },
             Number::Float(lhs) => match rhs {
                 Number::Integer(rhs) => Number::Float(lhs.clone() + rhs.clone()),
                 Number::Rational(rhs) => Number::Float(lhs.clone() + rhs.clone()),
                 Number::Float(rhs) => Number::Float(lhs.clone() + rhs.clone()),
+                Number::Complex(rhs) => {
+                    Number::from_complex(Complex::with_val(100, lhs) + rhs.clone())
+                }
             },
+            Number::Complex(lhs) => Number::from_complex(lhs.clone() + rhs.to_complex()),
         }
     }
 }
@@ -255,20 +1039,30 @@ impl Sub<&Number> for &Number {
     fn sub(self, rhs: &Number) -> Self::Output {
         match self {
             Number::Integer(lhs) => match rhs {
-                Number::Integer(rhs) => Number::Integer(lhs.clone() - rhs.clone()),
+                Number::Integer(rhs) => Number::sub_integers(lhs.clone(), rhs),
                 Number::Rational(rhs) => Number::Rational(lhs.clone() - rhs.clone()),
                 Number::Float(rhs) => Number::Float(lhs.clone() - rhs.clone()),
+                Number::Complex(rhs) => {
+                    Number::from_complex(Complex::with_val(100, lhs) - rhs.clone())
+                }
             },
             Number::Rational(lhs) => match rhs {
                 Number::Float(rhs) => Number::Float(lhs.clone() - rhs.clone()),
                 Number::Integer(rhs) => Number::Rational(lhs.clone() - rhs.clone()),
                 Number::Rational(rhs) => Number::Rational(lhs.clone() - rhs.clone()),
+                Number::Complex(rhs) => {
+                    Number::from_complex(Complex::with_val(100, lhs) - rhs.clone())
+                }
             },
             Number::Float(lhs) => match rhs {
                 Number::Integer(rhs) => Number::Float(lhs.clone() - rhs.clone()),
                 Number::Rational(rhs) => Number::Float(lhs.clone() - rhs.clone()),
                 Number::Float(rhs) => Number::Float(lhs.clone() - rhs.clone()),
+                Number::Complex(rhs) => {
+                    Number::from_complex(Complex::with_val(100, lhs) - rhs.clone())
+                }
             },
+            Number::Complex(lhs) => Number::from_complex(lhs.clone() - rhs.to_complex()),
         }
     }
 }
@@ -279,20 +1073,30 @@ impl Mul<&Number> for &Number {
     fn mul(self, rhs: &Number) -> Self::Output {
         match self {
             Number::Integer(lhs) => match rhs {
-                Number::Integer(rhs) => Number::Integer(lhs.clone() * rhs.clone()),
+                Number::Integer(rhs) => Number::mul_integers(lhs.clone(), rhs),
                 Number::Rational(rhs) => Number::Rational(lhs.clone() * rhs.clone()),
                 Number::Float(rhs) => Number::Float(lhs.clone() * rhs.clone()),
+                Number::Complex(rhs) => {
+                    Number::from_complex(Complex::with_val(100, lhs) * rhs.clone())
+                }
             },
             Number::Rational(lhs) => match rhs {
                 Number::Float(rhs) => Number::Float(lhs.clone() * rhs.clone()),
                 Number::Integer(rhs) => Number::Rational(lhs.clone() * rhs.clone()),
                 Number::Rational(rhs) => Number::Rational(lhs.clone() * rhs.clone()),
+                Number::Complex(rhs) => {
+                    Number::from_complex(Complex::with_val(100, lhs) * rhs.clone())
+                }
             },
             Number::Float(lhs) => match rhs {
                 Number::Integer(rhs) => Number::Float(lhs.clone() * rhs.clone()),
                 Number::Rational(rhs) => Number::Float(lhs.clone() * rhs.clone()),
                 Number::Float(rhs) => Number::Float(lhs.clone() * rhs.clone()),
+                Number::Complex(rhs) => {
+                    Number::from_complex(Complex::with_val(100, lhs) * rhs.clone())
+                }
             },
+            Number::Complex(lhs) => Number::from_complex(lhs.clone() * rhs.to_complex()),
         }
     }
 }
@@ -301,22 +1105,38 @@ impl Div<&Number> for &Number {
     type Output = Number;
 
     fn div(self, rhs: &Number) -> Self::Output {
+        if rhs.is_exact_zero() {
+            return self.divide_by_zero();
+        }
+
         match self {
             Number::Integer(lhs) => match rhs {
-                Number::Integer(rhs) => Number::Integer(lhs.clone() / rhs.clone()),
+                Number::Integer(rhs) => {
+                    Number::Rational(Rational::from((lhs.clone(), rhs.clone())))
+                }
                 Number::Rational(rhs) => Number::Rational(lhs.clone() / rhs.clone()),
                 Number::Float(rhs) => Number::Float(lhs.clone() / rhs.clone()),
+                Number::Complex(rhs) => {
+                    Number::from_complex(Complex::with_val(100, lhs) / rhs.clone())
+                }
             },
             Number::Rational(lhs) => match rhs {
                 Number::Float(rhs) => Number::Float(lhs.clone() / rhs.clone()),
                 Number::Integer(rhs) => Number::Rational(lhs.clone() / rhs.clone()),
                 Number::Rational(rhs) => Number::Rational(lhs.clone() / rhs.clone()),
+                Number::Complex(rhs) => {
+                    Number::from_complex(Complex::with_val(100, lhs) / rhs.clone())
+                }
             },
             Number::Float(lhs) => match rhs {
                 Number::Integer(rhs) => Number::Float(lhs.clone() / rhs.clone()),
                 Number::Rational(rhs) => Number::Float(lhs.clone() / rhs.clone()),
                 Number::Float(rhs) => Number::Float(lhs.clone() / rhs.clone()),
+                Number::Complex(rhs) => {
+                    Number::from_complex(Complex::with_val(100, lhs) / rhs.clone())
+                }
             },
+            Number::Complex(lhs) => Number::from_complex(lhs.clone() / rhs.to_complex()),
         }
     }
 }
@@ -326,9 +1146,10 @@ impl Neg for &Number {
 
     fn neg(self) -> Self::Output {
         match self {
-            Number::Integer(lhs) => Number::Integer(-lhs.clone()),
+            Number::Integer(lhs) => Number::neg_integer(lhs.clone()),
             Number::Rational(lhs) => Number::Rational(-lhs.clone()),
             Number::Float(lhs) => Number::Float(-lhs.clone()),
+            Number::Complex(lhs) => Number::Complex(-lhs.clone()),
         }
     }
 }
@@ -339,20 +1160,24 @@ impl Add<Number> for Number {
     fn add(self, rhs: Number) -> Self::Output {
         match self {
             Number::Integer(lhs) => match rhs {
-                Number::Integer(rhs) => Number::Integer(lhs.clone() + rhs.clone()),
-                Number::Rational(rhs) => Number::Rational(lhs.clone() + rhs.clone()),
-                Number::Float(rhs) => Number::Float(lhs.clone() + rhs.clone()),
+                Number::Integer(rhs) => Number::add_integers(lhs, &rhs),
+                Number::Rational(rhs) => Number::Rational(lhs + rhs),
+                Number::Float(rhs) => Number::Float(lhs + rhs),
+                Number::Complex(rhs) => Number::from_complex(Complex::with_val(100, lhs) + rhs),
             },
             Number::Rational(lhs) => match rhs {
-                Number::Float(rhs) => Number::Float(lhs.clone() + rhs.clone()),
-                Number::Integer(rhs) => Number::Rational(lhs.clone() + rhs.clone()),
-                Number::Rational(rhs) => Number::Rational(lhs.clone() + rhs.clone()),
+                Number::Float(rhs) => Number::Float(lhs + rhs),
+                Number::Integer(rhs) => Number::Rational(lhs + rhs),
+                Number::Rational(rhs) => Number::Rational(lhs + rhs),
+                Number::Complex(rhs) => Number::from_complex(Complex::with_val(100, lhs) + rhs),
             },
             Number::Float(lhs) => match rhs {
-                Number::Integer(rhs) => Number::Float(lhs.clone() + rhs.clone()),
-                Number::Rational(rhs) => Number::Float(lhs.clone() + rhs.clone()),
-                Number::Float(rhs) => Number::Float(lhs.clone() + rhs.clone()),
+                Number::Integer(rhs) => Number::Float(lhs + rhs),
+                Number::Rational(rhs) => Number::Float(lhs + rhs),
+                Number::Float(rhs) => Number::Float(lhs + rhs),
+                Number::Complex(rhs) => Number::from_complex(Complex::with_val(100, lhs) + rhs),
             },
+            Number::Complex(lhs) => Number::from_complex(lhs + rhs.to_complex()),
         }
     }
 }
@@ -363,20 +1188,24 @@ impl Sub<Number> for Number {
     fn sub(self, rhs: Number) -> Self::Output {
         match self {
             Number::Integer(lhs) => match rhs {
-                Number::Integer(rhs) => Number::Integer(lhs.clone() - rhs.clone()),
-                Number::Rational(rhs) => Number::Rational(lhs.clone() - rhs.clone()),
-                Number::Float(rhs) => Number::Float(lhs.clone() - rhs.clone()),
+                Number::Integer(rhs) => Number::sub_integers(lhs, &rhs),
+                Number::Rational(rhs) => Number::Rational(lhs - rhs),
+                Number::Float(rhs) => Number::Float(lhs - rhs),
+                Number::Complex(rhs) => Number::from_complex(Complex::with_val(100, lhs) - rhs),
             },
             Number::Rational(lhs) => match rhs {
-                Number::Float(rhs) => Number::Float(lhs.clone() - rhs.clone()),
-                Number::Integer(rhs) => Number::Rational(lhs.clone() - rhs.clone()),
-                Number::Rational(rhs) => Number::Rational(lhs.clone() - rhs.clone()),
+                Number::Float(rhs) => Number::Float(lhs - rhs),
+                Number::Integer(rhs) => Number::Rational(lhs - rhs),
+                Number::Rational(rhs) => Number::Rational(lhs - rhs),
+                Number::Complex(rhs) => Number::from_complex(Complex::with_val(100, lhs) - rhs),
             },
             Number::Float(lhs) => match rhs {
-                Number::Integer(rhs) => Number::Float(lhs.clone() - rhs.clone()),
-                Number::Rational(rhs) => Number::Float(lhs.clone() - rhs.clone()),
-                Number::Float(rhs) => Number::Float(lhs.clone() - rhs.clone()),
+                Number::Integer(rhs) => Number::Float(lhs - rhs),
+                Number::Rational(rhs) => Number::Float(lhs - rhs),
+                Number::Float(rhs) => Number::Float(lhs - rhs),
+                Number::Complex(rhs) => Number::from_complex(Complex::with_val(100, lhs) - rhs),
             },
+            Number::Complex(lhs) => Number::from_complex(lhs - rhs.to_complex()),
         }
     }
 }
@@ -387,20 +1216,24 @@ impl Mul<Number> for Number {
     fn mul(self, rhs: Number) -> Self::Output {
         match self {
             Number::Integer(lhs) => match rhs {
-                Number::Integer(rhs) => Number::Integer(lhs.clone() * rhs.clone()),
-                Number::Rational(rhs) => Number::Rational(lhs.clone() * rhs.clone()),
-                Number::Float(rhs) => Number::Float(lhs.clone() * rhs.clone()),
+                Number::Integer(rhs) => Number::mul_integers(lhs, &rhs),
+                Number::Rational(rhs) => Number::Rational(lhs * rhs),
+                Number::Float(rhs) => Number::Float(lhs * rhs),
+                Number::Complex(rhs) => Number::from_complex(Complex::with_val(100, lhs) * rhs),
             },
             Number::Rational(lhs) => match rhs {
-                Number::Float(rhs) => Number::Float(lhs.clone() * rhs.clone()),
-                Number::Integer(rhs) => Number::Rational(lhs.clone() * rhs.clone()),
-                Number::Rational(rhs) => Number::Rational(lhs.clone() * rhs.clone()),
+                Number::Float(rhs) => Number::Float(lhs * rhs),
+                Number::Integer(rhs) => Number::Rational(lhs * rhs),
+                Number::Rational(rhs) => Number::Rational(lhs * rhs),
+                Number::Complex(rhs) => Number::from_complex(Complex::with_val(100, lhs) * rhs),
             },
             Number::Float(lhs) => match rhs {
-                Number::Integer(rhs) => Number::Float(lhs.clone() * rhs.clone()),
-                Number::Rational(rhs) => Number::Float(lhs.clone() * rhs.clone()),
-                Number::Float(rhs) => Number::Float(lhs.clone() * rhs.clone()),
+                Number::Integer(rhs) => Number::Float(lhs * rhs),
+                Number::Rational(rhs) => Number::Float(lhs * rhs),
+                Number::Float(rhs) => Number::Float(lhs * rhs),
+                Number::Complex(rhs) => Number::from_complex(Complex::with_val(100, lhs) * rhs),
             },
+            Number::Complex(lhs) => Number::from_complex(lhs * rhs.to_complex()),
         }
     }
 }
@@ -409,24 +1242,30 @@ impl Div<Number> for Number {
     type Output = Number;
 
     fn div(self, rhs: Number) -> Self::Output {
+        if rhs.is_exact_zero() {
+            return self.divide_by_zero();
+        }
+
         match self {
             Number::Integer(lhs) => match rhs {
-                Number::Integer(rhs) => {
-                    Number::Rational(Rational::from((lhs.clone(), rhs.clone())))
-                }
-                Number::Rational(rhs) => Number::Rational(lhs.clone() / rhs.clone()),
-                Number::Float(rhs) => Number::Float(lhs.clone() / rhs.clone()),
+                Number::Integer(rhs) => Number::Rational(Rational::from((lhs, rhs))),
+                Number::Rational(rhs) => Number::Rational(lhs / rhs),
+                Number::Float(rhs) => Number::Float(lhs / rhs),
+                Number::Complex(rhs) => Number::from_complex(Complex::with_val(100, lhs) / rhs),
             },
             Number::Rational(lhs) => match rhs {
-                Number::Float(rhs) => Number::Float(lhs.clone() / rhs.clone()),
-                Number::Integer(rhs) => Number::Rational(lhs.clone() / rhs.clone()),
-                Number::Rational(rhs) => Number::Rational(lhs.clone() / rhs.clone()),
+                Number::Float(rhs) => Number::Float(lhs / rhs),
+                Number::Integer(rhs) => Number::Rational(lhs / rhs),
+                Number::Rational(rhs) => Number::Rational(lhs / rhs),
+                Number::Complex(rhs) => Number::from_complex(Complex::with_val(100, lhs) / rhs),
             },
             Number::Float(lhs) => match rhs {
-                Number::Integer(rhs) => Number::Float(lhs.clone() / rhs.clone()),
-                Number::Rational(rhs) => Number::Float(lhs.clone() / rhs.clone()),
-                Number::Float(rhs) => Number::Float(lhs.clone() / rhs.clone()),
+                Number::Integer(rhs) => Number::Float(lhs / rhs),
+                Number::Rational(rhs) => Number::Float(lhs / rhs),
+                Number::Float(rhs) => Number::Float(lhs / rhs),
+                Number::Complex(rhs) => Number::from_complex(Complex::with_val(100, lhs) / rhs),
             },
+            Number::Complex(lhs) => Number::from_complex(lhs / rhs.to_complex()),
         }
     }
 }
@@ -436,10 +1275,134 @@ impl Neg for Number {
 
     fn neg(self) -> Self::Output {
         match self {
-            Number::Integer(lhs) => Number::Integer(-lhs),
+            Number::Integer(lhs) => Number::neg_integer(lhs),
             Number::Rational(lhs) => Number::Rational(-lhs),
             Number::Float(lhs) => Number::Float(-lhs),
+            Number::Complex(lhs) => Number::Complex(-lhs),
+        }
+    }
+}
+
+impl AddAssign<&Number> for Number {
+    fn add_assign(&mut self, rhs: &Number) {
+        match self {
+            Number::Integer(lhs) => match rhs {
+                Number::Integer(rhs) => *self = Number::add_integers(std::mem::take(lhs), rhs),
+                _ => *self = &*self + rhs,
+            },
+            Number::Rational(lhs) => match rhs {
+                Number::Integer(rhs) => *lhs += rhs,
+                Number::Rational(rhs) => *lhs += rhs,
+                Number::Float(_) | Number::Complex(_) => *self = &*self + rhs,
+            },
+            Number::Float(lhs) => match rhs {
+                Number::Integer(rhs) => *lhs += rhs,
+                Number::Rational(rhs) => *lhs += rhs,
+                Number::Float(rhs) => *lhs += rhs,
+                Number::Complex(_) => *self = &*self + rhs,
+            },
+            Number::Complex(_) => *self = &*self + rhs,
+        }
+    }
+}
+
+impl SubAssign<&Number> for Number {
+    fn sub_assign(&mut self, rhs: &Number) {
+        match self {
+            Number::Integer(lhs) => match rhs {
+                Number::Integer(rhs) => *self = Number::sub_integers(std::mem::take(lhs), rhs),
+                _ => *self = &*self - rhs,
+            },
+            Number::Rational(lhs) => match rhs {
+                Number::Integer(rhs) => *lhs -= rhs,
+                Number::Rational(rhs) => *lhs -= rhs,
+                Number::Float(_) | Number::Complex(_) => *self = &*self - rhs,
+            },
+            Number::Float(lhs) => match rhs {
+                Number::Integer(rhs) => *lhs -= rhs,
+                Number::Rational(rhs) => *lhs -= rhs,
+                Number::Float(rhs) => *lhs -= rhs,
+                Number::Complex(_) => *self = &*self - rhs,
+            },
+            Number::Complex(_) => *self = &*self - rhs,
+        }
+    }
+}
+
+impl MulAssign<&Number> for Number {
+    fn mul_assign(&mut self, rhs: &Number) {
+        match self {
+            Number::Integer(lhs) => match rhs {
+                Number::Integer(rhs) => *self = Number::mul_integers(std::mem::take(lhs), rhs),
+                _ => *self = &*self * rhs,
+            },
+            Number::Rational(lhs) => match rhs {
+                Number::Integer(rhs) => *lhs *= rhs,
+                Number::Rational(rhs) => *lhs *= rhs,
+                Number::Float(_) | Number::Complex(_) => *self = &*self * rhs,
+            },
+            Number::Float(lhs) => match rhs {
+                Number::Integer(rhs) => *lhs *= rhs,
+                Number::Rational(rhs) => *lhs *= rhs,
+                Number::Float(rhs) => *lhs *= rhs,
+                Number::Complex(_) => *self = &*self * rhs,
+            },
+            Number::Complex(_) => *self = &*self * rhs,
+        }
+    }
+}
+
+impl DivAssign<&Number> for Number {
+    fn div_assign(&mut self, rhs: &Number) {
+        if rhs.is_exact_zero() {
+            *self = self.divide_by_zero();
+            return;
         }
+
+        match self {
+            Number::Rational(lhs) => match rhs {
+                Number::Integer(rhs) => *lhs /= rhs,
+                Number::Rational(rhs) => *lhs /= rhs,
+                Number::Float(_) | Number::Complex(_) => {
+                    *self = Number::Rational(std::mem::take(lhs)) / rhs.clone();
+                }
+            },
+            Number::Float(lhs) => match rhs {
+                Number::Integer(rhs) => *lhs /= rhs,
+                Number::Rational(rhs) => *lhs /= rhs,
+                Number::Float(rhs) => *lhs /= rhs,
+                Number::Complex(_) => *self = &*self / rhs,
+            },
+            Number::Complex(_) => *self = &*self / rhs,
+            // integer division produces a Rational, same as `Div<Number> for Number`
+            Number::Integer(lhs) => {
+                *self = Number::Integer(std::mem::take(lhs)) / rhs.clone();
+            }
+        }
+    }
+}
+
+impl AddAssign<Number> for Number {
+    fn add_assign(&mut self, rhs: Number) {
+        *self += &rhs;
+    }
+}
+
+impl SubAssign<Number> for Number {
+    fn sub_assign(&mut self, rhs: Number) {
+        *self -= &rhs;
+    }
+}
+
+impl MulAssign<Number> for Number {
+    fn mul_assign(&mut self, rhs: Number) {
+        *self *= &rhs;
+    }
+}
+
+impl DivAssign<Number> for Number {
+    fn div_assign(&mut self, rhs: Number) {
+        *self /= &rhs;
     }
 }
 
@@ -448,9 +1411,10 @@ impl Add<i32> for Number {
 
     fn add(self, rhs: i32) -> Self::Output {
         match self {
-            Number::Integer(lhs) => Number::Integer(lhs.clone() + rhs),
-            Number::Rational(lhs) => Number::Rational(lhs.clone() + rhs),
-            Number::Float(lhs) => Number::Float(lhs.clone() + rhs),
+            Number::Integer(lhs) => Number::add_integers(lhs, &Integer::from(rhs)),
+            Number::Rational(lhs) => Number::Rational(lhs + rhs),
+            Number::Float(lhs) => Number::Float(lhs + rhs),
+            Number::Complex(lhs) => Number::from_complex(lhs + Complex::with_val(100, rhs)),
         }
     }
 }
@@ -460,9 +1424,10 @@ impl Sub<i32> for Number {
 
     fn sub(self, rhs: i32) -> Self::Output {
         match self {
-            Number::Integer(lhs) => Number::Integer(lhs.clone() - rhs),
-            Number::Rational(lhs) => Number::Rational(lhs.clone() - rhs),
-            Number::Float(lhs) => Number::Float(lhs.clone() - rhs),
+            Number::Integer(lhs) => Number::sub_integers(lhs, &Integer::from(rhs)),
+            Number::Rational(lhs) => Number::Rational(lhs - rhs),
+            Number::Float(lhs) => Number::Float(lhs - rhs),
+            Number::Complex(lhs) => Number::from_complex(lhs - Complex::with_val(100, rhs)),
         }
     }
 }
@@ -472,9 +1437,10 @@ impl Mul<i32> for Number {
 
     fn mul(self, rhs: i32) -> Self::Output {
         match self {
-            Number::Integer(lhs) => Number::Integer(lhs.clone() * rhs),
-            Number::Rational(lhs) => Number::Rational(lhs.clone() * rhs),
-            Number::Float(lhs) => Number::Float(lhs.clone() * rhs),
+            Number::Integer(lhs) => Number::mul_integers(lhs, &Integer::from(rhs)),
+            Number::Rational(lhs) => Number::Rational(lhs * rhs),
+            Number::Float(lhs) => Number::Float(lhs * rhs),
+            Number::Complex(lhs) => Number::from_complex(lhs * Complex::with_val(100, rhs)),
         }
     }
 }
@@ -484,9 +1450,10 @@ impl Div<i32> for Number {
 
     fn div(self, rhs: i32) -> Self::Output {
         match self {
-            Number::Integer(lhs) => Number::Integer(lhs.clone() / rhs),
-            Number::Rational(lhs) => Number::Rational(lhs.clone() / rhs),
-            Number::Float(lhs) => Number::Float(lhs.clone() / rhs),
+            Number::Integer(lhs) => Number::Integer(lhs / rhs),
+            Number::Rational(lhs) => Number::Rational(lhs / rhs),
+            Number::Float(lhs) => Number::Float(lhs / rhs),
+            Number::Complex(lhs) => Number::from_complex(lhs / Complex::with_val(100, rhs)),
         }
     }
 }
@@ -496,9 +1463,10 @@ impl Add<i64> for Number {
 
     fn add(self, rhs: i64) -> Self::Output {
         match self {
-            Number::Integer(lhs) => Number::Integer(lhs.clone() + rhs),
-            Number::Rational(lhs) => Number::Rational(lhs.clone() + rhs),
-            Number::Float(lhs) => Number::Float(lhs.clone() + rhs),
+            Number::Integer(lhs) => Number::add_integers(lhs, &Integer::from(rhs)),
+            Number::Rational(lhs) => Number::Rational(lhs + rhs),
+            Number::Float(lhs) => Number::Float(lhs + rhs),
+            Number::Complex(lhs) => Number::from_complex(lhs + Complex::with_val(100, rhs)),
         }
     }
 }
@@ -508,9 +1476,10 @@ impl Sub<i64> for Number {
 
     fn sub(self, rhs: i64) -> Self::Output {
         match self {
-            Number::Integer(lhs) => Number::Integer(lhs.clone() - rhs),
-            Number::Rational(lhs) => Number::Rational(lhs.clone() - rhs),
-            Number::Float(lhs) => Number::Float(lhs.clone() - rhs),
+            Number::Integer(lhs) => Number::sub_integers(lhs, &Integer::from(rhs)),
+            Number::Rational(lhs) => Number::Rational(lhs - rhs),
+            Number::Float(lhs) => Number::Float(lhs - rhs),
+            Number::Complex(lhs) => Number::from_complex(lhs - Complex::with_val(100, rhs)),
         }
     }
 }
@@ -520,9 +1489,10 @@ impl Mul<i64> for Number {
 
     fn mul(self, rhs: i64) -> Self::Output {
         match self {
-            Number::Integer(lhs) => Number::Integer(lhs.clone() * rhs),
-            Number::Rational(lhs) => Number::Rational(lhs.clone() * rhs),
-            Number::Float(lhs) => Number::Float(lhs.clone() * rhs),
+            Number::Integer(lhs) => Number::mul_integers(lhs, &Integer::from(rhs)),
+            Number::Rational(lhs) => Number::Rational(lhs * rhs),
+            Number::Float(lhs) => Number::Float(lhs * rhs),
+            Number::Complex(lhs) => Number::from_complex(lhs * Complex::with_val(100, rhs)),
         }
     }
 }
@@ -532,9 +1502,10 @@ impl Div<i64> for Number {
 
     fn div(self, rhs: i64) -> Self::Output {
         match self {
-            Number::Integer(lhs) => Number::Integer(lhs.clone() / rhs),
-            Number::Rational(lhs) => Number::Rational(lhs.clone() / rhs),
-            Number::Float(lhs) => Number::Float(lhs.clone() / rhs),
+            Number::Integer(lhs) => Number::Integer(lhs / rhs),
+            Number::Rational(lhs) => Number::Rational(lhs / rhs),
+            Number::Float(lhs) => Number::Float(lhs / rhs),
+            Number::Complex(lhs) => Number::from_complex(lhs / Complex::with_val(100, rhs)),
         }
     }
 }
@@ -546,7 +1517,8 @@ impl Add<f32> for Number {
         match self {
             Number::Integer(lhs) => Number::Float(Float::with_val(100, lhs) + rhs),
             Number::Rational(lhs) => Number::Float(Float::with_val(100, lhs) + rhs),
-            Number::Float(lhs) => Number::Float(lhs.clone() + rhs),
+            Number::Float(lhs) => Number::Float(lhs + rhs),
+            Number::Complex(lhs) => Number::from_complex(lhs + Complex::with_val(100, rhs)),
         }
     }
 }
@@ -558,7 +1530,8 @@ impl Sub<f32> for Number {
         match self {
             Number::Integer(lhs) => Number::Float(Float::with_val(100, lhs) - rhs),
             Number::Rational(lhs) => Number::Float(Float::with_val(100, lhs) - rhs),
-            Number::Float(lhs) => Number::Float(lhs.clone() - rhs),
+            Number::Float(lhs) => Number::Float(lhs - rhs),
+            Number::Complex(lhs) => Number::from_complex(lhs - Complex::with_val(100, rhs)),
         }
     }
 }
@@ -570,7 +1543,8 @@ impl Mul<f32> for Number {
         match self {
             Number::Integer(lhs) => Number::Float(Float::with_val(100, lhs) * rhs),
             Number::Rational(lhs) => Number::Float(Float::with_val(100, lhs) * rhs),
-            Number::Float(lhs) => Number::Float(lhs.clone() * rhs),
+            Number::Float(lhs) => Number::Float(lhs * rhs),
+            Number::Complex(lhs) => Number::from_complex(lhs * Complex::with_val(100, rhs)),
         }
     }
 }
@@ -582,7 +1556,8 @@ impl Div<f32> for Number {
         match self {
             Number::Integer(lhs) => Number::Float(Float::with_val(100, lhs) / rhs),
             Number::Rational(lhs) => Number::Float(Float::with_val(100, lhs) / rhs),
-            Number::Float(lhs) => Number::Float(lhs.clone() / rhs),
+            Number::Float(lhs) => Number::Float(lhs / rhs),
+            Number::Complex(lhs) => Number::from_complex(lhs / Complex::with_val(100, rhs)),
         }
     }
 }
@@ -594,7 +1569,8 @@ impl Add<f64> for Number {
         match self {
             Number::Integer(lhs) => Number::Float(Float::with_val(100, lhs) + rhs),
             Number::Rational(lhs) => Number::Float(Float::with_val(100, lhs) + rhs),
-            Number::Float(lhs) => Number::Float(lhs.clone() + rhs),
+            Number::Float(lhs) => Number::Float(lhs + rhs),
+            Number::Complex(lhs) => Number::from_complex(lhs + Complex::with_val(100, rhs)),
         }
     }
 }
@@ -606,7 +1582,8 @@ impl Sub<f64> for Number {
         match self {
             Number::Integer(lhs) => Number::Float(Float::with_val(100, lhs) - rhs),
             Number::Rational(lhs) => Number::Float(Float::with_val(100, lhs) - rhs),
-            Number::Float(lhs) => Number::Float(lhs.clone() - rhs),
+            Number::Float(lhs) => Number::Float(lhs - rhs),
+            Number::Complex(lhs) => Number::from_complex(lhs - Complex::with_val(100, rhs)),
         }
     }
 }
@@ -618,7 +1595,8 @@ impl Mul<f64> for Number {
         match self {
             Number::Integer(lhs) => Number::Float(Float::with_val(100, lhs) * rhs),
             Number::Rational(lhs) => Number::Float(Float::with_val(100, lhs) * rhs),
-            Number::Float(lhs) => Number::Float(lhs.clone() * rhs),
+            Number::Float(lhs) => Number::Float(lhs * rhs),
+            Number::Complex(lhs) => Number::from_complex(lhs * Complex::with_val(100, rhs)),
         }
     }
 }
@@ -630,7 +1608,557 @@ impl Div<f64> for Number {
         match self {
             Number::Integer(lhs) => Number::Float(Float::with_val(100, lhs) / rhs),
             Number::Rational(lhs) => Number::Float(Float::with_val(100, lhs) / rhs),
-            Number::Float(lhs) => Number::Float(lhs.clone() / rhs),
+            Number::Float(lhs) => Number::Float(lhs / rhs),
+            Number::Complex(lhs) => Number::from_complex(lhs / Complex::with_val(100, rhs)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pow_integer_1() {
+        let mut naive = Integer::from(1);
+        for _ in 0..20 {
+            naive *= 3;
+        }
+
+        let result = Number::from(3).pow(&Number::from(20));
+        assert_eq!(result, Number::Integer(naive));
+        assert_eq!(Number::from(3).pow(&Number::from(0)), Number::from(1));
+    }
+
+    #[test]
+    fn test_from_usize_1() {
+        assert_eq!(Number::from(10usize), Number::from(10));
+        assert!(matches!(Number::from(10usize), Number::Integer(_)));
+        assert_eq!(Number::from(usize::MAX).to_string(), usize::MAX.to_string());
+        assert_eq!(Number::from(-10isize), Number::from(-10));
+        assert_eq!(Number::from(isize::MIN).to_string(), isize::MIN.to_string());
+    }
+
+    #[test]
+    fn test_assign_ops_1() {
+        let numbers: Vec<Number> = vec![
+            Number::from(6),
+            Number::from(-4),
+            Number::from(3) / Number::from(4),
+            Number::from(2.5),
+            Number::from(1) + Number::from(-4).pow(&(Number::from(1) / Number::from(2))),
+        ];
+
+        for a in numbers.iter() {
+            for b in numbers.iter() {
+                let mut result: Number = a.clone();
+                result += b;
+                assert_eq!(result, a.clone() + b.clone(), "{:?} += {:?}", a, b);
+                let mut result: Number = a.clone();
+                result += b.clone();
+                assert_eq!(result, a.clone() + b.clone(), "{:?} += {:?}", a, b);
+
+                let mut result: Number = a.clone();
+                result -= b;
+                assert_eq!(result, a.clone() - b.clone(), "{:?} -= {:?}", a, b);
+                let mut result: Number = a.clone();
+                result -= b.clone();
+                assert_eq!(result, a.clone() - b.clone(), "{:?} -= {:?}", a, b);
+
+                let mut result: Number = a.clone();
+                result *= b;
+                assert_eq!(result, a.clone() * b.clone(), "{:?} *= {:?}", a, b);
+                let mut result: Number = a.clone();
+                result *= b.clone();
+                assert_eq!(result, a.clone() * b.clone(), "{:?} *= {:?}", a, b);
+
+                let mut result: Number = a.clone();
+                result /= b;
+                assert_eq!(result, a.clone() / b.clone(), "{:?} /= {:?}", a, b);
+                let mut result: Number = a.clone();
+                result /= b.clone();
+                assert_eq!(result, a.clone() / b.clone(), "{:?} /= {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_powmod_1() {
+        let powmod =
+            |b: i64, e: i64, m: i64| Number::from(b).powmod(&Number::from(e), &Number::from(m));
+
+        assert_eq!(powmod(3, 4, 5).unwrap(), Number::from(1));
+        assert_eq!(powmod(7, 5, 1000).unwrap(), Number::from(807));
+        assert_eq!(powmod(2, 0, 7).unwrap(), Number::from(1));
+        assert_eq!(powmod(5, 3, 1).unwrap(), Number::from(0));
+        assert_eq!(powmod(-2, 3, 5).unwrap(), Number::from(2));
+        assert_eq!(powmod(2, 10, -7).unwrap(), Number::from(2));
+        // 7 * 143 = 1 modulo 1000
+        assert_eq!(powmod(7, -5, 1000).unwrap(), Number::from(943));
+        // Fermat's little theorem for the prime 2^61 - 1
+        let p: i64 = 2_305_843_009_213_693_951;
+        assert_eq!(powmod(1_000_000_007, p - 1, p).unwrap(), Number::from(1));
+        assert_eq!(
+            Number::from(4)
+                .powmod(&(Number::from(4) / Number::from(2)), &Number::from(5))
+                .unwrap(),
+            Number::from(1)
+        );
+
+        assert!(matches!(powmod(2, -1, 4), Err(MathError::DomainError)));
+        assert!(matches!(powmod(3, 4, 0), Err(MathError::ZeroDivisionError)));
+        assert!(matches!(
+            Number::from(2.5).powmod(&Number::from(2), &Number::from(5)),
+            Err(MathError::DomainError)
+        ));
+        assert!(matches!(
+            Number::from(2).powmod(&(Number::from(1) / Number::from(2)), &Number::from(5)),
+            Err(MathError::DomainError)
+        ));
+    }
+
+    #[test]
+    fn test_to_rational_approx_1() {
+        let third: Number = Number::from(1) / Number::from(3);
+        assert_eq!(Number::from(0.3333333).to_rational_approx(100), third);
+        assert_eq!(Number::from(-0.3333333).to_rational_approx(100), -third);
+        assert!(matches!(
+            Number::from(0.3333333).to_rational_approx(100),
+            Number::Rational(_)
+        ));
+
+        // 22/7 and 355/113 are the best approximations of pi below their bounds
+        assert_eq!(
+            Number::pi().to_rational_approx(10),
+            Number::from(22) / Number::from(7)
+        );
+        assert_eq!(
+            Number::pi().to_rational_approx(1000),
+            Number::from(355) / Number::from(113)
+        );
+        // a semiconvergent, between the convergents 1/3 and 3/10
+        assert_eq!(
+            Number::from(0.3).to_rational_approx(8),
+            Number::from(2) / Number::from(7)
+        );
+
+        assert_eq!(Number::from(2.5).to_rational_approx(1), Number::from(2));
+        assert_eq!(Number::from(4).to_rational_approx(1), Number::from(4));
+        assert_eq!(
+            (Number::from(5) / Number::from(7)).to_rational_approx(7),
+            Number::from(5) / Number::from(7)
+        );
+        assert!(Number::from(f64::NAN).to_rational_approx(10).is_nan());
+    }
+
+    #[test]
+    fn test_signum_1() {
+        let cases: Vec<(Number, i64)> = vec![
+            (Number::from(7), 1),
+            (Number::from(-3), -1),
+            (Number::from(0), 0),
+            (Number::from(-1) / Number::from(3), -1),
+            (Number::from(2) / Number::from(5), 1),
+            (Number::from(-0.25), -1),
+            (Number::from(1e-30), 1),
+            (Number::from(0.0), 0),
+            (Number::from(-0.0), 0),
+            (
+                Number::from(-2) + Number::from(-1).pow(&(Number::from(1) / Number::from(2))),
+                -1,
+            ),
+            (
+                Number::from(-1).pow(&(Number::from(1) / Number::from(2))),
+                1,
+            ),
+        ];
+
+        for (number, expected) in cases {
+            let signum: Number = number.signum();
+            assert!(matches!(signum, Number::Integer(_)), "{:?}", number);
+            assert_eq!(signum, Number::from(expected), "{:?}", number);
+        }
+
+        assert!(Number::from(f64::NAN).signum().is_nan());
+    }
+
+    #[test]
+    fn test_hash_1() {
+        use std::collections::hash_map::DefaultHasher;
+
+        let hash = |n: &Number| {
+            let mut hasher = DefaultHasher::new();
+            n.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        assert_eq!(hash(&Number::from(2)), hash(&Number::from(2.0)));
+        assert_eq!(
+            hash(&(Number::from(5) / Number::from(2))),
+            hash(&Number::from(2.5))
+        );
+        assert_eq!(hash(&Number::from(0.0)), hash(&Number::from(-0.0)));
+        assert_eq!(hash(&Number::from(f64::NAN)), hash(&Number::from(f64::NAN)));
+        assert_eq!(
+            hash(&Number::from(-4).pow(&(Number::from(1) / Number::from(2)))),
+            hash(&Number::from(-4).pow(&(Number::from(1) / Number::from(2))))
+        );
+        assert_ne!(hash(&Number::from(2)), hash(&Number::from(3)));
+    }
+
+    #[test]
+    fn test_pow_negative_exponent_1() {
+        assert_eq!(
+            Number::from(2).pow(&Number::from(-2)),
+            Number::Rational(Rational::from((1, 4)))
+        );
+        assert_eq!(
+            Number::Rational(Rational::from((2, 3))).pow(&Number::from(-3)),
+            Number::Rational(Rational::from((27, 8)))
+        );
+        assert_eq!(
+            Number::from(-2).pow(&Number::from(-1)),
+            Number::Rational(Rational::from((-1, 2)))
+        );
+        assert_eq!(Number::from(4.0).pow(&Number::from(-1)), Number::from(0.25));
+        assert!(matches!(
+            Number::from(0).real_pow(&Number::from(-1)),
+            Err(MathError::ZeroDivisionError)
+        ));
+    }
+
+    #[test]
+    fn test_pow_rational_1() {
+        let base = Number::Rational(Rational::from((2, 3)));
+        let result = base.pow(&Number::from(5));
+        assert_eq!(result, Number::Rational(Rational::from((32, 243))));
+    }
+
+    #[test]
+    fn test_numerator_denominator_1() {
+        let three_quarters: Number = Number::from(3) / Number::from(4);
+        assert_eq!(three_quarters.numerator(), Number::from(3));
+        assert_eq!(three_quarters.denominator(), Number::from(4));
+        assert!(matches!(three_quarters.numerator(), Number::Integer(_)));
+
+        assert_eq!((-three_quarters.clone()).numerator(), Number::from(-3));
+        assert_eq!((-three_quarters).denominator(), Number::from(4));
+        assert_eq!(
+            Number::Rational(Rational::from((6, 8))).denominator(),
+            Number::from(4)
+        );
+
+        assert_eq!(Number::from(5).numerator(), Number::from(5));
+        assert_eq!(Number::from(5).denominator(), Number::from(1));
+        assert_eq!(Number::from(2.5).numerator(), Number::from(2.5));
+        assert_eq!(Number::from(2.5).denominator(), Number::from(1));
+
+        let fraction = |numerator: i32, denominator: i32| {
+            Number::Rational(Rational::from((numerator, denominator)))
+        };
+        assert_eq!(
+            fraction(1, 4).lcm_denominators(&fraction(5, 6)),
+            Number::from(12)
+        );
+        assert_eq!(
+            fraction(1, 4).lcm_denominators(&fraction(-3, 4)),
+            Number::from(4)
+        );
+        assert_eq!(
+            Number::from(7).lcm_denominators(&fraction(2, 9)),
+            Number::from(9)
+        );
+    }
+
+    #[test]
+    fn test_floor_div_1() {
+        let seven: Number = Number::from(7);
+        let three: Number = Number::from(3);
+
+        assert_eq!(
+            Number::Rational(Rational::from((7, 2))).floor().unwrap(),
+            Number::from(3)
+        );
+        assert_eq!(Number::from(-3.5).floor().unwrap(), Number::from(-4));
+        assert_eq!(seven.floor_div(&three).unwrap(), Number::from(2));
+        assert_eq!(
+            (-seven.clone()).floor_div(&three).unwrap(),
+            Number::from(-3)
+        );
+        assert_eq!((-seven.clone()).rem(&three).unwrap(), Number::from(2));
+        assert_eq!(seven.rem(&-three.clone()).unwrap(), Number::from(-2));
+        assert_eq!(
+            Number::from(7.5).rem(&Number::from(2)).unwrap(),
+            Number::from(1.5)
+        );
+
+        assert!(matches!(
+            seven.floor_div(&Number::from(0)),
+            Err(MathError::ZeroDivisionError)
+        ));
+        assert!(matches!(
+            seven.rem(&Number::from(0.0)),
+            Err(MathError::ZeroDivisionError)
+        ));
+    }
+
+    #[test]
+    fn test_checked_div_1() {
+        let seven: Number = Number::from(7);
+        let two: Number = Number::from(2);
+        let zero: Number = Number::from(0);
+
+        let half: Number = &seven / &two;
+        assert!(matches!(half, Number::Rational(_)));
+        assert_eq!(half, Number::Rational(Rational::from((7, 2))));
+        assert_eq!(&seven / &two, seven.clone() / two.clone());
+        assert_eq!(&Number::from(8) / &two, Number::from(4));
+        assert_eq!(seven.checked_div(&two).unwrap(), half);
+
+        // dividing by an exact zero behaves as for floats instead of panicking
+        assert_eq!(&seven / &zero, Number::from(f64::INFINITY));
+        assert_eq!(
+            -seven.clone() / Number::Rational(Rational::from(0)),
+            Number::from(f64::NEG_INFINITY)
+        );
+        assert!((&zero / &zero).is_nan());
+        let mut value: Number = Number::Rational(Rational::from((1, 3)));
+        value /= &zero;
+        assert_eq!(value, Number::from(f64::INFINITY));
+
+        for divisor in [
+            zero.clone(),
+            Number::from(0.0),
+            Number::Rational(Rational::from(0)),
+        ] {
+            assert!(matches!(
+                seven.checked_div(&divisor),
+                Err(MathError::ZeroDivisionError)
+            ));
         }
     }
+
+    #[test]
+    fn test_pow_exact_root_1() {
+        let pow = |base: Number, numerator: i32, denominator: i32| {
+            base.pow(&(Number::from(numerator) / Number::from(denominator)))
+        };
+
+        assert!(matches!(pow(Number::from(8), 2, 3), Number::Integer(i) if i == 4));
+        assert!(matches!(pow(Number::from(27), -1, 3), Number::Rational(_)));
+        assert_eq!(
+            pow(Number::from(27), -1, 3),
+            Number::from(1) / Number::from(3)
+        );
+        assert_eq!(
+            pow(Number::Rational(Rational::from((4, 9))), 3, 2),
+            Number::Rational(Rational::from((8, 27)))
+        );
+        assert_eq!(
+            pow(Number::from(1_000_000_007i64).pow(&Number::from(3)), 1, 3),
+            Number::from(1_000_000_007i64)
+        );
+        assert!(matches!(pow(Number::from(2), 1, 2), Number::Float(_)));
+        assert!(matches!(pow(Number::from(0), 1, 3), Number::Integer(_)));
+    }
+
+    #[test]
+    #[cfg(feature = "rug")]
+    fn test_pow_large_exponent_1() {
+        let result = Number::from(2).pow(&Number::from(100_000));
+        assert_eq!(result, Number::Integer(Integer::from(1) << 100_000));
+
+        let result = Number::from(3).pow(&Number::from(100_001));
+        assert_eq!(
+            result,
+            Number::Integer(Integer::from(Integer::u_pow_u(3, 100_001)))
+        );
+    }
+
+    #[test]
+    fn test_normalize_1() {
+        assert!(matches!(
+            Number::Rational(Rational::from((4, 2))).normalize(),
+            Number::Integer(_)
+        ));
+        assert!(matches!(Number::from(3.0).normalize(), Number::Integer(_)));
+        assert!(matches!(
+            Number::Rational(Rational::from((1, 2))).normalize(),
+            Number::Rational(_)
+        ));
+        assert!(matches!(Number::from(2.5).normalize(), Number::Float(_)));
+        assert_eq!(Number::from(3.0).normalize(), Number::from(3));
+    }
+
+    #[test]
+    fn test_log_1() {
+        assert_eq!(
+            Number::from(8).log(&Number::from(2)).unwrap(),
+            Number::from(3)
+        );
+        assert!(matches!(
+            Number::from(8).log(&Number::from(2)).unwrap(),
+            Number::Integer(_)
+        ));
+        assert!(matches!(
+            Number::from(0).log(&Number::from(2)),
+            Err(MathError::DomainError)
+        ));
+        assert!(matches!(
+            Number::from(5).log(&Number::from(1)),
+            Err(MathError::DomainError)
+        ));
+        assert!(matches!(Number::from(-1).ln(), Err(MathError::DomainError)));
+        assert_eq!(Number::from(1).ln().unwrap(), Number::from(0));
+    }
+
+    #[test]
+    #[cfg(feature = "rug")]
+    fn test_from_str_1() {
+        let number: Number = "1234567890123456789012345678901234567890".parse().unwrap();
+        let expected: Integer = "1234567890123456789012345678901234567890".parse().unwrap();
+        assert_eq!(number, Number::Integer(expected));
+
+        assert_eq!("2.5".parse::<Number>().unwrap(), Number::from(2.5));
+        assert_eq!("2x".parse::<Number>(), Err(ParseNumberError));
+    }
+
+    #[test]
+    fn test_try_from_1() {
+        assert_eq!(
+            Integer::try_from(Number::from(5)).unwrap(),
+            Integer::from(5)
+        );
+        assert_eq!(
+            Integer::try_from(Number::Rational(Rational::from((6, 3)))).unwrap(),
+            Integer::from(2)
+        );
+        assert!(matches!(
+            Integer::try_from(Number::from(2.5)),
+            Err(MathError::DomainError)
+        ));
+        assert!(matches!(
+            Integer::try_from(Number::Rational(Rational::from((1, 2)))),
+            Err(MathError::DomainError)
+        ));
+
+        assert_eq!(
+            Rational::try_from(Number::from(5)).unwrap(),
+            Rational::from(5)
+        );
+        assert_eq!(
+            Rational::try_from(Number::Rational(Rational::from((1, 2)))).unwrap(),
+            Rational::from((1, 2))
+        );
+        assert!(matches!(
+            Rational::try_from(Number::from(0.5)),
+            Err(MathError::DomainError)
+        ));
+    }
+
+    #[test]
+    fn test_complex_1() {
+        let i = Number::from(-1).pow(&(Number::from(1) / Number::from(2)));
+        assert_eq!(i.to_string(), "1i");
+        assert_eq!(&i * &i, Number::from(-1));
+        assert!(matches!(&i * &i, Number::Integer(_)));
+
+        let z = Number::from(1) + Number::from(2) * i.clone();
+        assert_eq!(z.to_string(), "1 + 2i");
+        assert_eq!((-&z).to_string(), "-1 - 2i");
+        assert_eq!((&z - &z), Number::from(0));
+        assert!(z != Number::from(1));
+        assert!(z > Number::from(1));
+
+        assert!(matches!(z.ln(), Err(MathError::DomainError)));
+        assert!(matches!(
+            i.real_pow(&Number::from(2)),
+            Err(MathError::DomainError)
+        ));
+        assert!(z.to_f64().is_nan());
+    }
+
+    #[test]
+    fn test_cmp_nan_1() {
+        let nan = Number::from(0.0) / Number::from(0.0);
+        assert!(nan.is_nan());
+
+        let mut numbers = [
+            Number::from(2),
+            nan.clone(),
+            Number::from(-1),
+            Number::from(0.5),
+            Number::Rational(Rational::from((1, 3))),
+        ];
+        numbers.sort();
+
+        assert_eq!(
+            numbers[..4],
+            [
+                Number::from(-1),
+                Number::Rational(Rational::from((1, 3))),
+                Number::from(0.5),
+                Number::from(2),
+            ]
+        );
+        assert!(numbers[4].is_nan());
+        assert_eq!(nan, nan.clone());
+        assert_eq!(nan.cmp(&Number::from(i64::MAX)), Ordering::Greater);
+        assert_eq!(nan.cmp(&nan), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_display_1() {
+        let display = |value: Number| value.display().to_string();
+
+        assert_eq!(display(Number::from(5.0)), "5");
+        assert_eq!(display(Number::from(-2.5)), "-2.5");
+        assert_eq!(display(Number::from(0.1)), "0.1");
+        assert_eq!(display(Number::from(0.00025)), "0.00025");
+        assert_eq!(display(Number::from(1e30)), "1e30");
+        assert_eq!(display(Number::from(-1.25e-7)), "-1.25e-7");
+        assert_eq!(display(Number::from(0.0)), "0");
+        assert_eq!(display(Number::pi()), "3.14159265358979");
+        assert_eq!(display(Number::from(1e30).pow(&Number::from(2))), "1e60");
+
+        assert_eq!(display(Number::from(12)), "12");
+        assert_eq!(display(Number::Rational(Rational::from((1, 3)))), "1/3");
+        let i = Number::from(-1).pow(&(Number::from(1) / Number::from(2)));
+        assert_eq!(
+            display(Number::from(0.5) - Number::from(2.0) * i),
+            "0.5 - 2i"
+        );
+    }
+
+    #[test]
+    fn test_magnitude_1() {
+        assert_eq!(Number::from(1234).magnitude(), 3);
+        assert_eq!(Number::from(-1000).magnitude(), 3);
+        assert_eq!(Number::from(7).magnitude(), 0);
+        assert_eq!(Number::from(0).magnitude(), 0);
+
+        assert_eq!(Number::from(0.001).magnitude(), -3);
+        assert_eq!(Number::from(1234.5).magnitude(), 3);
+        assert_eq!(Number::from(-2.5e-7).magnitude(), -7);
+        assert_eq!(Number::from(1e30).magnitude(), 30);
+        // rounds to 1000 as it is displayed
+        assert_eq!(Number::from(999.9999999999999).magnitude(), 3);
+        assert_eq!(Number::from(999.5).magnitude(), 2);
+        assert_eq!(Number::from(f64::INFINITY).magnitude(), 0);
+
+        let rational = |n: i64, d: i64| Number::Rational(Rational::from((n, d)));
+        assert_eq!(rational(1, 1000).magnitude(), -3);
+        assert_eq!(rational(999, 1000).magnitude(), -1);
+        assert_eq!(rational(-1, 3).magnitude(), -1);
+        assert_eq!(rational(10, 3).magnitude(), 0);
+        assert_eq!(rational(100, 7).magnitude(), 1);
+        assert_eq!(rational(0, 5).magnitude(), 0);
+        assert_eq!((Number::from(0) / Number::from(5)).magnitude(), 0);
+
+        let i = Number::from(-1).pow(&(Number::from(1) / Number::from(2)));
+        assert_eq!(
+            (Number::from(0.5) + Number::from(2000.0) * i).magnitude(),
+            3
+        );
+    }
 }
@@ -0,0 +1,517 @@
+use std::fmt;
+use std::fmt::Display;
+
+use crate::equation::PartEquation;
+use crate::math::MathError;
+use crate::number::Number;
+
+/// Univariate polynomial over exact (integer or rational) coefficients.
+/// `coefficients[i]` is the coefficient of `variable ^ i`; trailing zero coefficients are trimmed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+    variable: char,
+    coefficients: Vec<Number>,
+}
+
+impl Polynomial {
+    pub fn new(variable: char, coefficients: Vec<Number>) -> Self {
+        let mut polynomial = Polynomial {
+            variable,
+            coefficients,
+        };
+        polynomial.trim();
+        polynomial
+    }
+
+    pub fn variable(&self) -> char {
+        self.variable
+    }
+
+    pub fn coefficients(&self) -> &[Number] {
+        &self.coefficients
+    }
+
+    /// Degree of the polynomial, `None` for the zero polynomial
+    pub fn degree(&self) -> Option<usize> {
+        if self.coefficients.is_empty() {
+            return None;
+        }
+        Some(self.coefficients.len() - 1)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coefficients.is_empty()
+    }
+
+    /// Value of the polynomial at `x` by Horner's method, without computing any powers of `x`
+    pub fn eval(&self, x: &Number) -> Number {
+        let mut result: Number = Number::from(0);
+
+        for c in self.coefficients.iter().rev() {
+            result = &(&result * x) + c;
+        }
+
+        result.normalize()
+    }
+
+    /// Distinct rational roots in increasing order, by the rational root theorem: every root `p/q`
+    /// of the polynomial scaled to integer coefficients has `p` dividing the lowest non-zero
+    /// coefficient and `q` dividing the leading one.
+    /// Empty if a coefficient is a float, the scaled coefficients do not fit in an `i64`, or the
+    /// lowest non-zero or the leading one is larger than a million in absolute value
+    pub fn rational_roots(&self) -> Vec<Number> {
+        let coefficients: Vec<i64> = match self.integer_coefficients() {
+            Some(c) => c,
+            None => return Vec::new(),
+        };
+
+        let mut roots: Vec<Number> = Vec::new();
+
+        // x ^ k divides the polynomial
+        let lowest: usize = match coefficients.iter().position(|c| *c != 0) {
+            Some(k) => k,
+            None => return roots,
+        };
+        if lowest > 0 {
+            roots.push(Number::from(0));
+        }
+
+        let leading: i64 = coefficients[coefficients.len() - 1];
+        let (numerators, denominators) = match (divisors(coefficients[lowest]), divisors(leading)) {
+            (Some(numerators), Some(denominators)) => (numerators, denominators),
+            _ => return Vec::new(),
+        };
+        for p in numerators.iter() {
+            for q in denominators.iter() {
+                for sign in [1, -1] {
+                    let candidate: Number = (Number::from(sign * p) / Number::from(*q)).normalize();
+                    if !roots.contains(&candidate) && self.eval(&candidate) == Number::from(0) {
+                        roots.push(candidate);
+                    }
+                }
+            }
+        }
+
+        roots.sort();
+        roots
+    }
+
+    // coefficients multiplied by the least common multiple of their denominators
+    fn integer_coefficients(&self) -> Option<Vec<i64>> {
+        let mut fractions: Vec<(i64, i64)> = Vec::with_capacity(self.coefficients.len());
+
+        for c in self.coefficients.iter() {
+            let fraction: (i64, i64) = match c.clone().normalize() {
+                Number::Integer(i) => (i.to_i64()?, 1),
+                Number::Rational(r) => (r.numer().to_i64()?, r.denom().to_i64()?),
+                _ => return None,
+            };
+            fractions.push(fraction);
+        }
+
+        let mut multiple: i64 = 1;
+        for (_, denominator) in fractions.iter() {
+            let divisor: i64 = i64::try_from(gcd(multiple, *denominator)).ok()?;
+            multiple = (multiple / divisor).checked_mul(*denominator)?;
+        }
+
+        fractions
+            .iter()
+            .map(|(numerator, denominator)| numerator.checked_mul(multiple / denominator))
+            .collect()
+    }
+
+    pub fn to_part_equation(&self) -> PartEquation {
+        let mut result: PartEquation = PartEquation::from(0);
+
+        for (i, c) in self.coefficients.iter().enumerate() {
+            if *c == Number::from(0) {
+                continue;
+            }
+
+            let term: PartEquation = if i == 0 {
+                PartEquation::from(c.clone())
+            } else {
+                PartEquation::from(c.clone())
+                    * PartEquation::from(self.variable).pow(&PartEquation::from(Number::from(i)))
+            };
+            result = result + term;
+        }
+
+        result
+    }
+
+    /// Long division over the rationals, returns `(quotient, remainder)`
+    pub fn div_rem(&self, divisor: &Polynomial) -> Result<(Polynomial, Polynomial), MathError> {
+        if self.variable != divisor.variable {
+            return Err(MathError::EquationMismatchError);
+        }
+
+        let divisor_degree: usize = match divisor.degree() {
+            Some(d) => d,
+            None => return Err(MathError::ZeroDivisionError),
+        };
+        let leading: &Number = &divisor.coefficients[divisor_degree];
+
+        let mut remainder: Polynomial = self.clone();
+        let mut quotient: Vec<Number> =
+            vec![Number::from(0); self.coefficients.len().saturating_sub(divisor_degree)];
+
+        while let Some(degree) = remainder.degree() {
+            if degree < divisor_degree {
+                break;
+            }
+
+            let shift: usize = degree - divisor_degree;
+            let factor: Number = remainder.coefficients[degree].clone() / leading.clone();
+
+            for (i, c) in divisor.coefficients.iter().enumerate() {
+                remainder.coefficients[shift + i] -= &(&factor * c);
+            }
+
+            // the leading term is cancelled exactly, drop it even if rounding left a residue
+            remainder.coefficients.pop();
+            remainder.trim();
+            quotient[shift] = factor;
+        }
+
+        Ok((Polynomial::new(self.variable, quotient), remainder))
+    }
+
+    /// Monic greatest common divisor, computed with the Euclidean algorithm
+    pub fn gcd(&self, other: &Polynomial) -> Result<Polynomial, MathError> {
+        if self.variable != other.variable {
+            return Err(MathError::EquationMismatchError);
+        }
+
+        let mut a: Polynomial = self.clone();
+        let mut b: Polynomial = other.clone();
+
+        while !b.is_zero() {
+            let (_, remainder) = a.div_rem(&b)?;
+            a = b;
+            b = remainder;
+        }
+
+        let leading: Number = match a.coefficients.last() {
+            Some(c) => c.clone(),
+            None => return Ok(a),
+        };
+        Ok(a.mul(&Polynomial::constant(a.variable, Number::from(1) / leading)))
+    }
+
+    pub(crate) fn constant(variable: char, value: Number) -> Self {
+        Polynomial::new(variable, vec![value])
+    }
+
+    pub(crate) fn add(&self, other: &Polynomial) -> Self {
+        let length = self.coefficients.len().max(other.coefficients.len());
+        let mut coefficients: Vec<Number> = Vec::with_capacity(length);
+
+        for i in 0..length {
+            let mut c = Number::from(0);
+            if let Some(x) = self.coefficients.get(i) {
+                c += x;
+            }
+            if let Some(x) = other.coefficients.get(i) {
+                c += x;
+            }
+            coefficients.push(c);
+        }
+
+        Polynomial::new(self.variable, coefficients)
+    }
+
+    pub(crate) fn neg(&self) -> Self {
+        Polynomial::new(
+            self.variable,
+            self.coefficients.iter().map(|x| -x).collect(),
+        )
+    }
+
+    pub(crate) fn mul(&self, other: &Polynomial) -> Self {
+        if self.is_zero() || other.is_zero() {
+            return Polynomial::new(self.variable, Vec::new());
+        }
+
+        let mut coefficients: Vec<Number> =
+            vec![Number::from(0); self.coefficients.len() + other.coefficients.len() - 1];
+
+        for (i, a) in self.coefficients.iter().enumerate() {
+            for (j, b) in other.coefficients.iter().enumerate() {
+                coefficients[i + j] += &(a * b);
+            }
+        }
+
+        Polynomial::new(self.variable, coefficients)
+    }
+
+    pub(crate) fn pow(&self, exponent: u32) -> Self {
+        let mut result: Polynomial = Polynomial::constant(self.variable, Number::from(1));
+        for _ in 0..exponent {
+            result = result.mul(self);
+        }
+        result
+    }
+
+    fn trim(&mut self) {
+        while let Some(c) = self.coefficients.last() {
+            if *c != Number::from(0) {
+                break;
+            }
+            self.coefficients.pop();
+        }
+    }
+}
+
+// of the absolute values, as a u64 since gcd(i64::MIN, 0) is 2 ^ 63
+pub(crate) fn gcd(a: i64, b: i64) -> u64 {
+    let (mut a, mut b): (u64, u64) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Largest coefficient, in absolute value, whose divisors `Polynomial::rational_roots` tries
+const MAX_ROOT_SEARCH: u64 = 1_000_000;
+
+// positive divisors of a non-zero integer by trial division, None past `MAX_ROOT_SEARCH`.
+// `%` rather than `is_multiple_of`, which needs Rust 1.87
+#[allow(clippy::manual_is_multiple_of)]
+fn divisors(n: i64) -> Option<Vec<i64>> {
+    let n: u64 = n.unsigned_abs();
+    if n > MAX_ROOT_SEARCH {
+        return None;
+    }
+    let mut small: Vec<i64> = Vec::new();
+    let mut large: Vec<i64> = Vec::new();
+
+    let mut i: u64 = 1;
+    while i * i <= n {
+        if n % i == 0 {
+            small.push(i as i64);
+            if i * i != n {
+                large.push((n / i) as i64);
+            }
+        }
+        i += 1;
+    }
+
+    small.extend(large.into_iter().rev());
+    Some(small)
+}
+
+impl Display for Polynomial {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_part_equation())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_polynomial_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let eq: PartEquation = x.pow(&PartEquation::from(2)) + 2 * &x + 1;
+
+        let polynomial = eq.to_polynomial('x').unwrap();
+        assert_eq!(polynomial.variable(), 'x');
+        assert_eq!(
+            polynomial.coefficients(),
+            &[Number::from(1), Number::from(2), Number::from(1)]
+        );
+        assert_eq!(polynomial.to_part_equation(), eq);
+    }
+
+    #[test]
+    fn test_to_polynomial_2() {
+        let x: PartEquation = PartEquation::from('x');
+        let eq: PartEquation = (&x + 1) * (3 * &x - 2) / 2;
+
+        let polynomial = eq.to_polynomial('x').unwrap();
+        assert_eq!(polynomial.degree(), Some(2));
+        assert_eq!(
+            polynomial.coefficients(),
+            &[Number::from(-1), Number::from(0.5), Number::from(1.5)]
+        );
+    }
+
+    #[test]
+    fn test_to_polynomial_3() {
+        let x: PartEquation = PartEquation::from('x');
+        let y: PartEquation = PartEquation::from('y');
+
+        assert_eq!((&x + &y).to_polynomial('x'), None);
+        assert_eq!((&x + 1.5).to_polynomial('x'), None);
+        assert_eq!((PartEquation::from(1) / &x).to_polynomial('x'), None);
+    }
+
+    #[test]
+    fn test_eval_1() {
+        let x: PartEquation = PartEquation::from('x');
+        let eq: PartEquation = x.pow(&PartEquation::from(3)) - &x * 2 + 1;
+        let polynomial = eq.to_polynomial('x').unwrap();
+
+        for value in [Number::from(3), Number::from(-2), Number::from(0)] {
+            assert_eq!(
+                polynomial.eval(&value),
+                eq.substitute('x', &PartEquation::from(value.clone()))
+                    .evaluate()
+                    .unwrap()
+            );
+        }
+        assert_eq!(polynomial.eval(&Number::from(3)), Number::from(22));
+        assert_eq!(
+            polynomial.eval(&(Number::from(1) / Number::from(2))),
+            Number::from(1) / Number::from(8)
+        );
+        assert_eq!(
+            Polynomial::new('x', vec![]).eval(&Number::from(5)),
+            Number::from(0)
+        );
+    }
+
+    #[test]
+    fn test_rational_roots_1() {
+        let x: PartEquation = PartEquation::from('x');
+
+        let polynomial = (x.pow(&PartEquation::from(2)) - &x * 5 + 6)
+            .to_polynomial('x')
+            .unwrap();
+        assert_eq!(
+            polynomial.rational_roots(),
+            vec![Number::from(2), Number::from(3)]
+        );
+
+        let polynomial = (x.pow(&PartEquation::from(2)) * 2 - &x * 3 + 1)
+            .to_polynomial('x')
+            .unwrap();
+        assert_eq!(
+            polynomial.rational_roots(),
+            vec![Number::from(1) / Number::from(2), Number::from(1)]
+        );
+
+        // x (x - 2/3) (x + 4) ^ 2, with rational coefficients
+        let polynomial = (&x
+            * (&x - PartEquation::from(2) / PartEquation::from(3))
+            * (&x + 4).pow(&PartEquation::from(2)))
+        .to_polynomial('x')
+        .unwrap();
+        assert_eq!(
+            polynomial.rational_roots(),
+            vec![
+                Number::from(-4),
+                Number::from(0),
+                Number::from(2) / Number::from(3)
+            ]
+        );
+
+        let polynomial = (x.pow(&PartEquation::from(2)) - 2)
+            .to_polynomial('x')
+            .unwrap();
+        assert!(polynomial.rational_roots().is_empty());
+
+        // (x - 1) (x - 1000003), the constant is past the search limit
+        let polynomial = (x.pow(&PartEquation::from(2)) - &x * 1_000_004 + 1_000_003)
+            .to_polynomial('x')
+            .unwrap();
+        assert!(polynomial.rational_roots().is_empty());
+        let polynomial = (x.pow(&PartEquation::from(3)) + i64::MIN)
+            .to_polynomial('x')
+            .unwrap();
+        assert!(polynomial.rational_roots().is_empty());
+    }
+
+    #[test]
+    fn test_div_rem_1() {
+        let x: PartEquation = PartEquation::from('x');
+
+        let dividend = (x.pow(&PartEquation::from(2)) - 1)
+            .to_polynomial('x')
+            .unwrap();
+        let divisor = (&x - 1).to_polynomial('x').unwrap();
+
+        let (quotient, remainder) = dividend.div_rem(&divisor).unwrap();
+        assert_eq!(quotient.to_part_equation(), &x + 1);
+        assert!(remainder.is_zero());
+    }
+
+    #[test]
+    fn test_div_rem_2() {
+        let x: PartEquation = PartEquation::from('x');
+
+        let dividend = (x.pow(&PartEquation::from(3)) * 2 + &x + 3)
+            .to_polynomial('x')
+            .unwrap();
+        let divisor = (x.pow(&PartEquation::from(2)) * 2 - 1)
+            .to_polynomial('x')
+            .unwrap();
+
+        let (quotient, remainder) = dividend.div_rem(&divisor).unwrap();
+        assert_eq!(quotient.to_part_equation(), x.clone());
+        assert_eq!(remainder.to_part_equation(), 2 * &x + 3);
+    }
+
+    #[test]
+    fn test_div_rem_3() {
+        let dividend = Polynomial::new('x', vec![Number::from(1), Number::from(1)]);
+        let divisor = Polynomial::new('x', vec![Number::from(3)]);
+
+        let (quotient, remainder) = dividend.div_rem(&divisor).unwrap();
+        assert_eq!(
+            quotient.coefficients(),
+            &[
+                Number::from(1) / Number::from(3),
+                Number::from(1) / Number::from(3)
+            ]
+        );
+        assert!(remainder.is_zero());
+
+        assert!(matches!(
+            dividend.div_rem(&Polynomial::new('x', Vec::new())),
+            Err(MathError::ZeroDivisionError)
+        ));
+        assert!(matches!(
+            dividend.div_rem(&Polynomial::new('y', vec![Number::from(1)])),
+            Err(MathError::EquationMismatchError)
+        ));
+    }
+
+    #[test]
+    fn test_gcd_1() {
+        let x: PartEquation = PartEquation::from('x');
+
+        let a = (x.pow(&PartEquation::from(2)) - 1)
+            .to_polynomial('x')
+            .unwrap();
+        let b = (x.pow(&PartEquation::from(2)) - &x * 2 + 1)
+            .to_polynomial('x')
+            .unwrap();
+        assert_eq!(a.gcd(&b).unwrap().to_part_equation(), &x - 1);
+
+        let a = (x.pow(&PartEquation::from(2)) * 2 + &x * 4)
+            .to_polynomial('x')
+            .unwrap();
+        let b = (&x * 3 + 6).to_polynomial('x').unwrap();
+        assert_eq!(a.gcd(&b).unwrap().to_part_equation(), &x + 2);
+
+        let a = (&x + 1).to_polynomial('x').unwrap();
+        let b = (&x + 2).to_polynomial('x').unwrap();
+        assert_eq!(a.gcd(&b).unwrap().degree(), Some(0));
+    }
+
+    #[test]
+    fn test_gcd_integers_1() {
+        assert_eq!(gcd(12, -18), 6);
+        assert_eq!(gcd(-7, 0), 7);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(gcd(i64::MIN, 0), 1 << 63);
+        assert_eq!(gcd(0, i64::MIN), 1 << 63);
+        assert_eq!(gcd(i64::MIN, -1), 1);
+        assert_eq!(gcd(i64::MIN, 6), 2);
+        assert_eq!(gcd(i64::MIN, i64::MIN), 1 << 63);
+    }
+}
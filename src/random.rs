@@ -0,0 +1,41 @@
+//! Pseudo-random numbers for generating test inputs, see `PartEquation::random`, and for the
+//! sample points of `PartEquation::numerically_equal`. Public with the `test-util` feature.
+//! Not suitable for anything that needs unpredictable values.
+
+/// Source of uniformly distributed 64 bit values
+pub trait Rng {
+    fn next_u64(&mut self) -> u64;
+
+    /// Uniform value in `0..n`, `n` must not be 0
+    fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n
+    }
+}
+
+/// xorshift64, the same sequence for the same seed
+#[derive(Debug, Clone)]
+pub struct XorShift {
+    state: u64,
+}
+
+impl XorShift {
+    /// A zero seed is replaced, the generator would only produce zeros
+    pub fn new(seed: u64) -> Self {
+        XorShift {
+            state: if seed == 0 {
+                0x9E37_79B9_7F4A_7C15
+            } else {
+                seed
+            },
+        }
+    }
+}
+
+impl Rng for XorShift {
+    fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+}